@@ -15,7 +15,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut char_to_name = Vec::new();
     for line in BufReader::new(File::open("data/glyphlist.txt")?)
         .lines()
-        .filter_map(|e| e.ok())
+        .map_while(Result::ok)
         .filter(|l| !l.starts_with('#'))
     {
         let fields: Vec<&str> = line.split(';').collect();
@@ -31,23 +31,60 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut font_names = Vec::new();
     let mut name_to_width = HashMap::new();
+    let mut name_to_code = HashMap::new();
     let mut output = String::new();
-    write!(output, "#![allow(non_snake_case)]\n")?;
-    write!(output, "#![allow(missing_docs)]\n")?;
+    // Fonts with their own encoding, such as Symbol, need to be written with the codes from
+    // their AFM instead of WinAnsi
+    let mut codes = String::new();
+    // Metrics of each font as a whole, from the keys before the character metrics
+    let mut metrics = String::new();
+    writeln!(output, "#![allow(non_snake_case)]")?;
+    writeln!(output, "#![allow(missing_docs)]")?;
+    writeln!(
+        output,
+        "#![allow(clippy::match_same_arms, clippy::too_many_lines)]"
+    )?;
 
-    write!(
+    writeln!(
         output,
-        "pub fn glyph_width(font: &Font, c: char) -> f64 {{\n    match font {{\n"
+        "pub fn glyph_width(font: &Font, c: char) -> f64 {{\n    match font {{"
     )?;
 
-    for entry in std::fs::read_dir(Path::new("data/Core14_AFMs"))?
+    // Sort the AFM files so the generated source doesn't depend on directory iteration order
+    let mut entries: Vec<_> = std::fs::read_dir(Path::new("data/Core14_AFMs"))?
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().unwrap().is_file())
         .filter(|e| e.file_name().to_str().unwrap().ends_with(".afm"))
-    {
+        .collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let font_specific = BufReader::new(File::open(entry.path())?)
+            .lines()
+            .map_while(Result::ok)
+            .any(|line| line == "EncodingScheme FontSpecific");
+        let header: HashMap<String, String> = BufReader::new(File::open(entry.path())?)
+            .lines()
+            .map_while(Result::ok)
+            .take_while(|line| !line.starts_with("StartCharMetrics"))
+            .filter_map(|line| {
+                let (key, value) = line.split_once(' ')?;
+                Some((key.to_owned(), value.trim().to_owned()))
+            })
+            .collect();
+        let header_value = |key: &str| -> Result<Option<f64>, std::num::ParseFloatError> {
+            header
+                .get(key)
+                .map(|value| value.parse::<f64>().map(|v| v / 1000.0))
+                .transpose()
+        };
+        let bbox = header["FontBBox"]
+            .split_whitespace()
+            .map(|v| v.parse::<f64>().map(|v| v / 1000.0))
+            .collect::<Result<Vec<f64>, _>>()?;
         for line in BufReader::new(File::open(entry.path())?)
             .lines()
-            .filter_map(|e| e.ok())
+            .map_while(Result::ok)
             .skip_while(|line| !line.starts_with("StartCharMetrics"))
             .skip(1)
             .take_while(|line| !line.starts_with("EndCharMetrics"))
@@ -56,44 +93,142 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let width: f64 = fields[4].parse()?;
             let name = fields[7];
             name_to_width.insert(name.to_owned(), width / 1000.0);
+            if let Ok(code) = fields[1].parse::<u8>() {
+                name_to_code.insert(name.to_owned(), code);
+            }
         }
 
-        let font_name = entry
+        let postscript_name = entry
             .file_name()
             .to_str()
             .unwrap()
             .split('.')
             .next()
             .unwrap()
-            .replace('-', "")
             .to_owned();
-        font_names.push(font_name.clone());
+        let font_name = postscript_name.replace('-', "");
+        font_names.push((font_name.clone(), postscript_name));
+
+        writeln!(
+            metrics,
+            "        Font::{} => FontGlobalMetrics {{\n            \
+             ascender: {:?},\n            \
+             descender: {:?},\n            \
+             cap_height: {:?},\n            \
+             x_height: {:?},\n            \
+             underline_position: {:?},\n            \
+             underline_thickness: {:?},\n            \
+             bbox: {:?},\n        \
+             }},",
+            font_name,
+            // Symbol fonts only have a bounding box
+            header_value("Ascender")?.unwrap_or(bbox[3]),
+            header_value("Descender")?.unwrap_or(bbox[1]),
+            header_value("CapHeight")?,
+            header_value("XHeight")?,
+            header_value("UnderlinePosition")?.unwrap_or(-0.1),
+            header_value("UnderlineThickness")?.unwrap_or(0.05),
+            bbox,
+        )?;
 
-        write!(output, "        &Font::{} => match c {{\n", font_name)?;
+        writeln!(output, "        Font::{} => match c {{", font_name)?;
 
         for &(chr, ref name) in &char_to_name {
             if let Some(&width) = name_to_width.get(name) {
                 if chr == '\'' || chr == '\\' {
-                    write!(output, "            '\\{}' => {:.2},\n", chr, width)?;
+                    writeln!(output, "            '\\{}' => {:.2},", chr, width)?;
                 } else {
-                    write!(output, "            '{}' => {:.2},\n", chr, width)?;
+                    writeln!(output, "            '{}' => {:.2},", chr, width)?;
                 }
             }
         }
-        write!(output, "            _ => 0.0,\n")?;
-        write!(output, "        }},\n")?;
+        writeln!(output, "            _ => 0.0,")?;
+        writeln!(output, "        }},")?;
         name_to_width.clear();
+
+        if font_specific {
+            writeln!(codes, "        Font::{} => match c {{", font_name)?;
+            for &(chr, ref name) in &char_to_name {
+                if let Some(&code) = name_to_code.get(name) {
+                    if chr == '\'' || chr == '\\' {
+                        writeln!(codes, "            '\\{}' => Some({}),", chr, code)?;
+                    } else {
+                        writeln!(codes, "            '{}' => Some({}),", chr, code)?;
+                    }
+                }
+            }
+            writeln!(codes, "            _ => None,")?;
+            writeln!(codes, "        }},")?;
+        }
+        name_to_code.clear();
     }
-    write!(output, "    }}\n")?;
-    write!(output, "}}\n\n")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}\n")?;
+
+    writeln!(
+        output,
+        "/// The code for `c` in `font`'s own encoding, for fonts which don't use `WinAnsi`\n\
+         pub fn builtin_code(font: &Font, c: char) -> Option<u8> {{\n    match font {{"
+    )?;
+    output.push_str(&codes);
+    writeln!(output, "        _ => None,")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}\n")?;
 
     // Write the font enum
-    write!(output, "#[derive(Clone, Debug, Eq, Hash, PartialEq)]\n")?;
-    write!(output, "pub enum Font {{\n")?;
-    for name in &font_names {
-        write!(output, "    {},\n", name)?;
+    writeln!(output, "#[derive(Clone, Debug, Eq, Hash, PartialEq)]")?;
+    writeln!(output, "pub enum Font {{")?;
+    for (name, _) in &font_names {
+        writeln!(output, "    {},", name)?;
     }
-    write!(output, "}}\n")?;
+    writeln!(output, "}}\n")?;
+
+    writeln!(
+        output,
+        "/// The font with the `PostScript` name `name`, such as `\"Times-Roman\"`\n\
+         pub fn font_from_name(name: &str) -> Option<Font> {{\n    match name {{"
+    )?;
+    for (name, postscript_name) in &font_names {
+        writeln!(
+            output,
+            "        \"{}\" => Some(Font::{}),",
+            postscript_name, name
+        )?;
+    }
+    writeln!(output, "        _ => None,")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}\n")?;
+
+    writeln!(
+        output,
+        "/// Measurements of a font as a whole, in units of the font size\n\
+         #[derive(Clone, Copy, Debug, PartialEq)]\n\
+         pub struct FontGlobalMetrics {{\n    \
+         /// The height of the tallest letters above the baseline, such as d\n    \
+         pub ascender: f64,\n    \
+         /// The depth of the lowest letters below the baseline, such as p, which is negative\n    \
+         pub descender: f64,\n    \
+         /// The height of flat capital letters such as H, which symbol fonts don't have\n    \
+         pub cap_height: Option<f64>,\n    \
+         /// The height of flat lowercase letters such as x, which symbol fonts don't have\n    \
+         pub x_height: Option<f64>,\n    \
+         /// The distance from the baseline to the middle of an underline, which is negative\n    \
+         pub underline_position: f64,\n    \
+         /// The thickness of an underline\n    \
+         pub underline_thickness: f64,\n    \
+         /// The left, bottom, right, and top edges of a box around every glyph\n    \
+         pub bbox: [f64; 4],\n\
+         }}\n"
+    )?;
+    writeln!(
+        output,
+        "/// The metrics of `font` as a whole, from its AFM file\n\
+         #[must_use]\n\
+         pub fn font_metrics(font: &Font) -> FontGlobalMetrics {{\n    match font {{"
+    )?;
+    output.push_str(&metrics);
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}")?;
 
     // Write to output file only if we need to
     let mut current_contents = String::new();
@@ -1,4 +1,4 @@
-///! Example program drawing mandalas on a page.
+//! Example program drawing mandalas on a page.
 use pdfpdf::{Color, Matrix, Pdf, Point, Size};
 use std::env;
 use std::f64::consts::PI;
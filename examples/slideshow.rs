@@ -29,14 +29,17 @@ impl Slideshow {
         background_color: Color,
         text_color: Color,
     ) -> Self {
+        let width = width.into();
+        let height = height.into();
         Slideshow {
-            width: width.into(),
-            height: height.into(),
+            width,
+            height,
             background_color,
             text_color,
             pdf: {
                 let mut pdf = Pdf::new();
-                pdf.compression(pdfpdf::Compression::Off);
+                pdf.compression(pdfpdf::Compression::Off)
+                    .set_default_page_size(Size { width, height });
                 pdf
             },
         }
@@ -45,10 +48,7 @@ impl Slideshow {
     pub fn add_title_slide(&mut self, text: &str) -> &mut Self {
         // init the new slide
         self.pdf
-            .add_page(Size {
-                width: self.width,
-                height: self.height,
-            })
+            .add_default_page()
             .set_color(self.background_color)
             .draw_rectangle_filled(
                 Point { x: 0, y: 0 },
@@ -73,10 +73,7 @@ impl Slideshow {
     pub fn add_text_slide(&mut self, text: &str) -> &mut Self {
         // init the new slide
         self.pdf
-            .add_page(Size {
-                width: self.width,
-                height: self.height,
-            })
+            .add_default_page()
             .set_color(self.background_color)
             .draw_rectangle_filled(
                 Point { x: 0, y: 0 },
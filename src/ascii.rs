@@ -0,0 +1,121 @@
+//! Encoders for the `/ASCII85Decode` and `/ASCIIHexDecode` filters.
+//!
+//! Both break their output into lines of at most 80 characters and end with the filter's
+//! end-of-data marker and a newline.
+
+const LINE_LENGTH: usize = 80;
+
+/// Append `c` to `out`, starting a new line if the current one is full
+fn push_wrapped(out: &mut Vec<u8>, line: &mut usize, c: u8) {
+    if *line == LINE_LENGTH {
+        out.push(b'\n');
+        *line = 0;
+    }
+    out.push(c);
+    *line += 1;
+}
+
+/// Encode each group of 4 bytes as 5 characters from `!` to `u`, or `z` for 4 zero bytes
+pub fn ascii85_encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() * 5 / 4 + data.len() / LINE_LENGTH + 4);
+    let mut line = 0;
+    for chunk in data.chunks(4) {
+        let mut group = [0; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+        if value == 0 && chunk.len() == 4 {
+            push_wrapped(&mut out, &mut line, b'z');
+            continue;
+        }
+        let mut digits = [0; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = b'!' + (value % 85) as u8;
+            value /= 85;
+        }
+        // A partial group of n bytes is written as its first n + 1 digits
+        for &digit in &digits[..=chunk.len()] {
+            push_wrapped(&mut out, &mut line, digit);
+        }
+    }
+    out.extend(b"~>\n");
+    out
+}
+
+/// Encode each byte as two hexadecimal digits
+pub fn hex_encode(data: &[u8]) -> Vec<u8> {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    let mut out = Vec::with_capacity(data.len() * 2 + data.len() / (LINE_LENGTH / 2) + 2);
+    let mut line = 0;
+    for &byte in data {
+        push_wrapped(&mut out, &mut line, DIGITS[usize::from(byte >> 4)]);
+        push_wrapped(&mut out, &mut line, DIGITS[usize::from(byte & 0xF)]);
+    }
+    out.extend(b">\n");
+    out
+}
+
+/// Decode the output of `ascii85_encode`
+#[cfg(any(test, feature = "recompress"))]
+pub fn ascii85_decode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut group = Vec::with_capacity(5);
+    for &c in data.iter().filter(|c| !c.is_ascii_whitespace()) {
+        match c {
+            b'~' => break,
+            b'z' => out.extend([0; 4]),
+            _ => {
+                group.push(u32::from(c - b'!'));
+                if group.len() == 5 {
+                    let value = group.iter().fold(0, |value, digit| value * 85 + digit);
+                    out.extend(value.to_be_bytes());
+                    group.clear();
+                }
+            }
+        }
+    }
+    if !group.is_empty() {
+        let len = group.len() - 1;
+        group.resize(5, 84);
+        let value = group.iter().fold(0, |value, digit| value * 85 + digit);
+        out.extend(&value.to_be_bytes()[..len]);
+    }
+    out
+}
+
+/// Decode the output of `hex_encode`, ignoring whitespace
+#[cfg(any(test, feature = "recompress"))]
+pub fn hex_decode(data: &[u8]) -> Vec<u8> {
+    let digits: Vec<u8> = data
+        .iter()
+        .take_while(|&&c| c != b'>')
+        .filter_map(|&c| match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            _ => None,
+        })
+        .collect();
+    // A missing last digit is zero
+    digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+        .collect()
+}
+
+#[test]
+fn test_ascii85_round_trip() {
+    assert_eq!(ascii85_encode(b""), b"~>\n");
+    assert_eq!(ascii85_encode(&[0, 0, 0, 0, 1]), b"z!<~>\n");
+    let data: Vec<u8> = (0..=255).cycle().take(1001).collect();
+    let encoded = ascii85_encode(&data);
+    assert!(encoded.split(|&c| c == b'\n').all(|line| line.len() <= 80));
+    assert_eq!(ascii85_decode(&encoded), data);
+}
+
+#[test]
+fn test_hex_encode() {
+    assert_eq!(hex_encode(&[0x00, 0x9F, 0xFF]), b"009FFF>\n");
+    let data: Vec<u8> = (0..=255).collect();
+    assert_eq!(hex_decode(&hex_encode(&data)), data);
+    assert_eq!(hex_decode(b"A 1 f>"), [0xA1, 0xF0]);
+}
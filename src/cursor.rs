@@ -0,0 +1,89 @@
+use crate::{Alignment, Pdf, Point};
+
+/// A position on the page that moves down as paragraphs are drawn, for flowing text from the
+/// top of a page towards the bottom
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextCursor {
+    x: f64,
+    y: f64,
+    width: f64,
+}
+
+impl TextCursor {
+    /// Start at `position`, the top-left corner of the first line, wrapping text to `width`
+    #[must_use]
+    pub fn new<X, Y>(position: Point<X, Y>, width: f64) -> Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        Self {
+            x: position.x,
+            y: position.y,
+            width,
+        }
+    }
+
+    /// Move to `position`, such as the top of a new page or column
+    pub fn move_to<X, Y>(&mut self, position: Point<X, Y>) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        self.x = position.x;
+        self.y = position.y;
+        self
+    }
+
+    /// Where the next paragraph will start
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> Point<f64, f64> {
+        Point {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    /// Draw `text` wrapped to the cursor's width with `leading` between baselines, then move
+    /// down past it and a further `space_after`
+    /// Returns `true` if the paragraph ran off the bottom of the page. It is drawn regardless,
+    /// so callers that want to break pages should check this before drawing anything else.
+    pub fn paragraph(&mut self, pdf: &mut Pdf, text: &str, leading: f64, space_after: f64) -> bool {
+        let lines = pdf.wrap_text(text, self.width);
+        for (l, line) in lines.iter().enumerate() {
+            let y = self.y - l as f64 * leading;
+            pdf.draw_text(Point { x: self.x, y }, Alignment::TopLeft, line);
+        }
+        let (_, font_size) = pdf.current_font();
+        let bottom = self.y - (lines.len() - 1) as f64 * leading - font_size;
+        self.y -= lines.len() as f64 * leading + space_after;
+        bottom < 0.0
+    }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_paragraphs_advance_cursor() {
+    use crate::Size;
+
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 400,
+    });
+    let mut cursor = TextCursor::new(Point { x: 20, y: 380 }, 160.0);
+    let first = "A paragraph long enough that it has to wrap onto a second line at least";
+    let second = "A short one";
+    let lines = pdf.wrap_text(first, 160.0).len() + pdf.wrap_text(second, 160.0).len();
+    assert!(lines >= 3);
+
+    assert!(!cursor.paragraph(&mut pdf, first, 14.0, 6.0));
+    assert!(!cursor.paragraph(&mut pdf, second, 14.0, 6.0));
+    assert_eq!(cursor.position().y, 380.0 - lines as f64 * 14.0 - 2.0 * 6.0);
+
+    cursor.move_to(Point { x: 20, y: 20 });
+    assert!(cursor.paragraph(&mut pdf, first, 14.0, 6.0));
+}
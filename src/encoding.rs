@@ -0,0 +1,161 @@
+//! The `WinAnsiEncoding` used for text drawn with the builtin fonts.
+
+use std::convert::TryFrom;
+
+/// The characters at 0x80..=0x9F, where `WinAnsiEncoding` differs from ISO 8859-1
+const HIGH_CONTROL_RANGE: [Option<char>; 32] = [
+    Some('€'),
+    None,
+    Some('‚'),
+    Some('ƒ'),
+    Some('„'),
+    Some('…'),
+    Some('†'),
+    Some('‡'),
+    Some('ˆ'),
+    Some('‰'),
+    Some('Š'),
+    Some('‹'),
+    Some('Œ'),
+    None,
+    Some('Ž'),
+    None,
+    None,
+    Some('‘'),
+    Some('’'),
+    Some('“'),
+    Some('”'),
+    Some('•'),
+    Some('–'),
+    Some('—'),
+    Some('˜'),
+    Some('™'),
+    Some('š'),
+    Some('›'),
+    Some('œ'),
+    None,
+    Some('ž'),
+    Some('Ÿ'),
+];
+
+/// Ligatures which can replace a sequence of letters, which is also the glyph's name, with the
+/// otherwise unused code that `encoding_dictionary` assigns to them
+/// Longer sequences come first so that "ffi" is not drawn as "ff" followed by "i".
+pub const LIGATURES: [(&str, char, u8); 5] = [
+    ("ffi", '\u{FB03}', 0x1E),
+    ("ffl", '\u{FB04}', 0x1F),
+    ("ff", '\u{FB00}', 0x1B),
+    ("fi", '\u{FB01}', 0x1C),
+    ("fl", '\u{FB02}', 0x1D),
+];
+
+/// The code that a ligature character is written with
+pub fn ligature_code(c: char) -> Option<u8> {
+    LIGATURES
+        .iter()
+        .find(|&&(_, ligature, _)| ligature == c)
+        .map(|&(_, _, code)| code)
+}
+
+/// The `/Encoding` dictionary for the builtin fonts, which is `WinAnsiEncoding` plus the ligatures
+pub fn encoding_dictionary() -> String {
+    use std::fmt::Write;
+
+    let mut differences = String::new();
+    let mut next_code = None;
+    let mut ligatures = LIGATURES;
+    ligatures.sort_by_key(|&(_, _, code)| code);
+    for (name, _, code) in ligatures {
+        if next_code != Some(code) {
+            let _ = write!(differences, " {code}");
+        }
+        let _ = write!(differences, " /{name}");
+        next_code = Some(code + 1);
+    }
+    format!("<< /Type /Encoding /BaseEncoding /WinAnsiEncoding /Differences [{differences} ] >>")
+}
+
+/// The Unicode character drawn for an encoded byte, if there is one
+pub fn win_ansi_to_unicode(code: u8) -> Option<char> {
+    match code {
+        0x20..=0x7E | 0xA0..=0xFF => Some(char::from(code)),
+        0x80..=0x9F => HIGH_CONTROL_RANGE[usize::from(code - 0x80)],
+        _ => None,
+    }
+}
+
+/// The encoded byte that draws `c`, if `WinAnsiEncoding` has it
+pub fn unicode_to_win_ansi(c: char) -> Option<u8> {
+    match u32::from(c) {
+        code @ (0x20..=0x7E | 0xA0..=0xFF) => u8::try_from(code).ok(),
+        _ => HIGH_CONTROL_RANGE
+            .iter()
+            .position(|&high| high == Some(c))
+            .and_then(|i| u8::try_from(0x80 + i).ok()),
+    }
+}
+
+/// Build a `/ToUnicode` character map that maps every encoded byte back to its Unicode code point
+pub fn to_unicode_cmap() -> Vec<u8> {
+    use std::fmt::Write;
+
+    let mut mappings: Vec<(u8, String)> = (0..=255_u8)
+        .filter_map(|code| {
+            win_ansi_to_unicode(code).map(|c| (code, format!("{:04X}", u32::from(c))))
+        })
+        .collect();
+    // Ligatures are extracted as the letters they replace
+    for (letters, _, code) in LIGATURES {
+        let mut utf16 = String::new();
+        for c in letters.chars() {
+            let _ = write!(utf16, "{:04X}", u32::from(c));
+        }
+        mappings.push((code, utf16));
+    }
+    mappings.sort();
+
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n\
+         12 dict begin\n\
+         begincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n\
+         /CMapType 2 def\n\
+         1 begincodespacerange\n<00> <FF>\nendcodespacerange\n",
+    );
+    // A bfchar block may contain at most 100 entries
+    for chunk in mappings.chunks(100) {
+        let _ = writeln!(cmap, "{} beginbfchar", chunk.len());
+        for (code, utf16) in chunk {
+            let _ = writeln!(cmap, "<{code:02X}> <{utf16}>");
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str(
+        "endcmap\n\
+         CMapName currentdict /CMap defineresource pop\n\
+         end\n\
+         end\n",
+    );
+    cmap.into_bytes()
+}
+
+#[test]
+fn test_win_ansi_high_range() {
+    assert_eq!(win_ansi_to_unicode(0x80), Some('€'));
+    assert_eq!(win_ansi_to_unicode(0x81), None);
+    assert_eq!(win_ansi_to_unicode(0xE9), Some('é'));
+    assert_eq!(unicode_to_win_ansi('…'), Some(0x85));
+    assert_eq!(unicode_to_win_ansi('é'), Some(0xE9));
+    assert_eq!(unicode_to_win_ansi('\u{81}'), None);
+}
+
+#[test]
+fn test_ligature_encoding() {
+    assert_eq!(ligature_code('\u{FB01}'), Some(0x1C));
+    assert_eq!(ligature_code('f'), None);
+    assert!(encoding_dictionary().contains("/Differences [ 27 /ff /fi /fl /ffi /ffl ]"));
+    assert!(String::from_utf8(to_unicode_cmap())
+        .unwrap()
+        .contains("<1C> <00660069>\n"));
+}
@@ -0,0 +1,56 @@
+//! Writing documents to the filesystem, which is only available with the `std` feature
+
+use crate::Pdf;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+impl Pdf {
+    /// Write the in-memory PDF representation to disk
+    /// Like `to_bytes`, this ends the current page and may be called repeatedly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn write_to<F>(&mut self, filename: F) -> io::Result<()>
+    where
+        F: AsRef<Path>,
+    {
+        let bytes = self.to_bytes();
+        File::create(filename)?.write_all(&bytes)
+    }
+}
+
+#[cfg(test)]
+use crate::{Point, Size};
+
+#[test]
+fn test_write_twice() {
+    let dir = std::env::temp_dir();
+    let first = dir.join("pdfpdf_test_write_twice_1.pdf");
+    let second = dir.join("pdfpdf_test_write_twice_2.pdf");
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_circle(Point { x: 50, y: 50 }, 10);
+    pdf.write_to(&first).unwrap();
+    pdf.write_to(&second).unwrap();
+    let first = std::fs::read(first).unwrap();
+    assert_eq!(first, std::fs::read(second).unwrap());
+
+    // The startxref offset points at the cross-reference table
+    let text = String::from_utf8_lossy(&first);
+    let startxref: usize = text
+        .rsplit("startxref\n")
+        .next()
+        .unwrap()
+        .trim_end_matches("%%EOF")
+        .trim()
+        .parse()
+        .unwrap();
+    // The catalog, page tree, content stream, ToUnicode map, and page make five objects
+    assert!(first[startxref..].starts_with(b"xref\n0 6\n"));
+    assert!(text.contains("/Count 1\n/Kids [5 0 R] >>"));
+}
@@ -1,326 +1,9 @@
 #![allow(non_snake_case)]
 #![allow(missing_docs)]
+#![allow(clippy::match_same_arms, clippy::too_many_lines)]
 pub fn glyph_width(font: &Font, c: char) -> f64 {
     match font {
-        &Font::TimesItalic => match c {
-            'A' => 0.61,
-            'Æ' => 0.89,
-            'Á' => 0.61,
-            'Ă' => 0.61,
-            'Â' => 0.61,
-            'Ä' => 0.61,
-            'À' => 0.61,
-            'Ā' => 0.61,
-            'Ą' => 0.61,
-            'Å' => 0.61,
-            'Ã' => 0.61,
-            'B' => 0.61,
-            'C' => 0.67,
-            'Ć' => 0.67,
-            'Č' => 0.67,
-            'Ç' => 0.67,
-            'D' => 0.72,
-            'Ď' => 0.72,
-            'Đ' => 0.72,
-            '∆' => 0.61,
-            'E' => 0.61,
-            'É' => 0.61,
-            'Ě' => 0.61,
-            'Ê' => 0.61,
-            'Ë' => 0.61,
-            'Ė' => 0.61,
-            'È' => 0.61,
-            'Ē' => 0.61,
-            'Ę' => 0.61,
-            'Ð' => 0.72,
-            '€' => 0.50,
-            'F' => 0.61,
-            'G' => 0.72,
-            'Ğ' => 0.72,
-            'Ģ' => 0.72,
-            'H' => 0.72,
-            'I' => 0.33,
-            'Í' => 0.33,
-            'Î' => 0.33,
-            'Ï' => 0.33,
-            'İ' => 0.33,
-            'Ì' => 0.33,
-            'Ī' => 0.33,
-            'Į' => 0.33,
-            'J' => 0.44,
-            'K' => 0.67,
-            'Ķ' => 0.67,
-            'L' => 0.56,
-            'Ĺ' => 0.56,
-            'Ľ' => 0.61,
-            'Ļ' => 0.56,
-            'Ł' => 0.56,
-            'M' => 0.83,
-            'N' => 0.67,
-            'Ń' => 0.67,
-            'Ň' => 0.67,
-            'Ņ' => 0.67,
-            'Ñ' => 0.67,
-            'O' => 0.72,
-            'Œ' => 0.94,
-            'Ó' => 0.72,
-            'Ô' => 0.72,
-            'Ö' => 0.72,
-            'Ò' => 0.72,
-            'Ő' => 0.72,
-            'Ō' => 0.72,
-            'Ø' => 0.72,
-            'Õ' => 0.72,
-            'P' => 0.61,
-            'Q' => 0.72,
-            'R' => 0.61,
-            'Ŕ' => 0.61,
-            'Ř' => 0.61,
-            'Ŗ' => 0.61,
-            'S' => 0.50,
-            'Ś' => 0.50,
-            'Š' => 0.50,
-            'Ş' => 0.50,
-            'Ș' => 0.50,
-            'T' => 0.56,
-            'Ť' => 0.56,
-            'Ţ' => 0.56,
-            'Þ' => 0.61,
-            'U' => 0.72,
-            'Ú' => 0.72,
-            'Û' => 0.72,
-            'Ü' => 0.72,
-            'Ù' => 0.72,
-            'Ű' => 0.72,
-            'Ū' => 0.72,
-            'Ų' => 0.72,
-            'Ů' => 0.72,
-            'V' => 0.61,
-            'W' => 0.83,
-            'X' => 0.61,
-            'Y' => 0.56,
-            'Ý' => 0.56,
-            'Ÿ' => 0.56,
-            'Z' => 0.56,
-            'Ź' => 0.56,
-            'Ž' => 0.56,
-            'Ż' => 0.56,
-            'a' => 0.50,
-            'á' => 0.50,
-            'ă' => 0.50,
-            'â' => 0.50,
-            '´' => 0.33,
-            'ä' => 0.50,
-            'æ' => 0.67,
-            'à' => 0.50,
-            'ā' => 0.50,
-            '&' => 0.78,
-            'ą' => 0.50,
-            'å' => 0.50,
-            '^' => 0.42,
-            '~' => 0.54,
-            '*' => 0.50,
-            '@' => 0.92,
-            'ã' => 0.50,
-            'b' => 0.50,
-            '\\' => 0.28,
-            '|' => 0.28,
-            '{' => 0.40,
-            '}' => 0.40,
-            '[' => 0.39,
-            ']' => 0.39,
-            '˘' => 0.33,
-            '¦' => 0.28,
-            '•' => 0.35,
-            'c' => 0.44,
-            'ć' => 0.44,
-            'ˇ' => 0.33,
-            'č' => 0.44,
-            'ç' => 0.44,
-            '¸' => 0.33,
-            '¢' => 0.50,
-            'ˆ' => 0.33,
-            ':' => 0.33,
-            ',' => 0.25,
-            '' => 0.25,
-            '©' => 0.76,
-            '¤' => 0.50,
-            'd' => 0.50,
-            '†' => 0.50,
-            '‡' => 0.50,
-            'ď' => 0.54,
-            'đ' => 0.50,
-            '°' => 0.40,
-            '¨' => 0.33,
-            '÷' => 0.68,
-            '$' => 0.50,
-            '˙' => 0.33,
-            'ı' => 0.28,
-            'e' => 0.44,
-            'é' => 0.44,
-            'ě' => 0.44,
-            'ê' => 0.44,
-            'ë' => 0.44,
-            'ė' => 0.44,
-            'è' => 0.44,
-            '8' => 0.50,
-            '…' => 0.89,
-            'ē' => 0.44,
-            '—' => 0.89,
-            '–' => 0.50,
-            'ę' => 0.44,
-            '=' => 0.68,
-            'ð' => 0.50,
-            '!' => 0.33,
-            '¡' => 0.39,
-            'f' => 0.28,
-            'ﬁ' => 0.50,
-            '5' => 0.50,
-            'ﬂ' => 0.50,
-            'ƒ' => 0.50,
-            '4' => 0.50,
-            '⁄' => 0.17,
-            'g' => 0.50,
-            'ğ' => 0.50,
-            'ģ' => 0.50,
-            'ß' => 0.50,
-            '`' => 0.33,
-            '>' => 0.68,
-            '≥' => 0.55,
-            '«' => 0.50,
-            '»' => 0.50,
-            '‹' => 0.33,
-            '›' => 0.33,
-            'h' => 0.50,
-            '˝' => 0.33,
-            '-' => 0.33,
-            'i' => 0.28,
-            'í' => 0.28,
-            'î' => 0.28,
-            'ï' => 0.28,
-            'ì' => 0.28,
-            'ī' => 0.28,
-            'į' => 0.28,
-            'j' => 0.28,
-            'k' => 0.44,
-            'ķ' => 0.44,
-            'l' => 0.28,
-            'ĺ' => 0.28,
-            'ľ' => 0.30,
-            'ļ' => 0.28,
-            '<' => 0.68,
-            '≤' => 0.55,
-            '¬' => 0.68,
-            '◊' => 0.47,
-            'ł' => 0.28,
-            'm' => 0.72,
-            '¯' => 0.33,
-            '−' => 0.68,
-            'µ' => 0.50,
-            '×' => 0.68,
-            'n' => 0.50,
-            'ń' => 0.50,
-            'ň' => 0.50,
-            'ņ' => 0.50,
-            '9' => 0.50,
-            '≠' => 0.55,
-            'ñ' => 0.50,
-            '#' => 0.50,
-            'o' => 0.50,
-            'ó' => 0.50,
-            'ô' => 0.50,
-            'ö' => 0.50,
-            'œ' => 0.67,
-            '˛' => 0.33,
-            'ò' => 0.50,
-            'ő' => 0.50,
-            'ō' => 0.50,
-            '1' => 0.50,
-            '½' => 0.75,
-            '¼' => 0.75,
-            '¹' => 0.30,
-            'ª' => 0.28,
-            'º' => 0.31,
-            'ø' => 0.50,
-            'õ' => 0.50,
-            'p' => 0.50,
-            '¶' => 0.52,
-            '(' => 0.33,
-            ')' => 0.33,
-            '∂' => 0.48,
-            '%' => 0.83,
-            '.' => 0.25,
-            '·' => 0.25,
-            '‰' => 1.00,
-            '+' => 0.68,
-            '±' => 0.68,
-            'q' => 0.50,
-            '?' => 0.50,
-            '¿' => 0.50,
-            '"' => 0.42,
-            '„' => 0.56,
-            '“' => 0.56,
-            '”' => 0.56,
-            '‘' => 0.33,
-            '’' => 0.33,
-            '‚' => 0.33,
-            '\'' => 0.21,
-            'r' => 0.39,
-            'ŕ' => 0.39,
-            '√' => 0.45,
-            'ř' => 0.39,
-            'ŗ' => 0.39,
-            '®' => 0.76,
-            '˚' => 0.33,
-            's' => 0.39,
-            'ś' => 0.39,
-            'š' => 0.39,
-            'ş' => 0.39,
-            'ș' => 0.39,
-            '§' => 0.50,
-            ';' => 0.33,
-            '7' => 0.50,
-            '6' => 0.50,
-            '/' => 0.28,
-            ' ' => 0.25,
-            '£' => 0.50,
-            '∑' => 0.60,
-            't' => 0.28,
-            'ť' => 0.30,
-            'ţ' => 0.28,
-            'þ' => 0.50,
-            '3' => 0.50,
-            '¾' => 0.75,
-            '³' => 0.30,
-            '˜' => 0.33,
-            '™' => 0.98,
-            '2' => 0.50,
-            '²' => 0.30,
-            'u' => 0.50,
-            'ú' => 0.50,
-            'û' => 0.50,
-            'ü' => 0.50,
-            'ù' => 0.50,
-            'ű' => 0.50,
-            'ū' => 0.50,
-            '_' => 0.50,
-            'ų' => 0.50,
-            'ů' => 0.50,
-            'v' => 0.44,
-            'w' => 0.67,
-            'x' => 0.44,
-            'y' => 0.44,
-            'ý' => 0.44,
-            'ÿ' => 0.44,
-            '¥' => 0.50,
-            'z' => 0.39,
-            'ź' => 0.39,
-            'ž' => 0.39,
-            'ż' => 0.39,
-            '0' => 0.50,
-            _ => 0.0,
-        },
-        &Font::CourierBold => match c {
+        Font::CourierBold => match c {
             'A' => 0.60,
             'Æ' => 0.60,
             'Á' => 0.60,
@@ -638,7 +321,7 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '0' => 0.60,
             _ => 0.0,
         },
-        &Font::CourierBoldOblique => match c {
+        Font::CourierBoldOblique => match c {
             'A' => 0.60,
             'Æ' => 0.60,
             'Á' => 0.60,
@@ -956,7 +639,7 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '0' => 0.60,
             _ => 0.0,
         },
-        &Font::CourierOblique => match c {
+        Font::CourierOblique => match c {
             'A' => 0.60,
             'Æ' => 0.60,
             'Á' => 0.60,
@@ -1274,7 +957,7 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '0' => 0.60,
             _ => 0.0,
         },
-        &Font::Courier => match c {
+        Font::Courier => match c {
             'A' => 0.60,
             'Æ' => 0.60,
             'Á' => 0.60,
@@ -1592,9 +1275,9 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '0' => 0.60,
             _ => 0.0,
         },
-        &Font::TimesRoman => match c {
+        Font::HelveticaBold => match c {
             'A' => 0.72,
-            'Æ' => 0.89,
+            'Æ' => 1.00,
             'Á' => 0.72,
             'Ă' => 0.72,
             'Â' => 0.72,
@@ -1604,40 +1287,40 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ą' => 0.72,
             'Å' => 0.72,
             'Ã' => 0.72,
-            'B' => 0.67,
-            'C' => 0.67,
-            'Ć' => 0.67,
-            'Č' => 0.67,
-            'Ç' => 0.67,
+            'B' => 0.72,
+            'C' => 0.72,
+            'Ć' => 0.72,
+            'Č' => 0.72,
+            'Ç' => 0.72,
             'D' => 0.72,
             'Ď' => 0.72,
             'Đ' => 0.72,
             '∆' => 0.61,
-            'E' => 0.61,
-            'É' => 0.61,
-            'Ě' => 0.61,
-            'Ê' => 0.61,
-            'Ë' => 0.61,
-            'Ė' => 0.61,
-            'È' => 0.61,
-            'Ē' => 0.61,
-            'Ę' => 0.61,
+            'E' => 0.67,
+            'É' => 0.67,
+            'Ě' => 0.67,
+            'Ê' => 0.67,
+            'Ë' => 0.67,
+            'Ė' => 0.67,
+            'È' => 0.67,
+            'Ē' => 0.67,
+            'Ę' => 0.67,
             'Ð' => 0.72,
-            '€' => 0.50,
-            'F' => 0.56,
-            'G' => 0.72,
-            'Ğ' => 0.72,
-            'Ģ' => 0.72,
+            '€' => 0.56,
+            'F' => 0.61,
+            'G' => 0.78,
+            'Ğ' => 0.78,
+            'Ģ' => 0.78,
             'H' => 0.72,
-            'I' => 0.33,
-            'Í' => 0.33,
-            'Î' => 0.33,
-            'Ï' => 0.33,
-            'İ' => 0.33,
-            'Ì' => 0.33,
-            'Ī' => 0.33,
-            'Į' => 0.33,
-            'J' => 0.39,
+            'I' => 0.28,
+            'Í' => 0.28,
+            'Î' => 0.28,
+            'Ï' => 0.28,
+            'İ' => 0.28,
+            'Ì' => 0.28,
+            'Ī' => 0.28,
+            'Į' => 0.28,
+            'J' => 0.56,
             'K' => 0.72,
             'Ķ' => 0.72,
             'L' => 0.61,
@@ -1645,37 +1328,37 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ľ' => 0.61,
             'Ļ' => 0.61,
             'Ł' => 0.61,
-            'M' => 0.89,
+            'M' => 0.83,
             'N' => 0.72,
             'Ń' => 0.72,
             'Ň' => 0.72,
             'Ņ' => 0.72,
             'Ñ' => 0.72,
-            'O' => 0.72,
-            'Œ' => 0.89,
-            'Ó' => 0.72,
-            'Ô' => 0.72,
-            'Ö' => 0.72,
-            'Ò' => 0.72,
-            'Ő' => 0.72,
-            'Ō' => 0.72,
-            'Ø' => 0.72,
-            'Õ' => 0.72,
-            'P' => 0.56,
-            'Q' => 0.72,
-            'R' => 0.67,
-            'Ŕ' => 0.67,
-            'Ř' => 0.67,
-            'Ŗ' => 0.67,
-            'S' => 0.56,
-            'Ś' => 0.56,
-            'Š' => 0.56,
-            'Ş' => 0.56,
-            'Ș' => 0.56,
+            'O' => 0.78,
+            'Œ' => 1.00,
+            'Ó' => 0.78,
+            'Ô' => 0.78,
+            'Ö' => 0.78,
+            'Ò' => 0.78,
+            'Ő' => 0.78,
+            'Ō' => 0.78,
+            'Ø' => 0.78,
+            'Õ' => 0.78,
+            'P' => 0.67,
+            'Q' => 0.78,
+            'R' => 0.72,
+            'Ŕ' => 0.72,
+            'Ř' => 0.72,
+            'Ŗ' => 0.72,
+            'S' => 0.67,
+            'Ś' => 0.67,
+            'Š' => 0.67,
+            'Ş' => 0.67,
+            'Ș' => 0.67,
             'T' => 0.61,
             'Ť' => 0.61,
             'Ţ' => 0.61,
-            'Þ' => 0.56,
+            'Þ' => 0.67,
             'U' => 0.72,
             'Ú' => 0.72,
             'Û' => 0.72,
@@ -1685,103 +1368,103 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ū' => 0.72,
             'Ų' => 0.72,
             'Ů' => 0.72,
-            'V' => 0.72,
+            'V' => 0.67,
             'W' => 0.94,
-            'X' => 0.72,
-            'Y' => 0.72,
-            'Ý' => 0.72,
-            'Ÿ' => 0.72,
+            'X' => 0.67,
+            'Y' => 0.67,
+            'Ý' => 0.67,
+            'Ÿ' => 0.67,
             'Z' => 0.61,
             'Ź' => 0.61,
             'Ž' => 0.61,
             'Ż' => 0.61,
-            'a' => 0.44,
-            'á' => 0.44,
-            'ă' => 0.44,
-            'â' => 0.44,
+            'a' => 0.56,
+            'á' => 0.56,
+            'ă' => 0.56,
+            'â' => 0.56,
             '´' => 0.33,
-            'ä' => 0.44,
-            'æ' => 0.67,
-            'à' => 0.44,
-            'ā' => 0.44,
-            '&' => 0.78,
-            'ą' => 0.44,
-            'å' => 0.44,
-            '^' => 0.47,
-            '~' => 0.54,
-            '*' => 0.50,
-            '@' => 0.92,
-            'ã' => 0.44,
-            'b' => 0.50,
+            'ä' => 0.56,
+            'æ' => 0.89,
+            'à' => 0.56,
+            'ā' => 0.56,
+            '&' => 0.72,
+            'ą' => 0.56,
+            'å' => 0.56,
+            '^' => 0.58,
+            '~' => 0.58,
+            '*' => 0.39,
+            '@' => 0.97,
+            'ã' => 0.56,
+            'b' => 0.61,
             '\\' => 0.28,
-            '|' => 0.20,
-            '{' => 0.48,
-            '}' => 0.48,
+            '|' => 0.28,
+            '{' => 0.39,
+            '}' => 0.39,
             '[' => 0.33,
             ']' => 0.33,
             '˘' => 0.33,
-            '¦' => 0.20,
+            '¦' => 0.28,
             '•' => 0.35,
-            'c' => 0.44,
-            'ć' => 0.44,
+            'c' => 0.56,
+            'ć' => 0.56,
             'ˇ' => 0.33,
-            'č' => 0.44,
-            'ç' => 0.44,
+            'č' => 0.56,
+            'ç' => 0.56,
             '¸' => 0.33,
-            '¢' => 0.50,
+            '¢' => 0.56,
             'ˆ' => 0.33,
-            ':' => 0.28,
-            ',' => 0.25,
+            ':' => 0.33,
+            ',' => 0.28,
             '' => 0.25,
-            '©' => 0.76,
-            '¤' => 0.50,
-            'd' => 0.50,
-            '†' => 0.50,
-            '‡' => 0.50,
-            'ď' => 0.59,
-            'đ' => 0.50,
+            '©' => 0.74,
+            '¤' => 0.56,
+            'd' => 0.61,
+            '†' => 0.56,
+            '‡' => 0.56,
+            'ď' => 0.74,
+            'đ' => 0.61,
             '°' => 0.40,
             '¨' => 0.33,
-            '÷' => 0.56,
-            '$' => 0.50,
+            '÷' => 0.58,
+            '$' => 0.56,
             '˙' => 0.33,
             'ı' => 0.28,
-            'e' => 0.44,
-            'é' => 0.44,
-            'ě' => 0.44,
-            'ê' => 0.44,
-            'ë' => 0.44,
-            'ė' => 0.44,
-            'è' => 0.44,
-            '8' => 0.50,
+            'e' => 0.56,
+            'é' => 0.56,
+            'ě' => 0.56,
+            'ê' => 0.56,
+            'ë' => 0.56,
+            'ė' => 0.56,
+            'è' => 0.56,
+            '8' => 0.56,
             '…' => 1.00,
-            'ē' => 0.44,
+            'ē' => 0.56,
             '—' => 1.00,
-            '–' => 0.50,
-            'ę' => 0.44,
-            '=' => 0.56,
-            'ð' => 0.50,
+            '–' => 0.56,
+            'ę' => 0.56,
+            '=' => 0.58,
+            'ð' => 0.61,
             '!' => 0.33,
             '¡' => 0.33,
             'f' => 0.33,
-            'ﬁ' => 0.56,
-            '5' => 0.50,
-            'ﬂ' => 0.56,
-            'ƒ' => 0.50,
-            '4' => 0.50,
+            'ﬁ' => 0.61,
+            '5' => 0.56,
+            'ﬂ' => 0.61,
+            'ƒ' => 0.56,
+            '4' => 0.56,
             '⁄' => 0.17,
-            'g' => 0.50,
-            'ğ' => 0.50,
-            'ģ' => 0.50,
-            'ß' => 0.50,
+            'g' => 0.61,
+            'ğ' => 0.61,
+            'ģ' => 0.61,
+            'ß' => 0.61,
             '`' => 0.33,
-            '>' => 0.56,
+            '>' => 0.58,
             '≥' => 0.55,
-            '«' => 0.50,
-            '»' => 0.50,
+            '«' => 0.56,
+            '»' => 0.56,
             '‹' => 0.33,
             '›' => 0.33,
-            'h' => 0.50,
+            'h' => 0.61,
             '˝' => 0.33,
             '-' => 0.33,
             'i' => 0.28,
@@ -1792,141 +1475,141 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ī' => 0.28,
             'į' => 0.28,
             'j' => 0.28,
-            'k' => 0.50,
-            'ķ' => 0.50,
+            'k' => 0.56,
+            'ķ' => 0.56,
             'l' => 0.28,
             'ĺ' => 0.28,
-            'ľ' => 0.34,
+            'ľ' => 0.40,
             'ļ' => 0.28,
-            '<' => 0.56,
+            '<' => 0.58,
             '≤' => 0.55,
-            '¬' => 0.56,
-            '◊' => 0.47,
+            '¬' => 0.58,
+            '◊' => 0.49,
             'ł' => 0.28,
-            'm' => 0.78,
+            'm' => 0.89,
             '¯' => 0.33,
-            '−' => 0.56,
-            'µ' => 0.50,
-            '×' => 0.56,
-            'n' => 0.50,
-            'ń' => 0.50,
-            'ň' => 0.50,
-            'ņ' => 0.50,
-            '9' => 0.50,
+            '−' => 0.58,
+            'µ' => 0.61,
+            '×' => 0.58,
+            'n' => 0.61,
+            'ń' => 0.61,
+            'ň' => 0.61,
+            'ņ' => 0.61,
+            '9' => 0.56,
             '≠' => 0.55,
-            'ñ' => 0.50,
-            '#' => 0.50,
-            'o' => 0.50,
-            'ó' => 0.50,
-            'ô' => 0.50,
-            'ö' => 0.50,
-            'œ' => 0.72,
-            '˛' => 0.33,
-            'ò' => 0.50,
-            'ő' => 0.50,
-            'ō' => 0.50,
-            '1' => 0.50,
-            '½' => 0.75,
-            '¼' => 0.75,
-            '¹' => 0.30,
-            'ª' => 0.28,
-            'º' => 0.31,
-            'ø' => 0.50,
-            'õ' => 0.50,
-            'p' => 0.50,
-            '¶' => 0.45,
+            'ñ' => 0.61,
+            '#' => 0.56,
+            'o' => 0.61,
+            'ó' => 0.61,
+            'ô' => 0.61,
+            'ö' => 0.61,
+            'œ' => 0.94,
+            '˛' => 0.33,
+            'ò' => 0.61,
+            'ő' => 0.61,
+            'ō' => 0.61,
+            '1' => 0.56,
+            '½' => 0.83,
+            '¼' => 0.83,
+            '¹' => 0.33,
+            'ª' => 0.37,
+            'º' => 0.36,
+            'ø' => 0.61,
+            'õ' => 0.61,
+            'p' => 0.61,
+            '¶' => 0.56,
             '(' => 0.33,
             ')' => 0.33,
-            '∂' => 0.48,
-            '%' => 0.83,
-            '.' => 0.25,
-            '·' => 0.25,
+            '∂' => 0.49,
+            '%' => 0.89,
+            '.' => 0.28,
+            '·' => 0.28,
             '‰' => 1.00,
-            '+' => 0.56,
-            '±' => 0.56,
-            'q' => 0.50,
-            '?' => 0.44,
-            '¿' => 0.44,
-            '"' => 0.41,
-            '„' => 0.44,
-            '“' => 0.44,
-            '”' => 0.44,
-            '‘' => 0.33,
-            '’' => 0.33,
-            '‚' => 0.33,
-            '\'' => 0.18,
-            'r' => 0.33,
-            'ŕ' => 0.33,
-            '√' => 0.45,
-            'ř' => 0.33,
-            'ŗ' => 0.33,
-            '®' => 0.76,
+            '+' => 0.58,
+            '±' => 0.58,
+            'q' => 0.61,
+            '?' => 0.61,
+            '¿' => 0.61,
+            '"' => 0.47,
+            '„' => 0.50,
+            '“' => 0.50,
+            '”' => 0.50,
+            '‘' => 0.28,
+            '’' => 0.28,
+            '‚' => 0.28,
+            '\'' => 0.24,
+            'r' => 0.39,
+            'ŕ' => 0.39,
+            '√' => 0.55,
+            'ř' => 0.39,
+            'ŗ' => 0.39,
+            '®' => 0.74,
             '˚' => 0.33,
-            's' => 0.39,
-            'ś' => 0.39,
-            'š' => 0.39,
-            'ş' => 0.39,
-            'ș' => 0.39,
-            '§' => 0.50,
-            ';' => 0.28,
-            '7' => 0.50,
-            '6' => 0.50,
+            's' => 0.56,
+            'ś' => 0.56,
+            'š' => 0.56,
+            'ş' => 0.56,
+            'ș' => 0.56,
+            '§' => 0.56,
+            ';' => 0.33,
+            '7' => 0.56,
+            '6' => 0.56,
             '/' => 0.28,
-            ' ' => 0.25,
-            '£' => 0.50,
+            ' ' => 0.28,
+            '£' => 0.56,
             '∑' => 0.60,
-            't' => 0.28,
-            'ť' => 0.33,
-            'ţ' => 0.28,
-            'þ' => 0.50,
-            '3' => 0.50,
-            '¾' => 0.75,
-            '³' => 0.30,
+            't' => 0.33,
+            'ť' => 0.39,
+            'ţ' => 0.33,
+            'þ' => 0.61,
+            '3' => 0.56,
+            '¾' => 0.83,
+            '³' => 0.33,
             '˜' => 0.33,
-            '™' => 0.98,
-            '2' => 0.50,
-            '²' => 0.30,
-            'u' => 0.50,
-            'ú' => 0.50,
-            'û' => 0.50,
-            'ü' => 0.50,
-            'ù' => 0.50,
-            'ű' => 0.50,
-            'ū' => 0.50,
-            '_' => 0.50,
-            'ų' => 0.50,
-            'ů' => 0.50,
-            'v' => 0.50,
-            'w' => 0.72,
-            'x' => 0.50,
-            'y' => 0.50,
-            'ý' => 0.50,
-            'ÿ' => 0.50,
-            '¥' => 0.50,
-            'z' => 0.44,
-            'ź' => 0.44,
-            'ž' => 0.44,
-            'ż' => 0.44,
-            '0' => 0.50,
+            '™' => 1.00,
+            '2' => 0.56,
+            '²' => 0.33,
+            'u' => 0.61,
+            'ú' => 0.61,
+            'û' => 0.61,
+            'ü' => 0.61,
+            'ù' => 0.61,
+            'ű' => 0.61,
+            'ū' => 0.61,
+            '_' => 0.56,
+            'ų' => 0.61,
+            'ů' => 0.61,
+            'v' => 0.56,
+            'w' => 0.78,
+            'x' => 0.56,
+            'y' => 0.56,
+            'ý' => 0.56,
+            'ÿ' => 0.56,
+            '¥' => 0.56,
+            'z' => 0.50,
+            'ź' => 0.50,
+            'ž' => 0.50,
+            'ż' => 0.50,
+            '0' => 0.56,
             _ => 0.0,
         },
-        &Font::TimesBoldItalic => match c {
-            'A' => 0.67,
-            'Æ' => 0.94,
-            'Á' => 0.67,
-            'Ă' => 0.67,
-            'Â' => 0.67,
-            'Ä' => 0.67,
-            'À' => 0.67,
-            'Ā' => 0.67,
-            'Ą' => 0.67,
-            'Å' => 0.67,
-            'Ã' => 0.67,
-            'B' => 0.67,
-            'C' => 0.67,
-            'Ć' => 0.67,
-            'Č' => 0.67,
-            'Ç' => 0.67,
+        Font::HelveticaBoldOblique => match c {
+            'A' => 0.72,
+            'Æ' => 1.00,
+            'Á' => 0.72,
+            'Ă' => 0.72,
+            'Â' => 0.72,
+            'Ä' => 0.72,
+            'À' => 0.72,
+            'Ā' => 0.72,
+            'Ą' => 0.72,
+            'Å' => 0.72,
+            'Ã' => 0.72,
+            'B' => 0.72,
+            'C' => 0.72,
+            'Ć' => 0.72,
+            'Č' => 0.72,
+            'Ç' => 0.72,
             'D' => 0.72,
             'Ď' => 0.72,
             'Đ' => 0.72,
@@ -1941,59 +1624,59 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ē' => 0.67,
             'Ę' => 0.67,
             'Ð' => 0.72,
-            '€' => 0.50,
-            'F' => 0.67,
-            'G' => 0.72,
-            'Ğ' => 0.72,
-            'Ģ' => 0.72,
-            'H' => 0.78,
-            'I' => 0.39,
-            'Í' => 0.39,
-            'Î' => 0.39,
-            'Ï' => 0.39,
-            'İ' => 0.39,
-            'Ì' => 0.39,
-            'Ī' => 0.39,
-            'Į' => 0.39,
-            'J' => 0.50,
-            'K' => 0.67,
-            'Ķ' => 0.67,
+            '€' => 0.56,
+            'F' => 0.61,
+            'G' => 0.78,
+            'Ğ' => 0.78,
+            'Ģ' => 0.78,
+            'H' => 0.72,
+            'I' => 0.28,
+            'Í' => 0.28,
+            'Î' => 0.28,
+            'Ï' => 0.28,
+            'İ' => 0.28,
+            'Ì' => 0.28,
+            'Ī' => 0.28,
+            'Į' => 0.28,
+            'J' => 0.56,
+            'K' => 0.72,
+            'Ķ' => 0.72,
             'L' => 0.61,
             'Ĺ' => 0.61,
             'Ľ' => 0.61,
             'Ļ' => 0.61,
             'Ł' => 0.61,
-            'M' => 0.89,
+            'M' => 0.83,
             'N' => 0.72,
             'Ń' => 0.72,
             'Ň' => 0.72,
             'Ņ' => 0.72,
             'Ñ' => 0.72,
-            'O' => 0.72,
-            'Œ' => 0.94,
-            'Ó' => 0.72,
-            'Ô' => 0.72,
-            'Ö' => 0.72,
-            'Ò' => 0.72,
-            'Ő' => 0.72,
-            'Ō' => 0.72,
-            'Ø' => 0.72,
-            'Õ' => 0.72,
-            'P' => 0.61,
-            'Q' => 0.72,
-            'R' => 0.67,
-            'Ŕ' => 0.67,
-            'Ř' => 0.67,
-            'Ŗ' => 0.67,
-            'S' => 0.56,
-            'Ś' => 0.56,
-            'Š' => 0.56,
-            'Ş' => 0.56,
-            'Ș' => 0.56,
+            'O' => 0.78,
+            'Œ' => 1.00,
+            'Ó' => 0.78,
+            'Ô' => 0.78,
+            'Ö' => 0.78,
+            'Ò' => 0.78,
+            'Ő' => 0.78,
+            'Ō' => 0.78,
+            'Ø' => 0.78,
+            'Õ' => 0.78,
+            'P' => 0.67,
+            'Q' => 0.78,
+            'R' => 0.72,
+            'Ŕ' => 0.72,
+            'Ř' => 0.72,
+            'Ŗ' => 0.72,
+            'S' => 0.67,
+            'Ś' => 0.67,
+            'Š' => 0.67,
+            'Ş' => 0.67,
+            'Ș' => 0.67,
             'T' => 0.61,
             'Ť' => 0.61,
             'Ţ' => 0.61,
-            'Þ' => 0.61,
+            'Þ' => 0.67,
             'U' => 0.72,
             'Ú' => 0.72,
             'Û' => 0.72,
@@ -2004,102 +1687,102 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ų' => 0.72,
             'Ů' => 0.72,
             'V' => 0.67,
-            'W' => 0.89,
+            'W' => 0.94,
             'X' => 0.67,
-            'Y' => 0.61,
-            'Ý' => 0.61,
-            'Ÿ' => 0.61,
+            'Y' => 0.67,
+            'Ý' => 0.67,
+            'Ÿ' => 0.67,
             'Z' => 0.61,
             'Ź' => 0.61,
             'Ž' => 0.61,
             'Ż' => 0.61,
-            'a' => 0.50,
-            'á' => 0.50,
-            'ă' => 0.50,
-            'â' => 0.50,
+            'a' => 0.56,
+            'á' => 0.56,
+            'ă' => 0.56,
+            'â' => 0.56,
             '´' => 0.33,
-            'ä' => 0.50,
-            'æ' => 0.72,
-            'à' => 0.50,
-            'ā' => 0.50,
-            '&' => 0.78,
-            'ą' => 0.50,
-            'å' => 0.50,
-            '^' => 0.57,
-            '~' => 0.57,
-            '*' => 0.50,
-            '@' => 0.83,
-            'ã' => 0.50,
-            'b' => 0.50,
+            'ä' => 0.56,
+            'æ' => 0.89,
+            'à' => 0.56,
+            'ā' => 0.56,
+            '&' => 0.72,
+            'ą' => 0.56,
+            'å' => 0.56,
+            '^' => 0.58,
+            '~' => 0.58,
+            '*' => 0.39,
+            '@' => 0.97,
+            'ã' => 0.56,
+            'b' => 0.61,
             '\\' => 0.28,
-            '|' => 0.22,
-            '{' => 0.35,
-            '}' => 0.35,
+            '|' => 0.28,
+            '{' => 0.39,
+            '}' => 0.39,
             '[' => 0.33,
             ']' => 0.33,
             '˘' => 0.33,
-            '¦' => 0.22,
+            '¦' => 0.28,
             '•' => 0.35,
-            'c' => 0.44,
-            'ć' => 0.44,
+            'c' => 0.56,
+            'ć' => 0.56,
             'ˇ' => 0.33,
-            'č' => 0.44,
-            'ç' => 0.44,
+            'č' => 0.56,
+            'ç' => 0.56,
             '¸' => 0.33,
-            '¢' => 0.50,
+            '¢' => 0.56,
             'ˆ' => 0.33,
             ':' => 0.33,
-            ',' => 0.25,
+            ',' => 0.28,
             '' => 0.25,
-            '©' => 0.75,
-            '¤' => 0.50,
-            'd' => 0.50,
-            '†' => 0.50,
-            '‡' => 0.50,
-            'ď' => 0.61,
-            'đ' => 0.50,
+            '©' => 0.74,
+            '¤' => 0.56,
+            'd' => 0.61,
+            '†' => 0.56,
+            '‡' => 0.56,
+            'ď' => 0.74,
+            'đ' => 0.61,
             '°' => 0.40,
             '¨' => 0.33,
-            '÷' => 0.57,
-            '$' => 0.50,
+            '÷' => 0.58,
+            '$' => 0.56,
             '˙' => 0.33,
             'ı' => 0.28,
-            'e' => 0.44,
-            'é' => 0.44,
-            'ě' => 0.44,
-            'ê' => 0.44,
-            'ë' => 0.44,
-            'ė' => 0.44,
-            'è' => 0.44,
-            '8' => 0.50,
+            'e' => 0.56,
+            'é' => 0.56,
+            'ě' => 0.56,
+            'ê' => 0.56,
+            'ë' => 0.56,
+            'ė' => 0.56,
+            'è' => 0.56,
+            '8' => 0.56,
             '…' => 1.00,
-            'ē' => 0.44,
+            'ē' => 0.56,
             '—' => 1.00,
-            '–' => 0.50,
-            'ę' => 0.44,
-            '=' => 0.57,
-            'ð' => 0.50,
-            '!' => 0.39,
-            '¡' => 0.39,
+            '–' => 0.56,
+            'ę' => 0.56,
+            '=' => 0.58,
+            'ð' => 0.61,
+            '!' => 0.33,
+            '¡' => 0.33,
             'f' => 0.33,
-            'ﬁ' => 0.56,
-            '5' => 0.50,
-            'ﬂ' => 0.56,
-            'ƒ' => 0.50,
-            '4' => 0.50,
+            'ﬁ' => 0.61,
+            '5' => 0.56,
+            'ﬂ' => 0.61,
+            'ƒ' => 0.56,
+            '4' => 0.56,
             '⁄' => 0.17,
-            'g' => 0.50,
-            'ğ' => 0.50,
-            'ģ' => 0.50,
-            'ß' => 0.50,
+            'g' => 0.61,
+            'ğ' => 0.61,
+            'ģ' => 0.61,
+            'ß' => 0.61,
             '`' => 0.33,
-            '>' => 0.57,
+            '>' => 0.58,
             '≥' => 0.55,
-            '«' => 0.50,
-            '»' => 0.50,
+            '«' => 0.56,
+            '»' => 0.56,
             '‹' => 0.33,
             '›' => 0.33,
-            'h' => 0.56,
+            'h' => 0.61,
             '˝' => 0.33,
             '-' => 0.33,
             'i' => 0.28,
@@ -2110,137 +1793,137 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ī' => 0.28,
             'į' => 0.28,
             'j' => 0.28,
-            'k' => 0.50,
-            'ķ' => 0.50,
+            'k' => 0.56,
+            'ķ' => 0.56,
             'l' => 0.28,
             'ĺ' => 0.28,
-            'ľ' => 0.38,
+            'ľ' => 0.40,
             'ļ' => 0.28,
-            '<' => 0.57,
+            '<' => 0.58,
             '≤' => 0.55,
-            '¬' => 0.61,
+            '¬' => 0.58,
             '◊' => 0.49,
             'ł' => 0.28,
-            'm' => 0.78,
+            'm' => 0.89,
             '¯' => 0.33,
-            '−' => 0.61,
-            'µ' => 0.58,
-            '×' => 0.57,
-            'n' => 0.56,
-            'ń' => 0.56,
-            'ň' => 0.56,
-            'ņ' => 0.56,
-            '9' => 0.50,
+            '−' => 0.58,
+            'µ' => 0.61,
+            '×' => 0.58,
+            'n' => 0.61,
+            'ń' => 0.61,
+            'ň' => 0.61,
+            'ņ' => 0.61,
+            '9' => 0.56,
             '≠' => 0.55,
-            'ñ' => 0.56,
-            '#' => 0.50,
-            'o' => 0.50,
-            'ó' => 0.50,
-            'ô' => 0.50,
-            'ö' => 0.50,
-            'œ' => 0.72,
+            'ñ' => 0.61,
+            '#' => 0.56,
+            'o' => 0.61,
+            'ó' => 0.61,
+            'ô' => 0.61,
+            'ö' => 0.61,
+            'œ' => 0.94,
             '˛' => 0.33,
-            'ò' => 0.50,
-            'ő' => 0.50,
-            'ō' => 0.50,
-            '1' => 0.50,
-            '½' => 0.75,
-            '¼' => 0.75,
-            '¹' => 0.30,
-            'ª' => 0.27,
-            'º' => 0.30,
-            'ø' => 0.50,
-            'õ' => 0.50,
-            'p' => 0.50,
-            '¶' => 0.50,
+            'ò' => 0.61,
+            'ő' => 0.61,
+            'ō' => 0.61,
+            '1' => 0.56,
+            '½' => 0.83,
+            '¼' => 0.83,
+            '¹' => 0.33,
+            'ª' => 0.37,
+            'º' => 0.36,
+            'ø' => 0.61,
+            'õ' => 0.61,
+            'p' => 0.61,
+            '¶' => 0.56,
             '(' => 0.33,
             ')' => 0.33,
             '∂' => 0.49,
-            '%' => 0.83,
-            '.' => 0.25,
-            '·' => 0.25,
+            '%' => 0.89,
+            '.' => 0.28,
+            '·' => 0.28,
             '‰' => 1.00,
-            '+' => 0.57,
-            '±' => 0.57,
-            'q' => 0.50,
-            '?' => 0.50,
-            '¿' => 0.50,
-            '"' => 0.56,
+            '+' => 0.58,
+            '±' => 0.58,
+            'q' => 0.61,
+            '?' => 0.61,
+            '¿' => 0.61,
+            '"' => 0.47,
             '„' => 0.50,
             '“' => 0.50,
             '”' => 0.50,
-            '‘' => 0.33,
-            '’' => 0.33,
-            '‚' => 0.33,
-            '\'' => 0.28,
+            '‘' => 0.28,
+            '’' => 0.28,
+            '‚' => 0.28,
+            '\'' => 0.24,
             'r' => 0.39,
             'ŕ' => 0.39,
             '√' => 0.55,
             'ř' => 0.39,
             'ŗ' => 0.39,
-            '®' => 0.75,
+            '®' => 0.74,
             '˚' => 0.33,
-            's' => 0.39,
-            'ś' => 0.39,
-            'š' => 0.39,
-            'ş' => 0.39,
-            'ș' => 0.39,
-            '§' => 0.50,
+            's' => 0.56,
+            'ś' => 0.56,
+            'š' => 0.56,
+            'ş' => 0.56,
+            'ș' => 0.56,
+            '§' => 0.56,
             ';' => 0.33,
-            '7' => 0.50,
-            '6' => 0.50,
+            '7' => 0.56,
+            '6' => 0.56,
             '/' => 0.28,
-            ' ' => 0.25,
-            '£' => 0.50,
+            ' ' => 0.28,
+            '£' => 0.56,
             '∑' => 0.60,
-            't' => 0.28,
-            'ť' => 0.37,
-            'ţ' => 0.28,
-            'þ' => 0.50,
-            '3' => 0.50,
-            '¾' => 0.75,
-            '³' => 0.30,
+            't' => 0.33,
+            'ť' => 0.39,
+            'ţ' => 0.33,
+            'þ' => 0.61,
+            '3' => 0.56,
+            '¾' => 0.83,
+            '³' => 0.33,
             '˜' => 0.33,
             '™' => 1.00,
-            '2' => 0.50,
-            '²' => 0.30,
-            'u' => 0.56,
-            'ú' => 0.56,
-            'û' => 0.56,
-            'ü' => 0.56,
-            'ù' => 0.56,
-            'ű' => 0.56,
-            'ū' => 0.56,
-            '_' => 0.50,
-            'ų' => 0.56,
-            'ů' => 0.56,
-            'v' => 0.44,
-            'w' => 0.67,
-            'x' => 0.50,
-            'y' => 0.44,
-            'ý' => 0.44,
-            'ÿ' => 0.44,
-            '¥' => 0.50,
-            'z' => 0.39,
-            'ź' => 0.39,
-            'ž' => 0.39,
-            'ż' => 0.39,
-            '0' => 0.50,
+            '2' => 0.56,
+            '²' => 0.33,
+            'u' => 0.61,
+            'ú' => 0.61,
+            'û' => 0.61,
+            'ü' => 0.61,
+            'ù' => 0.61,
+            'ű' => 0.61,
+            'ū' => 0.61,
+            '_' => 0.56,
+            'ų' => 0.61,
+            'ů' => 0.61,
+            'v' => 0.56,
+            'w' => 0.78,
+            'x' => 0.56,
+            'y' => 0.56,
+            'ý' => 0.56,
+            'ÿ' => 0.56,
+            '¥' => 0.56,
+            'z' => 0.50,
+            'ź' => 0.50,
+            'ž' => 0.50,
+            'ż' => 0.50,
+            '0' => 0.56,
             _ => 0.0,
         },
-        &Font::HelveticaBold => match c {
-            'A' => 0.72,
+        Font::HelveticaOblique => match c {
+            'A' => 0.67,
             'Æ' => 1.00,
-            'Á' => 0.72,
-            'Ă' => 0.72,
-            'Â' => 0.72,
-            'Ä' => 0.72,
-            'À' => 0.72,
-            'Ā' => 0.72,
-            'Ą' => 0.72,
-            'Å' => 0.72,
-            'Ã' => 0.72,
-            'B' => 0.72,
+            'Á' => 0.67,
+            'Ă' => 0.67,
+            'Â' => 0.67,
+            'Ä' => 0.67,
+            'À' => 0.67,
+            'Ā' => 0.67,
+            'Ą' => 0.67,
+            'Å' => 0.67,
+            'Ã' => 0.67,
+            'B' => 0.67,
             'C' => 0.72,
             'Ć' => 0.72,
             'Č' => 0.72,
@@ -2273,14 +1956,14 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ì' => 0.28,
             'Ī' => 0.28,
             'Į' => 0.28,
-            'J' => 0.56,
-            'K' => 0.72,
-            'Ķ' => 0.72,
-            'L' => 0.61,
-            'Ĺ' => 0.61,
-            'Ľ' => 0.61,
-            'Ļ' => 0.61,
-            'Ł' => 0.61,
+            'J' => 0.50,
+            'K' => 0.67,
+            'Ķ' => 0.67,
+            'L' => 0.56,
+            'Ĺ' => 0.56,
+            'Ľ' => 0.56,
+            'Ļ' => 0.56,
+            'Ł' => 0.56,
             'M' => 0.83,
             'N' => 0.72,
             'Ń' => 0.72,
@@ -2340,42 +2023,42 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'æ' => 0.89,
             'à' => 0.56,
             'ā' => 0.56,
-            '&' => 0.72,
+            '&' => 0.67,
             'ą' => 0.56,
             'å' => 0.56,
-            '^' => 0.58,
+            '^' => 0.47,
             '~' => 0.58,
             '*' => 0.39,
-            '@' => 0.97,
+            '@' => 1.01,
             'ã' => 0.56,
-            'b' => 0.61,
+            'b' => 0.56,
             '\\' => 0.28,
-            '|' => 0.28,
-            '{' => 0.39,
-            '}' => 0.39,
-            '[' => 0.33,
-            ']' => 0.33,
+            '|' => 0.26,
+            '{' => 0.33,
+            '}' => 0.33,
+            '[' => 0.28,
+            ']' => 0.28,
             '˘' => 0.33,
-            '¦' => 0.28,
+            '¦' => 0.26,
             '•' => 0.35,
-            'c' => 0.56,
-            'ć' => 0.56,
+            'c' => 0.50,
+            'ć' => 0.50,
             'ˇ' => 0.33,
-            'č' => 0.56,
-            'ç' => 0.56,
+            'č' => 0.50,
+            'ç' => 0.50,
             '¸' => 0.33,
             '¢' => 0.56,
             'ˆ' => 0.33,
-            ':' => 0.33,
+            ':' => 0.28,
             ',' => 0.28,
             '' => 0.25,
             '©' => 0.74,
             '¤' => 0.56,
-            'd' => 0.61,
+            'd' => 0.56,
             '†' => 0.56,
             '‡' => 0.56,
-            'ď' => 0.74,
-            'đ' => 0.61,
+            'ď' => 0.64,
+            'đ' => 0.56,
             '°' => 0.40,
             '¨' => 0.33,
             '÷' => 0.58,
@@ -2396,19 +2079,19 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '–' => 0.56,
             'ę' => 0.56,
             '=' => 0.58,
-            'ð' => 0.61,
-            '!' => 0.33,
+            'ð' => 0.56,
+            '!' => 0.28,
             '¡' => 0.33,
-            'f' => 0.33,
-            'ﬁ' => 0.61,
+            'f' => 0.28,
+            'ﬁ' => 0.50,
             '5' => 0.56,
-            'ﬂ' => 0.61,
+            'ﬂ' => 0.50,
             'ƒ' => 0.56,
             '4' => 0.56,
             '⁄' => 0.17,
-            'g' => 0.61,
-            'ğ' => 0.61,
-            'ģ' => 0.61,
+            'g' => 0.56,
+            'ğ' => 0.56,
+            'ģ' => 0.56,
             'ß' => 0.61,
             '`' => 0.33,
             '>' => 0.58,
@@ -2417,50 +2100,50 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '»' => 0.56,
             '‹' => 0.33,
             '›' => 0.33,
-            'h' => 0.61,
+            'h' => 0.56,
             '˝' => 0.33,
             '-' => 0.33,
-            'i' => 0.28,
+            'i' => 0.22,
             'í' => 0.28,
             'î' => 0.28,
             'ï' => 0.28,
             'ì' => 0.28,
             'ī' => 0.28,
-            'į' => 0.28,
-            'j' => 0.28,
-            'k' => 0.56,
-            'ķ' => 0.56,
-            'l' => 0.28,
-            'ĺ' => 0.28,
-            'ľ' => 0.40,
-            'ļ' => 0.28,
+            'į' => 0.22,
+            'j' => 0.22,
+            'k' => 0.50,
+            'ķ' => 0.50,
+            'l' => 0.22,
+            'ĺ' => 0.22,
+            'ľ' => 0.30,
+            'ļ' => 0.22,
             '<' => 0.58,
             '≤' => 0.55,
             '¬' => 0.58,
-            '◊' => 0.49,
-            'ł' => 0.28,
-            'm' => 0.89,
+            '◊' => 0.47,
+            'ł' => 0.22,
+            'm' => 0.83,
             '¯' => 0.33,
             '−' => 0.58,
-            'µ' => 0.61,
+            'µ' => 0.56,
             '×' => 0.58,
-            'n' => 0.61,
-            'ń' => 0.61,
-            'ň' => 0.61,
-            'ņ' => 0.61,
+            'n' => 0.56,
+            'ń' => 0.56,
+            'ň' => 0.56,
+            'ņ' => 0.56,
             '9' => 0.56,
             '≠' => 0.55,
-            'ñ' => 0.61,
+            'ñ' => 0.56,
             '#' => 0.56,
-            'o' => 0.61,
-            'ó' => 0.61,
-            'ô' => 0.61,
-            'ö' => 0.61,
+            'o' => 0.56,
+            'ó' => 0.56,
+            'ô' => 0.56,
+            'ö' => 0.56,
             'œ' => 0.94,
             '˛' => 0.33,
-            'ò' => 0.61,
-            'ő' => 0.61,
-            'ō' => 0.61,
+            'ò' => 0.56,
+            'ő' => 0.56,
+            'ō' => 0.56,
             '1' => 0.56,
             '½' => 0.83,
             '¼' => 0.83,
@@ -2468,53 +2151,53 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ª' => 0.37,
             'º' => 0.36,
             'ø' => 0.61,
-            'õ' => 0.61,
-            'p' => 0.61,
-            '¶' => 0.56,
+            'õ' => 0.56,
+            'p' => 0.56,
+            '¶' => 0.54,
             '(' => 0.33,
             ')' => 0.33,
-            '∂' => 0.49,
+            '∂' => 0.48,
             '%' => 0.89,
             '.' => 0.28,
             '·' => 0.28,
             '‰' => 1.00,
             '+' => 0.58,
             '±' => 0.58,
-            'q' => 0.61,
-            '?' => 0.61,
+            'q' => 0.56,
+            '?' => 0.56,
             '¿' => 0.61,
-            '"' => 0.47,
-            '„' => 0.50,
-            '“' => 0.50,
-            '”' => 0.50,
-            '‘' => 0.28,
-            '’' => 0.28,
-            '‚' => 0.28,
-            '\'' => 0.24,
-            'r' => 0.39,
-            'ŕ' => 0.39,
-            '√' => 0.55,
-            'ř' => 0.39,
-            'ŗ' => 0.39,
+            '"' => 0.35,
+            '„' => 0.33,
+            '“' => 0.33,
+            '”' => 0.33,
+            '‘' => 0.22,
+            '’' => 0.22,
+            '‚' => 0.22,
+            '\'' => 0.19,
+            'r' => 0.33,
+            'ŕ' => 0.33,
+            '√' => 0.45,
+            'ř' => 0.33,
+            'ŗ' => 0.33,
             '®' => 0.74,
             '˚' => 0.33,
-            's' => 0.56,
-            'ś' => 0.56,
-            'š' => 0.56,
-            'ş' => 0.56,
-            'ș' => 0.56,
+            's' => 0.50,
+            'ś' => 0.50,
+            'š' => 0.50,
+            'ş' => 0.50,
+            'ș' => 0.50,
             '§' => 0.56,
-            ';' => 0.33,
+            ';' => 0.28,
             '7' => 0.56,
             '6' => 0.56,
             '/' => 0.28,
             ' ' => 0.28,
             '£' => 0.56,
             '∑' => 0.60,
-            't' => 0.33,
-            'ť' => 0.39,
-            'ţ' => 0.33,
-            'þ' => 0.61,
+            't' => 0.28,
+            'ť' => 0.32,
+            'ţ' => 0.28,
+            'þ' => 0.56,
             '3' => 0.56,
             '¾' => 0.83,
             '³' => 0.33,
@@ -2522,22 +2205,22 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '™' => 1.00,
             '2' => 0.56,
             '²' => 0.33,
-            'u' => 0.61,
-            'ú' => 0.61,
-            'û' => 0.61,
-            'ü' => 0.61,
-            'ù' => 0.61,
-            'ű' => 0.61,
-            'ū' => 0.61,
+            'u' => 0.56,
+            'ú' => 0.56,
+            'û' => 0.56,
+            'ü' => 0.56,
+            'ù' => 0.56,
+            'ű' => 0.56,
+            'ū' => 0.56,
             '_' => 0.56,
-            'ų' => 0.61,
-            'ů' => 0.61,
-            'v' => 0.56,
-            'w' => 0.78,
-            'x' => 0.56,
-            'y' => 0.56,
-            'ý' => 0.56,
-            'ÿ' => 0.56,
+            'ų' => 0.56,
+            'ů' => 0.56,
+            'v' => 0.50,
+            'w' => 0.72,
+            'x' => 0.50,
+            'y' => 0.50,
+            'ý' => 0.50,
+            'ÿ' => 0.50,
             '¥' => 0.56,
             'z' => 0.50,
             'ź' => 0.50,
@@ -2546,11 +2229,7 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '0' => 0.56,
             _ => 0.0,
         },
-        &Font::ZapfDingbats => match c {
-            ' ' => 0.28,
-            _ => 0.0,
-        },
-        &Font::Helvetica => match c {
+        Font::Helvetica => match c {
             'A' => 0.67,
             'Æ' => 1.00,
             'Á' => 0.67,
@@ -2758,128 +2437,321 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ļ' => 0.22,
             '<' => 0.58,
             '≤' => 0.55,
-            '¬' => 0.58,
-            '◊' => 0.47,
-            'ł' => 0.22,
-            'm' => 0.83,
-            '¯' => 0.33,
-            '−' => 0.58,
-            'µ' => 0.56,
-            '×' => 0.58,
-            'n' => 0.56,
-            'ń' => 0.56,
-            'ň' => 0.56,
-            'ņ' => 0.56,
-            '9' => 0.56,
+            '¬' => 0.58,
+            '◊' => 0.47,
+            'ł' => 0.22,
+            'm' => 0.83,
+            '¯' => 0.33,
+            '−' => 0.58,
+            'µ' => 0.56,
+            '×' => 0.58,
+            'n' => 0.56,
+            'ń' => 0.56,
+            'ň' => 0.56,
+            'ņ' => 0.56,
+            '9' => 0.56,
+            '≠' => 0.55,
+            'ñ' => 0.56,
+            '#' => 0.56,
+            'o' => 0.56,
+            'ó' => 0.56,
+            'ô' => 0.56,
+            'ö' => 0.56,
+            'œ' => 0.94,
+            '˛' => 0.33,
+            'ò' => 0.56,
+            'ő' => 0.56,
+            'ō' => 0.56,
+            '1' => 0.56,
+            '½' => 0.83,
+            '¼' => 0.83,
+            '¹' => 0.33,
+            'ª' => 0.37,
+            'º' => 0.36,
+            'ø' => 0.61,
+            'õ' => 0.56,
+            'p' => 0.56,
+            '¶' => 0.54,
+            '(' => 0.33,
+            ')' => 0.33,
+            '∂' => 0.48,
+            '%' => 0.89,
+            '.' => 0.28,
+            '·' => 0.28,
+            '‰' => 1.00,
+            '+' => 0.58,
+            '±' => 0.58,
+            'q' => 0.56,
+            '?' => 0.56,
+            '¿' => 0.61,
+            '"' => 0.35,
+            '„' => 0.33,
+            '“' => 0.33,
+            '”' => 0.33,
+            '‘' => 0.22,
+            '’' => 0.22,
+            '‚' => 0.22,
+            '\'' => 0.19,
+            'r' => 0.33,
+            'ŕ' => 0.33,
+            '√' => 0.45,
+            'ř' => 0.33,
+            'ŗ' => 0.33,
+            '®' => 0.74,
+            '˚' => 0.33,
+            's' => 0.50,
+            'ś' => 0.50,
+            'š' => 0.50,
+            'ş' => 0.50,
+            'ș' => 0.50,
+            '§' => 0.56,
+            ';' => 0.28,
+            '7' => 0.56,
+            '6' => 0.56,
+            '/' => 0.28,
+            ' ' => 0.28,
+            '£' => 0.56,
+            '∑' => 0.60,
+            't' => 0.28,
+            'ť' => 0.32,
+            'ţ' => 0.28,
+            'þ' => 0.56,
+            '3' => 0.56,
+            '¾' => 0.83,
+            '³' => 0.33,
+            '˜' => 0.33,
+            '™' => 1.00,
+            '2' => 0.56,
+            '²' => 0.33,
+            'u' => 0.56,
+            'ú' => 0.56,
+            'û' => 0.56,
+            'ü' => 0.56,
+            'ù' => 0.56,
+            'ű' => 0.56,
+            'ū' => 0.56,
+            '_' => 0.56,
+            'ų' => 0.56,
+            'ů' => 0.56,
+            'v' => 0.50,
+            'w' => 0.72,
+            'x' => 0.50,
+            'y' => 0.50,
+            'ý' => 0.50,
+            'ÿ' => 0.50,
+            '¥' => 0.56,
+            'z' => 0.50,
+            'ź' => 0.50,
+            'ž' => 0.50,
+            'ż' => 0.50,
+            '0' => 0.56,
+            _ => 0.0,
+        },
+        Font::Symbol => match c {
+            'Α' => 0.72,
+            'Β' => 0.67,
+            'Χ' => 0.72,
+            '∆' => 0.61,
+            'Ε' => 0.61,
+            'Η' => 0.72,
+            '€' => 0.75,
+            'Γ' => 0.60,
+            'ℑ' => 0.69,
+            'Ι' => 0.33,
+            'Κ' => 0.72,
+            'Λ' => 0.69,
+            'Μ' => 0.89,
+            'Ν' => 0.72,
+            'Ω' => 0.77,
+            'Ο' => 0.72,
+            'Φ' => 0.76,
+            'Π' => 0.77,
+            'Ψ' => 0.80,
+            'ℜ' => 0.80,
+            'Ρ' => 0.56,
+            'Σ' => 0.59,
+            'Τ' => 0.61,
+            'Θ' => 0.74,
+            'Υ' => 0.69,
+            'ϒ' => 0.62,
+            'Ξ' => 0.65,
+            'Ζ' => 0.61,
+            'ℵ' => 0.82,
+            'α' => 0.63,
+            '&' => 0.78,
+            '∠' => 0.77,
+            '〈' => 0.33,
+            '〉' => 0.33,
+            '' => 0.79,
+            '≈' => 0.55,
+            '↔' => 1.04,
+            '⇔' => 1.04,
+            '⇓' => 0.60,
+            '⇐' => 0.99,
+            '⇒' => 0.99,
+            '⇑' => 0.60,
+            '↓' => 0.60,
+            '' => 1.00,
+            '←' => 0.99,
+            '→' => 0.99,
+            '↑' => 0.60,
+            '' => 0.60,
+            '∗' => 0.50,
+            '|' => 0.20,
+            'β' => 0.55,
+            '' => 0.49,
+            '{' => 0.48,
+            '' => 0.49,
+            '' => 0.49,
+            '' => 0.49,
+            '}' => 0.48,
+            '' => 0.49,
+            '' => 0.49,
+            '' => 0.49,
+            '[' => 0.33,
+            '' => 0.38,
+            '' => 0.38,
+            '' => 0.38,
+            ']' => 0.33,
+            '' => 0.38,
+            '' => 0.38,
+            '' => 0.38,
+            '•' => 0.46,
+            '↵' => 0.66,
+            'χ' => 0.55,
+            '⊗' => 0.77,
+            '⊕' => 0.77,
+            '♣' => 0.75,
+            ':' => 0.28,
+            ',' => 0.25,
+            '≅' => 0.55,
+            '' => 0.79,
+            '' => 0.79,
+            '°' => 0.40,
+            'δ' => 0.49,
+            '♦' => 0.75,
+            '÷' => 0.55,
+            '⋅' => 0.25,
+            '8' => 0.50,
+            '∈' => 0.71,
+            '…' => 1.00,
+            '∅' => 0.82,
+            'ε' => 0.44,
+            '=' => 0.55,
+            '≡' => 0.55,
+            'η' => 0.60,
+            '!' => 0.33,
+            '∃' => 0.55,
+            '5' => 0.50,
+            'ƒ' => 0.50,
+            '4' => 0.50,
+            '⁄' => 0.17,
+            'γ' => 0.41,
+            '∇' => 0.71,
+            '>' => 0.55,
+            '≥' => 0.55,
+            '♥' => 0.75,
+            '∞' => 0.71,
+            '∫' => 0.27,
+            '⌡' => 0.69,
+            '' => 0.69,
+            '⌠' => 0.69,
+            '∩' => 0.77,
+            'ι' => 0.33,
+            'κ' => 0.55,
+            'λ' => 0.55,
+            '<' => 0.55,
+            '≤' => 0.55,
+            '∧' => 0.60,
+            '¬' => 0.71,
+            '∨' => 0.60,
+            '◊' => 0.49,
+            '−' => 0.55,
+            '′' => 0.25,
+            'µ' => 0.58,
+            '×' => 0.55,
+            '9' => 0.50,
+            '∉' => 0.71,
             '≠' => 0.55,
-            'ñ' => 0.56,
-            '#' => 0.56,
-            'o' => 0.56,
-            'ó' => 0.56,
-            'ô' => 0.56,
-            'ö' => 0.56,
-            'œ' => 0.94,
-            '˛' => 0.33,
-            'ò' => 0.56,
-            'ő' => 0.56,
-            'ō' => 0.56,
-            '1' => 0.56,
-            '½' => 0.83,
-            '¼' => 0.83,
-            '¹' => 0.33,
-            'ª' => 0.37,
-            'º' => 0.36,
-            'ø' => 0.61,
-            'õ' => 0.56,
-            'p' => 0.56,
-            '¶' => 0.54,
+            '⊄' => 0.71,
+            'ν' => 0.52,
+            '#' => 0.50,
+            'ω' => 0.69,
+            'ϖ' => 0.71,
+            'ο' => 0.55,
+            '1' => 0.50,
             '(' => 0.33,
+            '' => 0.38,
+            '' => 0.38,
+            '' => 0.38,
             ')' => 0.33,
-            '∂' => 0.48,
-            '%' => 0.89,
-            '.' => 0.28,
-            '·' => 0.28,
-            '‰' => 1.00,
-            '+' => 0.58,
-            '±' => 0.58,
-            'q' => 0.56,
-            '?' => 0.56,
-            '¿' => 0.61,
-            '"' => 0.35,
-            '„' => 0.33,
-            '“' => 0.33,
-            '”' => 0.33,
-            '‘' => 0.22,
-            '’' => 0.22,
-            '‚' => 0.22,
-            '\'' => 0.19,
-            'r' => 0.33,
-            'ŕ' => 0.33,
-            '√' => 0.45,
-            'ř' => 0.33,
-            'ŗ' => 0.33,
-            '®' => 0.74,
-            '˚' => 0.33,
-            's' => 0.50,
-            'ś' => 0.50,
-            'š' => 0.50,
-            'ş' => 0.50,
-            'ș' => 0.50,
-            '§' => 0.56,
+            '' => 0.38,
+            '' => 0.38,
+            '' => 0.38,
+            '∂' => 0.49,
+            '%' => 0.83,
+            '.' => 0.25,
+            '⊥' => 0.66,
+            'φ' => 0.52,
+            'ϕ' => 0.60,
+            'π' => 0.55,
+            '+' => 0.55,
+            '±' => 0.55,
+            '∏' => 0.82,
+            '⊂' => 0.71,
+            '⊃' => 0.71,
+            '∝' => 0.71,
+            'ψ' => 0.69,
+            '?' => 0.44,
+            '√' => 0.55,
+            '' => 0.50,
+            '⊆' => 0.71,
+            '⊇' => 0.71,
+            '' => 0.79,
+            '' => 0.79,
+            'ρ' => 0.55,
+            '″' => 0.41,
             ';' => 0.28,
-            '7' => 0.56,
-            '6' => 0.56,
+            '7' => 0.50,
+            'σ' => 0.60,
+            'ς' => 0.44,
+            '∼' => 0.55,
+            '6' => 0.50,
             '/' => 0.28,
-            ' ' => 0.28,
-            '£' => 0.56,
-            '∑' => 0.60,
-            't' => 0.28,
-            'ť' => 0.32,
-            'ţ' => 0.28,
-            'þ' => 0.56,
-            '3' => 0.56,
-            '¾' => 0.83,
-            '³' => 0.33,
-            '˜' => 0.33,
-            '™' => 1.00,
-            '2' => 0.56,
-            '²' => 0.33,
-            'u' => 0.56,
-            'ú' => 0.56,
-            'û' => 0.56,
-            'ü' => 0.56,
-            'ù' => 0.56,
-            'ű' => 0.56,
-            'ū' => 0.56,
-            '_' => 0.56,
-            'ų' => 0.56,
-            'ů' => 0.56,
-            'v' => 0.50,
-            'w' => 0.72,
-            'x' => 0.50,
-            'y' => 0.50,
-            'ý' => 0.50,
-            'ÿ' => 0.50,
-            '¥' => 0.56,
-            'z' => 0.50,
-            'ź' => 0.50,
-            'ž' => 0.50,
-            'ż' => 0.50,
-            '0' => 0.56,
+            ' ' => 0.25,
+            '♠' => 0.75,
+            '∋' => 0.44,
+            '∑' => 0.71,
+            'τ' => 0.44,
+            '∴' => 0.86,
+            'θ' => 0.52,
+            'ϑ' => 0.63,
+            '3' => 0.50,
+            '' => 0.79,
+            '' => 0.89,
+            '2' => 0.50,
+            '_' => 0.50,
+            '∪' => 0.77,
+            '∀' => 0.71,
+            'υ' => 0.58,
+            '℘' => 0.99,
+            'ξ' => 0.49,
+            '0' => 0.50,
+            'ζ' => 0.49,
             _ => 0.0,
         },
-        &Font::HelveticaOblique => match c {
-            'A' => 0.67,
+        Font::TimesBold => match c {
+            'A' => 0.72,
             'Æ' => 1.00,
-            'Á' => 0.67,
-            'Ă' => 0.67,
-            'Â' => 0.67,
-            'Ä' => 0.67,
-            'À' => 0.67,
-            'Ā' => 0.67,
-            'Ą' => 0.67,
-            'Å' => 0.67,
-            'Ã' => 0.67,
+            'Á' => 0.72,
+            'Ă' => 0.72,
+            'Â' => 0.72,
+            'Ä' => 0.72,
+            'À' => 0.72,
+            'Ā' => 0.72,
+            'Ą' => 0.72,
+            'Å' => 0.72,
+            'Ã' => 0.72,
             'B' => 0.67,
             'C' => 0.72,
             'Ć' => 0.72,
@@ -2899,29 +2771,29 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ē' => 0.67,
             'Ę' => 0.67,
             'Ð' => 0.72,
-            '€' => 0.56,
+            '€' => 0.50,
             'F' => 0.61,
             'G' => 0.78,
             'Ğ' => 0.78,
             'Ģ' => 0.78,
-            'H' => 0.72,
-            'I' => 0.28,
-            'Í' => 0.28,
-            'Î' => 0.28,
-            'Ï' => 0.28,
-            'İ' => 0.28,
-            'Ì' => 0.28,
-            'Ī' => 0.28,
-            'Į' => 0.28,
+            'H' => 0.78,
+            'I' => 0.39,
+            'Í' => 0.39,
+            'Î' => 0.39,
+            'Ï' => 0.39,
+            'İ' => 0.39,
+            'Ì' => 0.39,
+            'Ī' => 0.39,
+            'Į' => 0.39,
             'J' => 0.50,
-            'K' => 0.67,
-            'Ķ' => 0.67,
-            'L' => 0.56,
-            'Ĺ' => 0.56,
-            'Ľ' => 0.56,
-            'Ļ' => 0.56,
-            'Ł' => 0.56,
-            'M' => 0.83,
+            'K' => 0.78,
+            'Ķ' => 0.78,
+            'L' => 0.67,
+            'Ĺ' => 0.67,
+            'Ľ' => 0.67,
+            'Ļ' => 0.67,
+            'Ł' => 0.67,
+            'M' => 0.94,
             'N' => 0.72,
             'Ń' => 0.72,
             'Ň' => 0.72,
@@ -2937,21 +2809,21 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ō' => 0.78,
             'Ø' => 0.78,
             'Õ' => 0.78,
-            'P' => 0.67,
+            'P' => 0.61,
             'Q' => 0.78,
             'R' => 0.72,
             'Ŕ' => 0.72,
             'Ř' => 0.72,
             'Ŗ' => 0.72,
-            'S' => 0.67,
-            'Ś' => 0.67,
-            'Š' => 0.67,
-            'Ş' => 0.67,
-            'Ș' => 0.67,
-            'T' => 0.61,
-            'Ť' => 0.61,
-            'Ţ' => 0.61,
-            'Þ' => 0.67,
+            'S' => 0.56,
+            'Ś' => 0.56,
+            'Š' => 0.56,
+            'Ş' => 0.56,
+            'Ș' => 0.56,
+            'T' => 0.67,
+            'Ť' => 0.67,
+            'Ţ' => 0.67,
+            'Þ' => 0.61,
             'U' => 0.72,
             'Ú' => 0.72,
             'Û' => 0.72,
@@ -2961,207 +2833,207 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ū' => 0.72,
             'Ų' => 0.72,
             'Ů' => 0.72,
-            'V' => 0.67,
-            'W' => 0.94,
-            'X' => 0.67,
-            'Y' => 0.67,
-            'Ý' => 0.67,
-            'Ÿ' => 0.67,
-            'Z' => 0.61,
-            'Ź' => 0.61,
-            'Ž' => 0.61,
-            'Ż' => 0.61,
-            'a' => 0.56,
-            'á' => 0.56,
-            'ă' => 0.56,
-            'â' => 0.56,
+            'V' => 0.72,
+            'W' => 1.00,
+            'X' => 0.72,
+            'Y' => 0.72,
+            'Ý' => 0.72,
+            'Ÿ' => 0.72,
+            'Z' => 0.67,
+            'Ź' => 0.67,
+            'Ž' => 0.67,
+            'Ż' => 0.67,
+            'a' => 0.50,
+            'á' => 0.50,
+            'ă' => 0.50,
+            'â' => 0.50,
             '´' => 0.33,
-            'ä' => 0.56,
-            'æ' => 0.89,
-            'à' => 0.56,
-            'ā' => 0.56,
-            '&' => 0.67,
-            'ą' => 0.56,
-            'å' => 0.56,
-            '^' => 0.47,
-            '~' => 0.58,
-            '*' => 0.39,
-            '@' => 1.01,
-            'ã' => 0.56,
+            'ä' => 0.50,
+            'æ' => 0.72,
+            'à' => 0.50,
+            'ā' => 0.50,
+            '&' => 0.83,
+            'ą' => 0.50,
+            'å' => 0.50,
+            '^' => 0.58,
+            '~' => 0.52,
+            '*' => 0.50,
+            '@' => 0.93,
+            'ã' => 0.50,
             'b' => 0.56,
             '\\' => 0.28,
-            '|' => 0.26,
-            '{' => 0.33,
-            '}' => 0.33,
-            '[' => 0.28,
-            ']' => 0.28,
+            '|' => 0.22,
+            '{' => 0.39,
+            '}' => 0.39,
+            '[' => 0.33,
+            ']' => 0.33,
             '˘' => 0.33,
-            '¦' => 0.26,
+            '¦' => 0.22,
             '•' => 0.35,
-            'c' => 0.50,
-            'ć' => 0.50,
+            'c' => 0.44,
+            'ć' => 0.44,
             'ˇ' => 0.33,
-            'č' => 0.50,
-            'ç' => 0.50,
+            'č' => 0.44,
+            'ç' => 0.44,
             '¸' => 0.33,
-            '¢' => 0.56,
+            '¢' => 0.50,
             'ˆ' => 0.33,
-            ':' => 0.28,
-            ',' => 0.28,
+            ':' => 0.33,
+            ',' => 0.25,
             '' => 0.25,
-            '©' => 0.74,
-            '¤' => 0.56,
+            '©' => 0.75,
+            '¤' => 0.50,
             'd' => 0.56,
-            '†' => 0.56,
-            '‡' => 0.56,
-            'ď' => 0.64,
+            '†' => 0.50,
+            '‡' => 0.50,
+            'ď' => 0.67,
             'đ' => 0.56,
             '°' => 0.40,
             '¨' => 0.33,
-            '÷' => 0.58,
-            '$' => 0.56,
+            '÷' => 0.57,
+            '$' => 0.50,
             '˙' => 0.33,
             'ı' => 0.28,
-            'e' => 0.56,
-            'é' => 0.56,
-            'ě' => 0.56,
-            'ê' => 0.56,
-            'ë' => 0.56,
-            'ė' => 0.56,
-            'è' => 0.56,
-            '8' => 0.56,
+            'e' => 0.44,
+            'é' => 0.44,
+            'ě' => 0.44,
+            'ê' => 0.44,
+            'ë' => 0.44,
+            'ė' => 0.44,
+            'è' => 0.44,
+            '8' => 0.50,
             '…' => 1.00,
-            'ē' => 0.56,
+            'ē' => 0.44,
             '—' => 1.00,
-            '–' => 0.56,
-            'ę' => 0.56,
-            '=' => 0.58,
-            'ð' => 0.56,
-            '!' => 0.28,
+            '–' => 0.50,
+            'ę' => 0.44,
+            '=' => 0.57,
+            'ð' => 0.50,
+            '!' => 0.33,
             '¡' => 0.33,
-            'f' => 0.28,
-            'ﬁ' => 0.50,
-            '5' => 0.56,
-            'ﬂ' => 0.50,
-            'ƒ' => 0.56,
-            '4' => 0.56,
+            'f' => 0.33,
+            'ﬁ' => 0.56,
+            '5' => 0.50,
+            'ﬂ' => 0.56,
+            'ƒ' => 0.50,
+            '4' => 0.50,
             '⁄' => 0.17,
-            'g' => 0.56,
-            'ğ' => 0.56,
-            'ģ' => 0.56,
-            'ß' => 0.61,
+            'g' => 0.50,
+            'ğ' => 0.50,
+            'ģ' => 0.50,
+            'ß' => 0.56,
             '`' => 0.33,
-            '>' => 0.58,
+            '>' => 0.57,
             '≥' => 0.55,
-            '«' => 0.56,
-            '»' => 0.56,
+            '«' => 0.50,
+            '»' => 0.50,
             '‹' => 0.33,
             '›' => 0.33,
             'h' => 0.56,
             '˝' => 0.33,
             '-' => 0.33,
-            'i' => 0.22,
+            'i' => 0.28,
             'í' => 0.28,
             'î' => 0.28,
             'ï' => 0.28,
             'ì' => 0.28,
             'ī' => 0.28,
-            'į' => 0.22,
-            'j' => 0.22,
-            'k' => 0.50,
-            'ķ' => 0.50,
-            'l' => 0.22,
-            'ĺ' => 0.22,
-            'ľ' => 0.30,
-            'ļ' => 0.22,
-            '<' => 0.58,
+            'į' => 0.28,
+            'j' => 0.33,
+            'k' => 0.56,
+            'ķ' => 0.56,
+            'l' => 0.28,
+            'ĺ' => 0.28,
+            'ľ' => 0.39,
+            'ļ' => 0.28,
+            '<' => 0.57,
             '≤' => 0.55,
-            '¬' => 0.58,
-            '◊' => 0.47,
-            'ł' => 0.22,
+            '¬' => 0.57,
+            '◊' => 0.49,
+            'ł' => 0.28,
             'm' => 0.83,
             '¯' => 0.33,
-            '−' => 0.58,
+            '−' => 0.57,
             'µ' => 0.56,
-            '×' => 0.58,
+            '×' => 0.57,
             'n' => 0.56,
             'ń' => 0.56,
             'ň' => 0.56,
             'ņ' => 0.56,
-            '9' => 0.56,
+            '9' => 0.50,
             '≠' => 0.55,
             'ñ' => 0.56,
-            '#' => 0.56,
-            'o' => 0.56,
-            'ó' => 0.56,
-            'ô' => 0.56,
-            'ö' => 0.56,
-            'œ' => 0.94,
+            '#' => 0.50,
+            'o' => 0.50,
+            'ó' => 0.50,
+            'ô' => 0.50,
+            'ö' => 0.50,
+            'œ' => 0.72,
             '˛' => 0.33,
-            'ò' => 0.56,
-            'ő' => 0.56,
-            'ō' => 0.56,
-            '1' => 0.56,
-            '½' => 0.83,
-            '¼' => 0.83,
-            '¹' => 0.33,
-            'ª' => 0.37,
-            'º' => 0.36,
-            'ø' => 0.61,
-            'õ' => 0.56,
+            'ò' => 0.50,
+            'ő' => 0.50,
+            'ō' => 0.50,
+            '1' => 0.50,
+            '½' => 0.75,
+            '¼' => 0.75,
+            '¹' => 0.30,
+            'ª' => 0.30,
+            'º' => 0.33,
+            'ø' => 0.50,
+            'õ' => 0.50,
             'p' => 0.56,
             '¶' => 0.54,
             '(' => 0.33,
             ')' => 0.33,
-            '∂' => 0.48,
-            '%' => 0.89,
-            '.' => 0.28,
-            '·' => 0.28,
+            '∂' => 0.49,
+            '%' => 1.00,
+            '.' => 0.25,
+            '·' => 0.25,
             '‰' => 1.00,
-            '+' => 0.58,
-            '±' => 0.58,
+            '+' => 0.57,
+            '±' => 0.57,
             'q' => 0.56,
-            '?' => 0.56,
-            '¿' => 0.61,
-            '"' => 0.35,
-            '„' => 0.33,
-            '“' => 0.33,
-            '”' => 0.33,
-            '‘' => 0.22,
-            '’' => 0.22,
-            '‚' => 0.22,
-            '\'' => 0.19,
-            'r' => 0.33,
-            'ŕ' => 0.33,
-            '√' => 0.45,
-            'ř' => 0.33,
-            'ŗ' => 0.33,
-            '®' => 0.74,
+            '?' => 0.50,
+            '¿' => 0.50,
+            '"' => 0.56,
+            '„' => 0.50,
+            '“' => 0.50,
+            '”' => 0.50,
+            '‘' => 0.33,
+            '’' => 0.33,
+            '‚' => 0.33,
+            '\'' => 0.28,
+            'r' => 0.44,
+            'ŕ' => 0.44,
+            '√' => 0.55,
+            'ř' => 0.44,
+            'ŗ' => 0.44,
+            '®' => 0.75,
             '˚' => 0.33,
-            's' => 0.50,
-            'ś' => 0.50,
-            'š' => 0.50,
-            'ş' => 0.50,
-            'ș' => 0.50,
-            '§' => 0.56,
-            ';' => 0.28,
-            '7' => 0.56,
-            '6' => 0.56,
+            's' => 0.39,
+            'ś' => 0.39,
+            'š' => 0.39,
+            'ş' => 0.39,
+            'ș' => 0.39,
+            '§' => 0.50,
+            ';' => 0.33,
+            '7' => 0.50,
+            '6' => 0.50,
             '/' => 0.28,
-            ' ' => 0.28,
-            '£' => 0.56,
+            ' ' => 0.25,
+            '£' => 0.50,
             '∑' => 0.60,
-            't' => 0.28,
-            'ť' => 0.32,
-            'ţ' => 0.28,
+            't' => 0.33,
+            'ť' => 0.42,
+            'ţ' => 0.33,
             'þ' => 0.56,
-            '3' => 0.56,
-            '¾' => 0.83,
-            '³' => 0.33,
+            '3' => 0.50,
+            '¾' => 0.75,
+            '³' => 0.30,
             '˜' => 0.33,
             '™' => 1.00,
-            '2' => 0.56,
-            '²' => 0.33,
+            '2' => 0.50,
+            '²' => 0.30,
             'u' => 0.56,
             'ú' => 0.56,
             'û' => 0.56,
@@ -3169,7 +3041,7 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ù' => 0.56,
             'ű' => 0.56,
             'ū' => 0.56,
-            '_' => 0.56,
+            '_' => 0.50,
             'ų' => 0.56,
             'ů' => 0.56,
             'v' => 0.50,
@@ -3178,31 +3050,31 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'y' => 0.50,
             'ý' => 0.50,
             'ÿ' => 0.50,
-            '¥' => 0.56,
-            'z' => 0.50,
-            'ź' => 0.50,
-            'ž' => 0.50,
-            'ż' => 0.50,
-            '0' => 0.56,
+            '¥' => 0.50,
+            'z' => 0.44,
+            'ź' => 0.44,
+            'ž' => 0.44,
+            'ż' => 0.44,
+            '0' => 0.50,
             _ => 0.0,
         },
-        &Font::HelveticaBoldOblique => match c {
-            'A' => 0.72,
-            'Æ' => 1.00,
-            'Á' => 0.72,
-            'Ă' => 0.72,
-            'Â' => 0.72,
-            'Ä' => 0.72,
-            'À' => 0.72,
-            'Ā' => 0.72,
-            'Ą' => 0.72,
-            'Å' => 0.72,
-            'Ã' => 0.72,
-            'B' => 0.72,
-            'C' => 0.72,
-            'Ć' => 0.72,
-            'Č' => 0.72,
-            'Ç' => 0.72,
+        Font::TimesBoldItalic => match c {
+            'A' => 0.67,
+            'Æ' => 0.94,
+            'Á' => 0.67,
+            'Ă' => 0.67,
+            'Â' => 0.67,
+            'Ä' => 0.67,
+            'À' => 0.67,
+            'Ā' => 0.67,
+            'Ą' => 0.67,
+            'Å' => 0.67,
+            'Ã' => 0.67,
+            'B' => 0.67,
+            'C' => 0.67,
+            'Ć' => 0.67,
+            'Č' => 0.67,
+            'Ç' => 0.67,
             'D' => 0.72,
             'Ď' => 0.72,
             'Đ' => 0.72,
@@ -3217,59 +3089,59 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ē' => 0.67,
             'Ę' => 0.67,
             'Ð' => 0.72,
-            '€' => 0.56,
-            'F' => 0.61,
-            'G' => 0.78,
-            'Ğ' => 0.78,
-            'Ģ' => 0.78,
-            'H' => 0.72,
-            'I' => 0.28,
-            'Í' => 0.28,
-            'Î' => 0.28,
-            'Ï' => 0.28,
-            'İ' => 0.28,
-            'Ì' => 0.28,
-            'Ī' => 0.28,
-            'Į' => 0.28,
-            'J' => 0.56,
-            'K' => 0.72,
-            'Ķ' => 0.72,
+            '€' => 0.50,
+            'F' => 0.67,
+            'G' => 0.72,
+            'Ğ' => 0.72,
+            'Ģ' => 0.72,
+            'H' => 0.78,
+            'I' => 0.39,
+            'Í' => 0.39,
+            'Î' => 0.39,
+            'Ï' => 0.39,
+            'İ' => 0.39,
+            'Ì' => 0.39,
+            'Ī' => 0.39,
+            'Į' => 0.39,
+            'J' => 0.50,
+            'K' => 0.67,
+            'Ķ' => 0.67,
             'L' => 0.61,
             'Ĺ' => 0.61,
             'Ľ' => 0.61,
             'Ļ' => 0.61,
             'Ł' => 0.61,
-            'M' => 0.83,
+            'M' => 0.89,
             'N' => 0.72,
             'Ń' => 0.72,
             'Ň' => 0.72,
             'Ņ' => 0.72,
             'Ñ' => 0.72,
-            'O' => 0.78,
-            'Œ' => 1.00,
-            'Ó' => 0.78,
-            'Ô' => 0.78,
-            'Ö' => 0.78,
-            'Ò' => 0.78,
-            'Ő' => 0.78,
-            'Ō' => 0.78,
-            'Ø' => 0.78,
-            'Õ' => 0.78,
-            'P' => 0.67,
-            'Q' => 0.78,
-            'R' => 0.72,
-            'Ŕ' => 0.72,
-            'Ř' => 0.72,
-            'Ŗ' => 0.72,
-            'S' => 0.67,
-            'Ś' => 0.67,
-            'Š' => 0.67,
-            'Ş' => 0.67,
-            'Ș' => 0.67,
+            'O' => 0.72,
+            'Œ' => 0.94,
+            'Ó' => 0.72,
+            'Ô' => 0.72,
+            'Ö' => 0.72,
+            'Ò' => 0.72,
+            'Ő' => 0.72,
+            'Ō' => 0.72,
+            'Ø' => 0.72,
+            'Õ' => 0.72,
+            'P' => 0.61,
+            'Q' => 0.72,
+            'R' => 0.67,
+            'Ŕ' => 0.67,
+            'Ř' => 0.67,
+            'Ŗ' => 0.67,
+            'S' => 0.56,
+            'Ś' => 0.56,
+            'Š' => 0.56,
+            'Ş' => 0.56,
+            'Ș' => 0.56,
             'T' => 0.61,
             'Ť' => 0.61,
             'Ţ' => 0.61,
-            'Þ' => 0.67,
+            'Þ' => 0.61,
             'U' => 0.72,
             'Ú' => 0.72,
             'Û' => 0.72,
@@ -3280,102 +3152,102 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ų' => 0.72,
             'Ů' => 0.72,
             'V' => 0.67,
-            'W' => 0.94,
+            'W' => 0.89,
             'X' => 0.67,
-            'Y' => 0.67,
-            'Ý' => 0.67,
-            'Ÿ' => 0.67,
+            'Y' => 0.61,
+            'Ý' => 0.61,
+            'Ÿ' => 0.61,
             'Z' => 0.61,
             'Ź' => 0.61,
             'Ž' => 0.61,
             'Ż' => 0.61,
-            'a' => 0.56,
-            'á' => 0.56,
-            'ă' => 0.56,
-            'â' => 0.56,
+            'a' => 0.50,
+            'á' => 0.50,
+            'ă' => 0.50,
+            'â' => 0.50,
             '´' => 0.33,
-            'ä' => 0.56,
-            'æ' => 0.89,
-            'à' => 0.56,
-            'ā' => 0.56,
-            '&' => 0.72,
-            'ą' => 0.56,
-            'å' => 0.56,
-            '^' => 0.58,
-            '~' => 0.58,
-            '*' => 0.39,
-            '@' => 0.97,
-            'ã' => 0.56,
-            'b' => 0.61,
+            'ä' => 0.50,
+            'æ' => 0.72,
+            'à' => 0.50,
+            'ā' => 0.50,
+            '&' => 0.78,
+            'ą' => 0.50,
+            'å' => 0.50,
+            '^' => 0.57,
+            '~' => 0.57,
+            '*' => 0.50,
+            '@' => 0.83,
+            'ã' => 0.50,
+            'b' => 0.50,
             '\\' => 0.28,
-            '|' => 0.28,
-            '{' => 0.39,
-            '}' => 0.39,
+            '|' => 0.22,
+            '{' => 0.35,
+            '}' => 0.35,
             '[' => 0.33,
             ']' => 0.33,
             '˘' => 0.33,
-            '¦' => 0.28,
+            '¦' => 0.22,
             '•' => 0.35,
-            'c' => 0.56,
-            'ć' => 0.56,
+            'c' => 0.44,
+            'ć' => 0.44,
             'ˇ' => 0.33,
-            'č' => 0.56,
-            'ç' => 0.56,
+            'č' => 0.44,
+            'ç' => 0.44,
             '¸' => 0.33,
-            '¢' => 0.56,
+            '¢' => 0.50,
             'ˆ' => 0.33,
             ':' => 0.33,
-            ',' => 0.28,
+            ',' => 0.25,
             '' => 0.25,
-            '©' => 0.74,
-            '¤' => 0.56,
-            'd' => 0.61,
-            '†' => 0.56,
-            '‡' => 0.56,
-            'ď' => 0.74,
-            'đ' => 0.61,
+            '©' => 0.75,
+            '¤' => 0.50,
+            'd' => 0.50,
+            '†' => 0.50,
+            '‡' => 0.50,
+            'ď' => 0.61,
+            'đ' => 0.50,
             '°' => 0.40,
             '¨' => 0.33,
-            '÷' => 0.58,
-            '$' => 0.56,
+            '÷' => 0.57,
+            '$' => 0.50,
             '˙' => 0.33,
-            'ı' => 0.28,
-            'e' => 0.56,
-            'é' => 0.56,
-            'ě' => 0.56,
-            'ê' => 0.56,
-            'ë' => 0.56,
-            'ė' => 0.56,
-            'è' => 0.56,
-            '8' => 0.56,
+            'ı' => 0.28,
+            'e' => 0.44,
+            'é' => 0.44,
+            'ě' => 0.44,
+            'ê' => 0.44,
+            'ë' => 0.44,
+            'ė' => 0.44,
+            'è' => 0.44,
+            '8' => 0.50,
             '…' => 1.00,
-            'ē' => 0.56,
+            'ē' => 0.44,
             '—' => 1.00,
-            '–' => 0.56,
-            'ę' => 0.56,
-            '=' => 0.58,
-            'ð' => 0.61,
-            '!' => 0.33,
-            '¡' => 0.33,
+            '–' => 0.50,
+            'ę' => 0.44,
+            '=' => 0.57,
+            'ð' => 0.50,
+            '!' => 0.39,
+            '¡' => 0.39,
             'f' => 0.33,
-            'ﬁ' => 0.61,
-            '5' => 0.56,
-            'ﬂ' => 0.61,
-            'ƒ' => 0.56,
-            '4' => 0.56,
+            'ﬁ' => 0.56,
+            '5' => 0.50,
+            'ﬂ' => 0.56,
+            'ƒ' => 0.50,
+            '4' => 0.50,
             '⁄' => 0.17,
-            'g' => 0.61,
-            'ğ' => 0.61,
-            'ģ' => 0.61,
-            'ß' => 0.61,
+            'g' => 0.50,
+            'ğ' => 0.50,
+            'ģ' => 0.50,
+            'ß' => 0.50,
             '`' => 0.33,
-            '>' => 0.58,
+            '>' => 0.57,
             '≥' => 0.55,
-            '«' => 0.56,
-            '»' => 0.56,
+            '«' => 0.50,
+            '»' => 0.50,
             '‹' => 0.33,
             '›' => 0.33,
-            'h' => 0.61,
+            'h' => 0.56,
             '˝' => 0.33,
             '-' => 0.33,
             'i' => 0.28,
@@ -3386,320 +3258,445 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ī' => 0.28,
             'į' => 0.28,
             'j' => 0.28,
-            'k' => 0.56,
-            'ķ' => 0.56,
+            'k' => 0.50,
+            'ķ' => 0.50,
             'l' => 0.28,
             'ĺ' => 0.28,
-            'ľ' => 0.40,
+            'ľ' => 0.38,
             'ļ' => 0.28,
-            '<' => 0.58,
+            '<' => 0.57,
             '≤' => 0.55,
-            '¬' => 0.58,
+            '¬' => 0.61,
             '◊' => 0.49,
             'ł' => 0.28,
-            'm' => 0.89,
+            'm' => 0.78,
             '¯' => 0.33,
-            '−' => 0.58,
-            'µ' => 0.61,
-            '×' => 0.58,
-            'n' => 0.61,
-            'ń' => 0.61,
-            'ň' => 0.61,
-            'ņ' => 0.61,
-            '9' => 0.56,
+            '−' => 0.61,
+            'µ' => 0.58,
+            '×' => 0.57,
+            'n' => 0.56,
+            'ń' => 0.56,
+            'ň' => 0.56,
+            'ņ' => 0.56,
+            '9' => 0.50,
             '≠' => 0.55,
-            'ñ' => 0.61,
-            '#' => 0.56,
-            'o' => 0.61,
-            'ó' => 0.61,
-            'ô' => 0.61,
-            'ö' => 0.61,
-            'œ' => 0.94,
+            'ñ' => 0.56,
+            '#' => 0.50,
+            'o' => 0.50,
+            'ó' => 0.50,
+            'ô' => 0.50,
+            'ö' => 0.50,
+            'œ' => 0.72,
             '˛' => 0.33,
-            'ò' => 0.61,
-            'ő' => 0.61,
-            'ō' => 0.61,
-            '1' => 0.56,
-            '½' => 0.83,
-            '¼' => 0.83,
-            '¹' => 0.33,
-            'ª' => 0.37,
-            'º' => 0.36,
-            'ø' => 0.61,
-            'õ' => 0.61,
-            'p' => 0.61,
-            '¶' => 0.56,
+            'ò' => 0.50,
+            'ő' => 0.50,
+            'ō' => 0.50,
+            '1' => 0.50,
+            '½' => 0.75,
+            '¼' => 0.75,
+            '¹' => 0.30,
+            'ª' => 0.27,
+            'º' => 0.30,
+            'ø' => 0.50,
+            'õ' => 0.50,
+            'p' => 0.50,
+            '¶' => 0.50,
             '(' => 0.33,
             ')' => 0.33,
             '∂' => 0.49,
-            '%' => 0.89,
-            '.' => 0.28,
-            '·' => 0.28,
+            '%' => 0.83,
+            '.' => 0.25,
+            '·' => 0.25,
             '‰' => 1.00,
-            '+' => 0.58,
-            '±' => 0.58,
-            'q' => 0.61,
-            '?' => 0.61,
-            '¿' => 0.61,
-            '"' => 0.47,
+            '+' => 0.57,
+            '±' => 0.57,
+            'q' => 0.50,
+            '?' => 0.50,
+            '¿' => 0.50,
+            '"' => 0.56,
             '„' => 0.50,
             '“' => 0.50,
             '”' => 0.50,
-            '‘' => 0.28,
-            '’' => 0.28,
-            '‚' => 0.28,
-            '\'' => 0.24,
+            '‘' => 0.33,
+            '’' => 0.33,
+            '‚' => 0.33,
+            '\'' => 0.28,
             'r' => 0.39,
             'ŕ' => 0.39,
             '√' => 0.55,
             'ř' => 0.39,
             'ŗ' => 0.39,
-            '®' => 0.74,
+            '®' => 0.75,
             '˚' => 0.33,
-            's' => 0.56,
-            'ś' => 0.56,
-            'š' => 0.56,
-            'ş' => 0.56,
-            'ș' => 0.56,
-            '§' => 0.56,
+            's' => 0.39,
+            'ś' => 0.39,
+            'š' => 0.39,
+            'ş' => 0.39,
+            'ș' => 0.39,
+            '§' => 0.50,
             ';' => 0.33,
-            '7' => 0.56,
-            '6' => 0.56,
+            '7' => 0.50,
+            '6' => 0.50,
             '/' => 0.28,
-            ' ' => 0.28,
-            '£' => 0.56,
+            ' ' => 0.25,
+            '£' => 0.50,
             '∑' => 0.60,
-            't' => 0.33,
-            'ť' => 0.39,
-            'ţ' => 0.33,
-            'þ' => 0.61,
-            '3' => 0.56,
-            '¾' => 0.83,
-            '³' => 0.33,
+            't' => 0.28,
+            'ť' => 0.37,
+            'ţ' => 0.28,
+            'þ' => 0.50,
+            '3' => 0.50,
+            '¾' => 0.75,
+            '³' => 0.30,
             '˜' => 0.33,
             '™' => 1.00,
-            '2' => 0.56,
-            '²' => 0.33,
-            'u' => 0.61,
-            'ú' => 0.61,
-            'û' => 0.61,
-            'ü' => 0.61,
-            'ù' => 0.61,
-            'ű' => 0.61,
-            'ū' => 0.61,
-            '_' => 0.56,
-            'ų' => 0.61,
-            'ů' => 0.61,
-            'v' => 0.56,
-            'w' => 0.78,
-            'x' => 0.56,
-            'y' => 0.56,
-            'ý' => 0.56,
-            'ÿ' => 0.56,
-            '¥' => 0.56,
-            'z' => 0.50,
-            'ź' => 0.50,
-            'ž' => 0.50,
-            'ż' => 0.50,
-            '0' => 0.56,
-            _ => 0.0,
-        },
-        &Font::Symbol => match c {
-            'Α' => 0.72,
-            'Β' => 0.67,
-            'Χ' => 0.72,
-            '∆' => 0.61,
-            'Ε' => 0.61,
-            'Η' => 0.72,
-            '€' => 0.75,
-            'Γ' => 0.60,
-            'ℑ' => 0.69,
-            'Ι' => 0.33,
-            'Κ' => 0.72,
-            'Λ' => 0.69,
-            'Μ' => 0.89,
-            'Ν' => 0.72,
-            'Ω' => 0.77,
-            'Ο' => 0.72,
-            'Φ' => 0.76,
-            'Π' => 0.77,
-            'Ψ' => 0.80,
-            'ℜ' => 0.80,
-            'Ρ' => 0.56,
-            'Σ' => 0.59,
-            'Τ' => 0.61,
-            'Θ' => 0.74,
-            'Υ' => 0.69,
-            'ϒ' => 0.62,
-            'Ξ' => 0.65,
-            'Ζ' => 0.61,
-            'ℵ' => 0.82,
-            'α' => 0.63,
+            '2' => 0.50,
+            '²' => 0.30,
+            'u' => 0.56,
+            'ú' => 0.56,
+            'û' => 0.56,
+            'ü' => 0.56,
+            'ù' => 0.56,
+            'ű' => 0.56,
+            'ū' => 0.56,
+            '_' => 0.50,
+            'ų' => 0.56,
+            'ů' => 0.56,
+            'v' => 0.44,
+            'w' => 0.67,
+            'x' => 0.50,
+            'y' => 0.44,
+            'ý' => 0.44,
+            'ÿ' => 0.44,
+            '¥' => 0.50,
+            'z' => 0.39,
+            'ź' => 0.39,
+            'ž' => 0.39,
+            'ż' => 0.39,
+            '0' => 0.50,
+            _ => 0.0,
+        },
+        Font::TimesItalic => match c {
+            'A' => 0.61,
+            'Æ' => 0.89,
+            'Á' => 0.61,
+            'Ă' => 0.61,
+            'Â' => 0.61,
+            'Ä' => 0.61,
+            'À' => 0.61,
+            'Ā' => 0.61,
+            'Ą' => 0.61,
+            'Å' => 0.61,
+            'Ã' => 0.61,
+            'B' => 0.61,
+            'C' => 0.67,
+            'Ć' => 0.67,
+            'Č' => 0.67,
+            'Ç' => 0.67,
+            'D' => 0.72,
+            'Ď' => 0.72,
+            'Đ' => 0.72,
+            '∆' => 0.61,
+            'E' => 0.61,
+            'É' => 0.61,
+            'Ě' => 0.61,
+            'Ê' => 0.61,
+            'Ë' => 0.61,
+            'Ė' => 0.61,
+            'È' => 0.61,
+            'Ē' => 0.61,
+            'Ę' => 0.61,
+            'Ð' => 0.72,
+            '€' => 0.50,
+            'F' => 0.61,
+            'G' => 0.72,
+            'Ğ' => 0.72,
+            'Ģ' => 0.72,
+            'H' => 0.72,
+            'I' => 0.33,
+            'Í' => 0.33,
+            'Î' => 0.33,
+            'Ï' => 0.33,
+            'İ' => 0.33,
+            'Ì' => 0.33,
+            'Ī' => 0.33,
+            'Į' => 0.33,
+            'J' => 0.44,
+            'K' => 0.67,
+            'Ķ' => 0.67,
+            'L' => 0.56,
+            'Ĺ' => 0.56,
+            'Ľ' => 0.61,
+            'Ļ' => 0.56,
+            'Ł' => 0.56,
+            'M' => 0.83,
+            'N' => 0.67,
+            'Ń' => 0.67,
+            'Ň' => 0.67,
+            'Ņ' => 0.67,
+            'Ñ' => 0.67,
+            'O' => 0.72,
+            'Œ' => 0.94,
+            'Ó' => 0.72,
+            'Ô' => 0.72,
+            'Ö' => 0.72,
+            'Ò' => 0.72,
+            'Ő' => 0.72,
+            'Ō' => 0.72,
+            'Ø' => 0.72,
+            'Õ' => 0.72,
+            'P' => 0.61,
+            'Q' => 0.72,
+            'R' => 0.61,
+            'Ŕ' => 0.61,
+            'Ř' => 0.61,
+            'Ŗ' => 0.61,
+            'S' => 0.50,
+            'Ś' => 0.50,
+            'Š' => 0.50,
+            'Ş' => 0.50,
+            'Ș' => 0.50,
+            'T' => 0.56,
+            'Ť' => 0.56,
+            'Ţ' => 0.56,
+            'Þ' => 0.61,
+            'U' => 0.72,
+            'Ú' => 0.72,
+            'Û' => 0.72,
+            'Ü' => 0.72,
+            'Ù' => 0.72,
+            'Ű' => 0.72,
+            'Ū' => 0.72,
+            'Ų' => 0.72,
+            'Ů' => 0.72,
+            'V' => 0.61,
+            'W' => 0.83,
+            'X' => 0.61,
+            'Y' => 0.56,
+            'Ý' => 0.56,
+            'Ÿ' => 0.56,
+            'Z' => 0.56,
+            'Ź' => 0.56,
+            'Ž' => 0.56,
+            'Ż' => 0.56,
+            'a' => 0.50,
+            'á' => 0.50,
+            'ă' => 0.50,
+            'â' => 0.50,
+            '´' => 0.33,
+            'ä' => 0.50,
+            'æ' => 0.67,
+            'à' => 0.50,
+            'ā' => 0.50,
             '&' => 0.78,
-            '∠' => 0.77,
-            '〈' => 0.33,
-            '〉' => 0.33,
-            '' => 0.79,
-            '≈' => 0.55,
-            '↔' => 1.04,
-            '⇔' => 1.04,
-            '⇓' => 0.60,
-            '⇐' => 0.99,
-            '⇒' => 0.99,
-            '⇑' => 0.60,
-            '↓' => 0.60,
-            '' => 1.00,
-            '←' => 0.99,
-            '→' => 0.99,
-            '↑' => 0.60,
-            '' => 0.60,
-            '∗' => 0.50,
-            '|' => 0.20,
-            'β' => 0.55,
-            '' => 0.49,
-            '{' => 0.48,
-            '' => 0.49,
-            '' => 0.49,
-            '' => 0.49,
-            '}' => 0.48,
-            '' => 0.49,
-            '' => 0.49,
-            '' => 0.49,
-            '[' => 0.33,
-            '' => 0.38,
-            '' => 0.38,
-            '' => 0.38,
-            ']' => 0.33,
-            '' => 0.38,
-            '' => 0.38,
-            '' => 0.38,
-            '•' => 0.46,
-            '↵' => 0.66,
-            'χ' => 0.55,
-            '⊗' => 0.77,
-            '⊕' => 0.77,
-            '♣' => 0.75,
-            ':' => 0.28,
+            'ą' => 0.50,
+            'å' => 0.50,
+            '^' => 0.42,
+            '~' => 0.54,
+            '*' => 0.50,
+            '@' => 0.92,
+            'ã' => 0.50,
+            'b' => 0.50,
+            '\\' => 0.28,
+            '|' => 0.28,
+            '{' => 0.40,
+            '}' => 0.40,
+            '[' => 0.39,
+            ']' => 0.39,
+            '˘' => 0.33,
+            '¦' => 0.28,
+            '•' => 0.35,
+            'c' => 0.44,
+            'ć' => 0.44,
+            'ˇ' => 0.33,
+            'č' => 0.44,
+            'ç' => 0.44,
+            '¸' => 0.33,
+            '¢' => 0.50,
+            'ˆ' => 0.33,
+            ':' => 0.33,
             ',' => 0.25,
-            '≅' => 0.55,
-            '' => 0.79,
-            '' => 0.79,
+            '' => 0.25,
+            '©' => 0.76,
+            '¤' => 0.50,
+            'd' => 0.50,
+            '†' => 0.50,
+            '‡' => 0.50,
+            'ď' => 0.54,
+            'đ' => 0.50,
             '°' => 0.40,
-            'δ' => 0.49,
-            '♦' => 0.75,
-            '÷' => 0.55,
-            '⋅' => 0.25,
+            '¨' => 0.33,
+            '÷' => 0.68,
+            '$' => 0.50,
+            '˙' => 0.33,
+            'ı' => 0.28,
+            'e' => 0.44,
+            'é' => 0.44,
+            'ě' => 0.44,
+            'ê' => 0.44,
+            'ë' => 0.44,
+            'ė' => 0.44,
+            'è' => 0.44,
             '8' => 0.50,
-            '∈' => 0.71,
-            '…' => 1.00,
-            '∅' => 0.82,
-            'ε' => 0.44,
-            '=' => 0.55,
-            '≡' => 0.55,
-            'η' => 0.60,
+            '…' => 0.89,
+            'ē' => 0.44,
+            '—' => 0.89,
+            '–' => 0.50,
+            'ę' => 0.44,
+            '=' => 0.68,
+            'ð' => 0.50,
             '!' => 0.33,
-            '∃' => 0.55,
+            '¡' => 0.39,
+            'f' => 0.28,
+            'ﬁ' => 0.50,
             '5' => 0.50,
+            'ﬂ' => 0.50,
             'ƒ' => 0.50,
             '4' => 0.50,
             '⁄' => 0.17,
-            'γ' => 0.41,
-            '∇' => 0.71,
-            '>' => 0.55,
+            'g' => 0.50,
+            'ğ' => 0.50,
+            'ģ' => 0.50,
+            'ß' => 0.50,
+            '`' => 0.33,
+            '>' => 0.68,
             '≥' => 0.55,
-            '♥' => 0.75,
-            '∞' => 0.71,
-            '∫' => 0.27,
-            '⌡' => 0.69,
-            '' => 0.69,
-            '⌠' => 0.69,
-            '∩' => 0.77,
-            'ι' => 0.33,
-            'κ' => 0.55,
-            'λ' => 0.55,
-            '<' => 0.55,
+            '«' => 0.50,
+            '»' => 0.50,
+            '‹' => 0.33,
+            '›' => 0.33,
+            'h' => 0.50,
+            '˝' => 0.33,
+            '-' => 0.33,
+            'i' => 0.28,
+            'í' => 0.28,
+            'î' => 0.28,
+            'ï' => 0.28,
+            'ì' => 0.28,
+            'ī' => 0.28,
+            'į' => 0.28,
+            'j' => 0.28,
+            'k' => 0.44,
+            'ķ' => 0.44,
+            'l' => 0.28,
+            'ĺ' => 0.28,
+            'ľ' => 0.30,
+            'ļ' => 0.28,
+            '<' => 0.68,
             '≤' => 0.55,
-            '∧' => 0.60,
-            '¬' => 0.71,
-            '∨' => 0.60,
-            '◊' => 0.49,
-            '−' => 0.55,
-            '′' => 0.25,
-            'µ' => 0.58,
-            '×' => 0.55,
+            '¬' => 0.68,
+            '◊' => 0.47,
+            'ł' => 0.28,
+            'm' => 0.72,
+            '¯' => 0.33,
+            '−' => 0.68,
+            'µ' => 0.50,
+            '×' => 0.68,
+            'n' => 0.50,
+            'ń' => 0.50,
+            'ň' => 0.50,
+            'ņ' => 0.50,
             '9' => 0.50,
-            '∉' => 0.71,
             '≠' => 0.55,
-            '⊄' => 0.71,
-            'ν' => 0.52,
+            'ñ' => 0.50,
             '#' => 0.50,
-            'ω' => 0.69,
-            'ϖ' => 0.71,
-            'ο' => 0.55,
+            'o' => 0.50,
+            'ó' => 0.50,
+            'ô' => 0.50,
+            'ö' => 0.50,
+            'œ' => 0.67,
+            '˛' => 0.33,
+            'ò' => 0.50,
+            'ő' => 0.50,
+            'ō' => 0.50,
             '1' => 0.50,
+            '½' => 0.75,
+            '¼' => 0.75,
+            '¹' => 0.30,
+            'ª' => 0.28,
+            'º' => 0.31,
+            'ø' => 0.50,
+            'õ' => 0.50,
+            'p' => 0.50,
+            '¶' => 0.52,
             '(' => 0.33,
-            '' => 0.38,
-            '' => 0.38,
-            '' => 0.38,
             ')' => 0.33,
-            '' => 0.38,
-            '' => 0.38,
-            '' => 0.38,
-            '∂' => 0.49,
+            '∂' => 0.48,
             '%' => 0.83,
             '.' => 0.25,
-            '⊥' => 0.66,
-            'φ' => 0.52,
-            'ϕ' => 0.60,
-            'π' => 0.55,
-            '+' => 0.55,
-            '±' => 0.55,
-            '∏' => 0.82,
-            '⊂' => 0.71,
-            '⊃' => 0.71,
-            '∝' => 0.71,
-            'ψ' => 0.69,
-            '?' => 0.44,
-            '√' => 0.55,
-            '' => 0.50,
-            '⊆' => 0.71,
-            '⊇' => 0.71,
-            '' => 0.79,
-            '' => 0.79,
-            'ρ' => 0.55,
-            '″' => 0.41,
-            ';' => 0.28,
+            '·' => 0.25,
+            '‰' => 1.00,
+            '+' => 0.68,
+            '±' => 0.68,
+            'q' => 0.50,
+            '?' => 0.50,
+            '¿' => 0.50,
+            '"' => 0.42,
+            '„' => 0.56,
+            '“' => 0.56,
+            '”' => 0.56,
+            '‘' => 0.33,
+            '’' => 0.33,
+            '‚' => 0.33,
+            '\'' => 0.21,
+            'r' => 0.39,
+            'ŕ' => 0.39,
+            '√' => 0.45,
+            'ř' => 0.39,
+            'ŗ' => 0.39,
+            '®' => 0.76,
+            '˚' => 0.33,
+            's' => 0.39,
+            'ś' => 0.39,
+            'š' => 0.39,
+            'ş' => 0.39,
+            'ș' => 0.39,
+            '§' => 0.50,
+            ';' => 0.33,
             '7' => 0.50,
-            'σ' => 0.60,
-            'ς' => 0.44,
-            '∼' => 0.55,
             '6' => 0.50,
             '/' => 0.28,
             ' ' => 0.25,
-            '♠' => 0.75,
-            '∋' => 0.44,
-            '∑' => 0.71,
-            'τ' => 0.44,
-            '∴' => 0.86,
-            'θ' => 0.52,
-            'ϑ' => 0.63,
+            '£' => 0.50,
+            '∑' => 0.60,
+            't' => 0.28,
+            'ť' => 0.30,
+            'ţ' => 0.28,
+            'þ' => 0.50,
             '3' => 0.50,
-            '' => 0.79,
-            '' => 0.89,
+            '¾' => 0.75,
+            '³' => 0.30,
+            '˜' => 0.33,
+            '™' => 0.98,
             '2' => 0.50,
+            '²' => 0.30,
+            'u' => 0.50,
+            'ú' => 0.50,
+            'û' => 0.50,
+            'ü' => 0.50,
+            'ù' => 0.50,
+            'ű' => 0.50,
+            'ū' => 0.50,
             '_' => 0.50,
-            '∪' => 0.77,
-            '∀' => 0.71,
-            'υ' => 0.58,
-            '℘' => 0.99,
-            'ξ' => 0.49,
+            'ų' => 0.50,
+            'ů' => 0.50,
+            'v' => 0.44,
+            'w' => 0.67,
+            'x' => 0.44,
+            'y' => 0.44,
+            'ý' => 0.44,
+            'ÿ' => 0.44,
+            '¥' => 0.50,
+            'z' => 0.39,
+            'ź' => 0.39,
+            'ž' => 0.39,
+            'ż' => 0.39,
             '0' => 0.50,
-            'ζ' => 0.49,
             _ => 0.0,
         },
-        &Font::TimesBold => match c {
+        Font::TimesRoman => match c {
             'A' => 0.72,
-            'Æ' => 1.00,
+            'Æ' => 0.89,
             'Á' => 0.72,
             'Ă' => 0.72,
             'Â' => 0.72,
@@ -3710,77 +3707,77 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Å' => 0.72,
             'Ã' => 0.72,
             'B' => 0.67,
-            'C' => 0.72,
-            'Ć' => 0.72,
-            'Č' => 0.72,
-            'Ç' => 0.72,
+            'C' => 0.67,
+            'Ć' => 0.67,
+            'Č' => 0.67,
+            'Ç' => 0.67,
             'D' => 0.72,
             'Ď' => 0.72,
             'Đ' => 0.72,
             '∆' => 0.61,
-            'E' => 0.67,
-            'É' => 0.67,
-            'Ě' => 0.67,
-            'Ê' => 0.67,
-            'Ë' => 0.67,
-            'Ė' => 0.67,
-            'È' => 0.67,
-            'Ē' => 0.67,
-            'Ę' => 0.67,
+            'E' => 0.61,
+            'É' => 0.61,
+            'Ě' => 0.61,
+            'Ê' => 0.61,
+            'Ë' => 0.61,
+            'Ė' => 0.61,
+            'È' => 0.61,
+            'Ē' => 0.61,
+            'Ę' => 0.61,
             'Ð' => 0.72,
             '€' => 0.50,
-            'F' => 0.61,
-            'G' => 0.78,
-            'Ğ' => 0.78,
-            'Ģ' => 0.78,
-            'H' => 0.78,
-            'I' => 0.39,
-            'Í' => 0.39,
-            'Î' => 0.39,
-            'Ï' => 0.39,
-            'İ' => 0.39,
-            'Ì' => 0.39,
-            'Ī' => 0.39,
-            'Į' => 0.39,
-            'J' => 0.50,
-            'K' => 0.78,
-            'Ķ' => 0.78,
-            'L' => 0.67,
-            'Ĺ' => 0.67,
-            'Ľ' => 0.67,
-            'Ļ' => 0.67,
-            'Ł' => 0.67,
-            'M' => 0.94,
-            'N' => 0.72,
-            'Ń' => 0.72,
-            'Ň' => 0.72,
-            'Ņ' => 0.72,
-            'Ñ' => 0.72,
-            'O' => 0.78,
-            'Œ' => 1.00,
-            'Ó' => 0.78,
-            'Ô' => 0.78,
-            'Ö' => 0.78,
-            'Ò' => 0.78,
-            'Ő' => 0.78,
-            'Ō' => 0.78,
-            'Ø' => 0.78,
-            'Õ' => 0.78,
-            'P' => 0.61,
-            'Q' => 0.78,
-            'R' => 0.72,
-            'Ŕ' => 0.72,
-            'Ř' => 0.72,
-            'Ŗ' => 0.72,
+            'F' => 0.56,
+            'G' => 0.72,
+            'Ğ' => 0.72,
+            'Ģ' => 0.72,
+            'H' => 0.72,
+            'I' => 0.33,
+            'Í' => 0.33,
+            'Î' => 0.33,
+            'Ï' => 0.33,
+            'İ' => 0.33,
+            'Ì' => 0.33,
+            'Ī' => 0.33,
+            'Į' => 0.33,
+            'J' => 0.39,
+            'K' => 0.72,
+            'Ķ' => 0.72,
+            'L' => 0.61,
+            'Ĺ' => 0.61,
+            'Ľ' => 0.61,
+            'Ļ' => 0.61,
+            'Ł' => 0.61,
+            'M' => 0.89,
+            'N' => 0.72,
+            'Ń' => 0.72,
+            'Ň' => 0.72,
+            'Ņ' => 0.72,
+            'Ñ' => 0.72,
+            'O' => 0.72,
+            'Œ' => 0.89,
+            'Ó' => 0.72,
+            'Ô' => 0.72,
+            'Ö' => 0.72,
+            'Ò' => 0.72,
+            'Ő' => 0.72,
+            'Ō' => 0.72,
+            'Ø' => 0.72,
+            'Õ' => 0.72,
+            'P' => 0.56,
+            'Q' => 0.72,
+            'R' => 0.67,
+            'Ŕ' => 0.67,
+            'Ř' => 0.67,
+            'Ŗ' => 0.67,
             'S' => 0.56,
             'Ś' => 0.56,
             'Š' => 0.56,
             'Ş' => 0.56,
             'Ș' => 0.56,
-            'T' => 0.67,
-            'Ť' => 0.67,
-            'Ţ' => 0.67,
-            'Þ' => 0.61,
+            'T' => 0.61,
+            'Ť' => 0.61,
+            'Ţ' => 0.61,
+            'Þ' => 0.56,
             'U' => 0.72,
             'Ú' => 0.72,
             'Û' => 0.72,
@@ -3791,41 +3788,41 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'Ų' => 0.72,
             'Ů' => 0.72,
             'V' => 0.72,
-            'W' => 1.00,
+            'W' => 0.94,
             'X' => 0.72,
             'Y' => 0.72,
             'Ý' => 0.72,
             'Ÿ' => 0.72,
-            'Z' => 0.67,
-            'Ź' => 0.67,
-            'Ž' => 0.67,
-            'Ż' => 0.67,
-            'a' => 0.50,
-            'á' => 0.50,
-            'ă' => 0.50,
-            'â' => 0.50,
+            'Z' => 0.61,
+            'Ź' => 0.61,
+            'Ž' => 0.61,
+            'Ż' => 0.61,
+            'a' => 0.44,
+            'á' => 0.44,
+            'ă' => 0.44,
+            'â' => 0.44,
             '´' => 0.33,
-            'ä' => 0.50,
-            'æ' => 0.72,
-            'à' => 0.50,
-            'ā' => 0.50,
-            '&' => 0.83,
-            'ą' => 0.50,
-            'å' => 0.50,
-            '^' => 0.58,
-            '~' => 0.52,
+            'ä' => 0.44,
+            'æ' => 0.67,
+            'à' => 0.44,
+            'ā' => 0.44,
+            '&' => 0.78,
+            'ą' => 0.44,
+            'å' => 0.44,
+            '^' => 0.47,
+            '~' => 0.54,
             '*' => 0.50,
-            '@' => 0.93,
-            'ã' => 0.50,
-            'b' => 0.56,
+            '@' => 0.92,
+            'ã' => 0.44,
+            'b' => 0.50,
             '\\' => 0.28,
-            '|' => 0.22,
-            '{' => 0.39,
-            '}' => 0.39,
+            '|' => 0.20,
+            '{' => 0.48,
+            '}' => 0.48,
             '[' => 0.33,
             ']' => 0.33,
             '˘' => 0.33,
-            '¦' => 0.22,
+            '¦' => 0.20,
             '•' => 0.35,
             'c' => 0.44,
             'ć' => 0.44,
@@ -3835,19 +3832,19 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '¸' => 0.33,
             '¢' => 0.50,
             'ˆ' => 0.33,
-            ':' => 0.33,
+            ':' => 0.28,
             ',' => 0.25,
             '' => 0.25,
-            '©' => 0.75,
+            '©' => 0.76,
             '¤' => 0.50,
-            'd' => 0.56,
+            'd' => 0.50,
             '†' => 0.50,
             '‡' => 0.50,
-            'ď' => 0.67,
-            'đ' => 0.56,
+            'ď' => 0.59,
+            'đ' => 0.50,
             '°' => 0.40,
             '¨' => 0.33,
-            '÷' => 0.57,
+            '÷' => 0.56,
             '$' => 0.50,
             '˙' => 0.33,
             'ı' => 0.28,
@@ -3864,7 +3861,7 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '—' => 1.00,
             '–' => 0.50,
             'ę' => 0.44,
-            '=' => 0.57,
+            '=' => 0.56,
             'ð' => 0.50,
             '!' => 0.33,
             '¡' => 0.33,
@@ -3878,15 +3875,15 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'g' => 0.50,
             'ğ' => 0.50,
             'ģ' => 0.50,
-            'ß' => 0.56,
+            'ß' => 0.50,
             '`' => 0.33,
-            '>' => 0.57,
+            '>' => 0.56,
             '≥' => 0.55,
             '«' => 0.50,
             '»' => 0.50,
             '‹' => 0.33,
             '›' => 0.33,
-            'h' => 0.56,
+            'h' => 0.50,
             '˝' => 0.33,
             '-' => 0.33,
             'i' => 0.28,
@@ -3896,30 +3893,30 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ì' => 0.28,
             'ī' => 0.28,
             'į' => 0.28,
-            'j' => 0.33,
-            'k' => 0.56,
-            'ķ' => 0.56,
+            'j' => 0.28,
+            'k' => 0.50,
+            'ķ' => 0.50,
             'l' => 0.28,
             'ĺ' => 0.28,
-            'ľ' => 0.39,
+            'ľ' => 0.34,
             'ļ' => 0.28,
-            '<' => 0.57,
+            '<' => 0.56,
             '≤' => 0.55,
-            '¬' => 0.57,
-            '◊' => 0.49,
+            '¬' => 0.56,
+            '◊' => 0.47,
             'ł' => 0.28,
-            'm' => 0.83,
+            'm' => 0.78,
             '¯' => 0.33,
-            '−' => 0.57,
-            'µ' => 0.56,
-            '×' => 0.57,
-            'n' => 0.56,
-            'ń' => 0.56,
-            'ň' => 0.56,
-            'ņ' => 0.56,
+            '−' => 0.56,
+            'µ' => 0.50,
+            '×' => 0.56,
+            'n' => 0.50,
+            'ń' => 0.50,
+            'ň' => 0.50,
+            'ņ' => 0.50,
             '9' => 0.50,
             '≠' => 0.55,
-            'ñ' => 0.56,
+            'ñ' => 0.50,
             '#' => 0.50,
             'o' => 0.50,
             'ó' => 0.50,
@@ -3934,38 +3931,38 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '½' => 0.75,
             '¼' => 0.75,
             '¹' => 0.30,
-            'ª' => 0.30,
-            'º' => 0.33,
+            'ª' => 0.28,
+            'º' => 0.31,
             'ø' => 0.50,
             'õ' => 0.50,
-            'p' => 0.56,
-            '¶' => 0.54,
+            'p' => 0.50,
+            '¶' => 0.45,
             '(' => 0.33,
             ')' => 0.33,
-            '∂' => 0.49,
-            '%' => 1.00,
+            '∂' => 0.48,
+            '%' => 0.83,
             '.' => 0.25,
             '·' => 0.25,
             '‰' => 1.00,
-            '+' => 0.57,
-            '±' => 0.57,
-            'q' => 0.56,
-            '?' => 0.50,
-            '¿' => 0.50,
-            '"' => 0.56,
-            '„' => 0.50,
-            '“' => 0.50,
-            '”' => 0.50,
+            '+' => 0.56,
+            '±' => 0.56,
+            'q' => 0.50,
+            '?' => 0.44,
+            '¿' => 0.44,
+            '"' => 0.41,
+            '„' => 0.44,
+            '“' => 0.44,
+            '”' => 0.44,
             '‘' => 0.33,
             '’' => 0.33,
             '‚' => 0.33,
-            '\'' => 0.28,
-            'r' => 0.44,
-            'ŕ' => 0.44,
-            '√' => 0.55,
-            'ř' => 0.44,
-            'ŗ' => 0.44,
-            '®' => 0.75,
+            '\'' => 0.18,
+            'r' => 0.33,
+            'ŕ' => 0.33,
+            '√' => 0.45,
+            'ř' => 0.33,
+            'ŗ' => 0.33,
+            '®' => 0.76,
             '˚' => 0.33,
             's' => 0.39,
             'ś' => 0.39,
@@ -3973,34 +3970,34 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             'ş' => 0.39,
             'ș' => 0.39,
             '§' => 0.50,
-            ';' => 0.33,
+            ';' => 0.28,
             '7' => 0.50,
             '6' => 0.50,
             '/' => 0.28,
             ' ' => 0.25,
             '£' => 0.50,
             '∑' => 0.60,
-            't' => 0.33,
-            'ť' => 0.42,
-            'ţ' => 0.33,
-            'þ' => 0.56,
+            't' => 0.28,
+            'ť' => 0.33,
+            'ţ' => 0.28,
+            'þ' => 0.50,
             '3' => 0.50,
             '¾' => 0.75,
             '³' => 0.30,
             '˜' => 0.33,
-            '™' => 1.00,
+            '™' => 0.98,
             '2' => 0.50,
             '²' => 0.30,
-            'u' => 0.56,
-            'ú' => 0.56,
-            'û' => 0.56,
-            'ü' => 0.56,
-            'ù' => 0.56,
-            'ű' => 0.56,
-            'ū' => 0.56,
+            'u' => 0.50,
+            'ú' => 0.50,
+            'û' => 0.50,
+            'ü' => 0.50,
+            'ù' => 0.50,
+            'ű' => 0.50,
+            'ū' => 0.50,
             '_' => 0.50,
-            'ų' => 0.56,
-            'ů' => 0.56,
+            'ų' => 0.50,
+            'ů' => 0.50,
             'v' => 0.50,
             'w' => 0.72,
             'x' => 0.50,
@@ -4015,23 +4012,403 @@ pub fn glyph_width(font: &Font, c: char) -> f64 {
             '0' => 0.50,
             _ => 0.0,
         },
+        Font::ZapfDingbats => match c {
+            ' ' => 0.28,
+            _ => 0.0,
+        },
+    }
+}
+
+/// The code for `c` in `font`'s own encoding, for fonts which don't use `WinAnsi`
+pub fn builtin_code(font: &Font, c: char) -> Option<u8> {
+    match font {
+        Font::Symbol => match c {
+            'Α' => Some(65),
+            'Β' => Some(66),
+            'Χ' => Some(67),
+            '∆' => Some(68),
+            'Ε' => Some(69),
+            'Η' => Some(72),
+            '€' => Some(160),
+            'Γ' => Some(71),
+            'ℑ' => Some(193),
+            'Ι' => Some(73),
+            'Κ' => Some(75),
+            'Λ' => Some(76),
+            'Μ' => Some(77),
+            'Ν' => Some(78),
+            'Ω' => Some(87),
+            'Ο' => Some(79),
+            'Φ' => Some(70),
+            'Π' => Some(80),
+            'Ψ' => Some(89),
+            'ℜ' => Some(194),
+            'Ρ' => Some(82),
+            'Σ' => Some(83),
+            'Τ' => Some(84),
+            'Θ' => Some(81),
+            'Υ' => Some(85),
+            'ϒ' => Some(161),
+            'Ξ' => Some(88),
+            'Ζ' => Some(90),
+            'ℵ' => Some(192),
+            'α' => Some(97),
+            '&' => Some(38),
+            '∠' => Some(208),
+            '〈' => Some(225),
+            '〉' => Some(241),
+            '≈' => Some(187),
+            '↔' => Some(171),
+            '⇔' => Some(219),
+            '⇓' => Some(223),
+            '⇐' => Some(220),
+            '⇒' => Some(222),
+            '⇑' => Some(221),
+            '↓' => Some(175),
+            '' => Some(190),
+            '←' => Some(172),
+            '→' => Some(174),
+            '↑' => Some(173),
+            '' => Some(189),
+            '∗' => Some(42),
+            '|' => Some(124),
+            'β' => Some(98),
+            '' => Some(239),
+            '{' => Some(123),
+            '' => Some(238),
+            '' => Some(237),
+            '' => Some(236),
+            '}' => Some(125),
+            '' => Some(254),
+            '' => Some(253),
+            '' => Some(252),
+            '[' => Some(91),
+            '' => Some(235),
+            '' => Some(234),
+            '' => Some(233),
+            ']' => Some(93),
+            '' => Some(251),
+            '' => Some(250),
+            '' => Some(249),
+            '•' => Some(183),
+            '↵' => Some(191),
+            'χ' => Some(99),
+            '⊗' => Some(196),
+            '⊕' => Some(197),
+            '♣' => Some(167),
+            ':' => Some(58),
+            ',' => Some(44),
+            '≅' => Some(64),
+            '' => Some(227),
+            '' => Some(211),
+            '°' => Some(176),
+            'δ' => Some(100),
+            '♦' => Some(168),
+            '÷' => Some(184),
+            '⋅' => Some(215),
+            '8' => Some(56),
+            '∈' => Some(206),
+            '…' => Some(188),
+            '∅' => Some(198),
+            'ε' => Some(101),
+            '=' => Some(61),
+            '≡' => Some(186),
+            'η' => Some(104),
+            '!' => Some(33),
+            '∃' => Some(36),
+            '5' => Some(53),
+            'ƒ' => Some(166),
+            '4' => Some(52),
+            '⁄' => Some(164),
+            'γ' => Some(103),
+            '∇' => Some(209),
+            '>' => Some(62),
+            '≥' => Some(179),
+            '♥' => Some(169),
+            '∞' => Some(165),
+            '∫' => Some(242),
+            '⌡' => Some(245),
+            '' => Some(244),
+            '⌠' => Some(243),
+            '∩' => Some(199),
+            'ι' => Some(105),
+            'κ' => Some(107),
+            'λ' => Some(108),
+            '<' => Some(60),
+            '≤' => Some(163),
+            '∧' => Some(217),
+            '¬' => Some(216),
+            '∨' => Some(218),
+            '◊' => Some(224),
+            '−' => Some(45),
+            '′' => Some(162),
+            'µ' => Some(109),
+            '×' => Some(180),
+            '9' => Some(57),
+            '∉' => Some(207),
+            '≠' => Some(185),
+            '⊄' => Some(203),
+            'ν' => Some(110),
+            '#' => Some(35),
+            'ω' => Some(119),
+            'ϖ' => Some(118),
+            'ο' => Some(111),
+            '1' => Some(49),
+            '(' => Some(40),
+            '' => Some(232),
+            '' => Some(231),
+            '' => Some(230),
+            ')' => Some(41),
+            '' => Some(248),
+            '' => Some(247),
+            '' => Some(246),
+            '∂' => Some(182),
+            '%' => Some(37),
+            '.' => Some(46),
+            '⊥' => Some(94),
+            'φ' => Some(102),
+            'ϕ' => Some(106),
+            'π' => Some(112),
+            '+' => Some(43),
+            '±' => Some(177),
+            '∏' => Some(213),
+            '⊂' => Some(204),
+            '⊃' => Some(201),
+            '∝' => Some(181),
+            'ψ' => Some(121),
+            '?' => Some(63),
+            '√' => Some(214),
+            '' => Some(96),
+            '⊆' => Some(205),
+            '⊇' => Some(202),
+            '' => Some(226),
+            '' => Some(210),
+            'ρ' => Some(114),
+            '″' => Some(178),
+            ';' => Some(59),
+            '7' => Some(55),
+            'σ' => Some(115),
+            'ς' => Some(86),
+            '∼' => Some(126),
+            '6' => Some(54),
+            '/' => Some(47),
+            ' ' => Some(32),
+            '♠' => Some(170),
+            '∋' => Some(39),
+            '∑' => Some(229),
+            'τ' => Some(116),
+            '∴' => Some(92),
+            'θ' => Some(113),
+            'ϑ' => Some(74),
+            '3' => Some(51),
+            '' => Some(228),
+            '' => Some(212),
+            '2' => Some(50),
+            '_' => Some(95),
+            '∪' => Some(200),
+            '∀' => Some(34),
+            'υ' => Some(117),
+            '℘' => Some(195),
+            'ξ' => Some(120),
+            '0' => Some(48),
+            'ζ' => Some(122),
+            _ => None,
+        },
+        Font::ZapfDingbats => match c {
+            ' ' => Some(32),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Font {
-    TimesItalic,
     CourierBold,
     CourierBoldOblique,
     CourierOblique,
     Courier,
-    TimesRoman,
-    TimesBoldItalic,
     HelveticaBold,
-    ZapfDingbats,
-    Helvetica,
-    HelveticaOblique,
     HelveticaBoldOblique,
+    HelveticaOblique,
+    Helvetica,
     Symbol,
     TimesBold,
+    TimesBoldItalic,
+    TimesItalic,
+    TimesRoman,
+    ZapfDingbats,
+}
+
+/// The font with the `PostScript` name `name`, such as `"Times-Roman"`
+pub fn font_from_name(name: &str) -> Option<Font> {
+    match name {
+        "Courier-Bold" => Some(Font::CourierBold),
+        "Courier-BoldOblique" => Some(Font::CourierBoldOblique),
+        "Courier-Oblique" => Some(Font::CourierOblique),
+        "Courier" => Some(Font::Courier),
+        "Helvetica-Bold" => Some(Font::HelveticaBold),
+        "Helvetica-BoldOblique" => Some(Font::HelveticaBoldOblique),
+        "Helvetica-Oblique" => Some(Font::HelveticaOblique),
+        "Helvetica" => Some(Font::Helvetica),
+        "Symbol" => Some(Font::Symbol),
+        "Times-Bold" => Some(Font::TimesBold),
+        "Times-BoldItalic" => Some(Font::TimesBoldItalic),
+        "Times-Italic" => Some(Font::TimesItalic),
+        "Times-Roman" => Some(Font::TimesRoman),
+        "ZapfDingbats" => Some(Font::ZapfDingbats),
+        _ => None,
+    }
+}
+
+/// Measurements of a font as a whole, in units of the font size
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontGlobalMetrics {
+    /// The height of the tallest letters above the baseline, such as d
+    pub ascender: f64,
+    /// The depth of the lowest letters below the baseline, such as p, which is negative
+    pub descender: f64,
+    /// The height of flat capital letters such as H, which symbol fonts don't have
+    pub cap_height: Option<f64>,
+    /// The height of flat lowercase letters such as x, which symbol fonts don't have
+    pub x_height: Option<f64>,
+    /// The distance from the baseline to the middle of an underline, which is negative
+    pub underline_position: f64,
+    /// The thickness of an underline
+    pub underline_thickness: f64,
+    /// The left, bottom, right, and top edges of a box around every glyph
+    pub bbox: [f64; 4],
+}
+
+/// The metrics of `font` as a whole, from its AFM file
+#[must_use]
+pub fn font_metrics(font: &Font) -> FontGlobalMetrics {
+    match font {
+        Font::CourierBold => FontGlobalMetrics {
+            ascender: 0.629,
+            descender: -0.157,
+            cap_height: Some(0.562),
+            x_height: Some(0.439),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.113, -0.25, 0.749, 0.801],
+        },
+        Font::CourierBoldOblique => FontGlobalMetrics {
+            ascender: 0.629,
+            descender: -0.157,
+            cap_height: Some(0.562),
+            x_height: Some(0.439),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.057, -0.25, 0.869, 0.801],
+        },
+        Font::CourierOblique => FontGlobalMetrics {
+            ascender: 0.629,
+            descender: -0.157,
+            cap_height: Some(0.562),
+            x_height: Some(0.426),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.027, -0.25, 0.849, 0.805],
+        },
+        Font::Courier => FontGlobalMetrics {
+            ascender: 0.629,
+            descender: -0.157,
+            cap_height: Some(0.562),
+            x_height: Some(0.426),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.023, -0.25, 0.715, 0.805],
+        },
+        Font::HelveticaBold => FontGlobalMetrics {
+            ascender: 0.718,
+            descender: -0.207,
+            cap_height: Some(0.718),
+            x_height: Some(0.532),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.17, -0.228, 1.003, 0.962],
+        },
+        Font::HelveticaBoldOblique => FontGlobalMetrics {
+            ascender: 0.718,
+            descender: -0.207,
+            cap_height: Some(0.718),
+            x_height: Some(0.532),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.174, -0.228, 1.114, 0.962],
+        },
+        Font::HelveticaOblique => FontGlobalMetrics {
+            ascender: 0.718,
+            descender: -0.207,
+            cap_height: Some(0.718),
+            x_height: Some(0.523),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.17, -0.225, 1.116, 0.931],
+        },
+        Font::Helvetica => FontGlobalMetrics {
+            ascender: 0.718,
+            descender: -0.207,
+            cap_height: Some(0.718),
+            x_height: Some(0.523),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.166, -0.225, 1.0, 0.931],
+        },
+        Font::Symbol => FontGlobalMetrics {
+            ascender: 1.01,
+            descender: -0.293,
+            cap_height: None,
+            x_height: None,
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.18, -0.293, 1.09, 1.01],
+        },
+        Font::TimesBold => FontGlobalMetrics {
+            ascender: 0.683,
+            descender: -0.217,
+            cap_height: Some(0.676),
+            x_height: Some(0.461),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.168, -0.218, 1.0, 0.935],
+        },
+        Font::TimesBoldItalic => FontGlobalMetrics {
+            ascender: 0.683,
+            descender: -0.217,
+            cap_height: Some(0.669),
+            x_height: Some(0.462),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.2, -0.218, 0.996, 0.921],
+        },
+        Font::TimesItalic => FontGlobalMetrics {
+            ascender: 0.683,
+            descender: -0.217,
+            cap_height: Some(0.653),
+            x_height: Some(0.441),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.169, -0.217, 1.01, 0.883],
+        },
+        Font::TimesRoman => FontGlobalMetrics {
+            ascender: 0.683,
+            descender: -0.217,
+            cap_height: Some(0.662),
+            x_height: Some(0.45),
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.168, -0.218, 1.0, 0.898],
+        },
+        Font::ZapfDingbats => FontGlobalMetrics {
+            ascender: 0.82,
+            descender: -0.143,
+            cap_height: None,
+            x_height: None,
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            bbox: [-0.001, -0.143, 0.981, 0.82],
+        },
+    }
 }
@@ -1,5 +1,6 @@
 //! Types for representing details in the graphics state.
 
+#[cfg(test)]
 use std::f64::consts::PI;
 use std::fmt::{self, Display};
 use std::ops::Mul;
@@ -30,8 +31,37 @@ pub enum CapStyle {
     ProjectingSquare,
 }
 
+/// Rendering intents for mapping colors to an output device's gamut, as described in section
+/// 8.6.5.8 of the PDF specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderingIntent {
+    /// Reproduce colors exactly, without adjusting for the medium's white point.
+    AbsoluteColorimetric,
+    /// Reproduce colors exactly, relative to the medium's white point. This is the default.
+    RelativeColorimetric,
+    /// Keep colors saturated, at the expense of accuracy, as for business graphics.
+    Saturation,
+    /// Keep the relationships between colors, as for photographs.
+    Perceptual,
+}
+
+impl RenderingIntent {
+    /// The name of the intent in a PDF file
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::AbsoluteColorimetric => "/AbsoluteColorimetric",
+            Self::RelativeColorimetric => "/RelativeColorimetric",
+            Self::Saturation => "/Saturation",
+            Self::Perceptual => "/Perceptual",
+        }
+    }
+}
+
 /// Any color (or grayscale) value that this library can make PDF represent.
-#[derive(Clone, Copy, Debug)]
+///
+/// Colors are always stored as RGB, so two colors compare equal when they render identically,
+/// regardless of which constructor produced them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
 pub struct Color {
     pub red: u8,
@@ -40,8 +70,84 @@ pub struct Color {
 }
 
 impl Color {
-    /// Return a grayscale color value.
+    /// Return a color from its red, green, and blue components.
+    #[inline]
+    #[must_use]
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
 
+    /// Return the RGB equivalent of a CMYK color, with each component in `0.0..=1.0`.
+    ///
+    /// # Example
+    /// ````
+    /// # use pdfpdf::Color;
+    /// assert_eq!(Color::cmyk(0.0, 1.0, 1.0, 0.0), Color::rgb(255, 0, 0));
+    /// ````
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn cmyk(cyan: f64, magenta: f64, yellow: f64, black: f64) -> Self {
+        let channel = |c: f64| {
+            (255.0 * (1.0 - c.clamp(0.0, 1.0)) * (1.0 - black.clamp(0.0, 1.0))).round() as u8
+        };
+        Self {
+            red: channel(cyan),
+            green: channel(magenta),
+            blue: channel(yellow),
+        }
+    }
+
+    /// Return the red, green, and blue components of this color.
+    #[inline]
+    #[must_use]
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        (self.red, self.green, self.blue)
+    }
+
+    /// Return the cyan, magenta, yellow, and black components of this color, each in
+    /// `0.0..=1.0`.
+    #[inline]
+    #[must_use]
+    pub fn to_cmyk(&self) -> (f64, f64, f64, f64) {
+        let max = self.red.max(self.green).max(self.blue);
+        if max == 0 {
+            return (0.0, 0.0, 0.0, 1.0);
+        }
+        let max = f64::from(max);
+        let channel = |c: u8| 1.0 - f64::from(c) / max;
+        (
+            channel(self.red),
+            channel(self.green),
+            channel(self.blue),
+            1.0 - max / 255.0,
+        )
+    }
+
+    /// Return the color `t` of the way from this color to `other`, with `t` in `0.0..=1.0`.
+    ///
+    /// # Example
+    /// ````
+    /// # use pdfpdf::Color;
+    /// let pink = Color::rgb(255, 255, 255).lerp(Color::rgb(255, 0, 0), 0.5);
+    /// assert_eq!(pink, Color::rgb(255, 128, 128));
+    /// ````
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let channel =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Self {
+            red: channel(self.red, other.red),
+            green: channel(self.green, other.green),
+            blue: channel(self.blue, other.blue),
+        }
+    }
+
+    /// Return a grayscale color value.
+    ///
     /// # Example
     /// ````
     /// # use pdfpdf::Color;
@@ -49,6 +155,7 @@ impl Color {
     /// let gray = Color::gray(128);
     /// ````
     #[inline]
+    #[must_use]
     pub fn gray(gray: u8) -> Self {
         Self {
             red: gray,
@@ -58,6 +165,15 @@ impl Color {
     }
 }
 
+/// The color and width of a stroked line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StrokeStyle {
+    /// The color of the line.
+    pub color: Color,
+    /// The width of the line.
+    pub width: f64,
+}
+
 /// A transformation matrix for the pdf graphics state.
 ///
 /// Matrices can be created with numerous named constructors and
@@ -66,9 +182,9 @@ impl Color {
 /// # Examples
 ///
 /// ```
-/// # use pdfpdf::{Matrix, Pdf};
+/// # use pdfpdf::{Matrix, Pdf, Size};
 /// Pdf::new()
-///     .add_page(180.0, 240.0)
+///     .add_page(Size { width: 180.0, height: 240.0 })
 ///     .transform(Matrix::translate(10.0, 24.0))
 ///
 /// // Matrixes can be combined by multiplication:
@@ -76,7 +192,7 @@ impl Color {
 /// // ... will be visualy identical to:
 ///     .transform(Matrix::translate(7.0, 0.0))
 ///     .transform(Matrix::rotate_deg(45.0))
-///     .write_to("foo.pdf").unwrap();
+///     .to_bytes();
 /// ```
 #[derive(Clone, Copy, Debug)]
 pub struct Matrix {
@@ -113,7 +229,7 @@ impl Matrix {
     where
         N: Into<f64>,
     {
-        Self::rotate(a.into() * PI / 180.)
+        Self::rotate(a.into().to_radians())
     }
 
     /// Construct a matrix for scaling by factor `sx` in x-direction
@@ -177,6 +293,25 @@ impl Mul for Matrix {
     }
 }
 
+#[test]
+fn test_gray_equals_rgb() {
+    assert_eq!(Color::gray(128), Color::rgb(128, 128, 128));
+    assert_ne!(Color::gray(128), Color::rgb(128, 128, 129));
+}
+
+#[test]
+fn test_cmyk_round_trip() {
+    for &color in &[
+        Color::rgb(255, 0, 0),
+        Color::rgb(12, 200, 99),
+        Color::gray(0),
+        Color::gray(255),
+    ] {
+        let (c, m, y, k) = color.to_cmyk();
+        assert_eq!(Color::cmyk(c, m, y, k), color);
+    }
+}
+
 #[test]
 fn test_matrix_mul_a() {
     assert_unit(&(Matrix::rotate_deg(45.) * Matrix::rotate_deg(-45.)));
@@ -209,7 +344,7 @@ fn diff(a: &[f64; 6], b: &[f64; 6]) -> Option<String> {
     let epsilon = 1e-6 * large_a.max(large_b);
     for i in 0..6 {
         if (a[i] - b[i]).abs() > epsilon {
-            return Some(format!("{:?} != {:?}", a, b));
+            return Some(format!("{a:?} != {b:?}"));
         }
     }
     None
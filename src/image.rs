@@ -1,23 +1,228 @@
+use std::convert::TryInto;
+
 /// A wrapper around a buffer and dimensions to make drawing images more ergonomic
+///
+/// Images are placed at one unit (point) per pixel unless the page is scaled with `transform`.
 #[derive(Clone, Copy)]
 pub struct Image<'a> {
     pub(crate) buf: &'a [u8],
     pub(crate) width: u64,
     pub(crate) height: u64,
+    /// The lowest and highest red, green, and blue values of pixels which are left transparent
+    pub(crate) color_key: Option<[u8; 6]>,
 }
 
 impl<'a> Image<'a> {
     /// Create an Image from some bytes, panics if buffer length is not a multiple of 3 or if the
     /// product of the width and height is not the buffer length
-    pub fn new<N1, N2>(buf: &'a [u8], width: N1, height: N2) -> Image<'a>
+    /// The dimensions may be any integer type which converts to `u64`, including `usize`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is negative, if the byte length of `width * height` RGB pixels
+    /// overflows a `u64`, or if `buf` does not hold exactly that many pixels.
+    pub fn new<N1, N2>(buf: &'a [u8], width: N1, height: N2) -> Self
     where
-        u64: From<N1>,
-        u64: From<N2>,
+        N1: TryInto<u64>,
+        N2: TryInto<u64>,
     {
-        let width = u64::from(width);
-        let height = u64::from(height);
+        let width = width
+            .try_into()
+            .unwrap_or_else(|_| panic!("image width does not fit in a u64"));
+        let height = height
+            .try_into()
+            .unwrap_or_else(|_| panic!("image height does not fit in a u64"));
         assert_eq!(buf.len() % 3, 0);
-        assert_eq!(width * height * 3, buf.len() as u64);
-        Image { buf, width, height }
+        let len = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .unwrap_or_else(|| panic!("a {} by {} image is too large", width, height));
+        assert_eq!(len, buf.len() as u64);
+        Self {
+            buf,
+            width,
+            height,
+            color_key: None,
+        }
+    }
+
+    /// Leave pixels transparent if each of their red, green, and blue values is within the
+    /// matching inclusive range, such as a white background around a logo
+    #[must_use]
+    pub fn with_color_key(self, red: (u8, u8), green: (u8, u8), blue: (u8, u8)) -> Self {
+        Self {
+            color_key: Some([red.0, red.1, green.0, green.1, blue.0, blue.1]),
+            ..self
+        }
+    }
+
+    /// The width and height of the image in pixels
+    #[inline]
+    #[must_use]
+    pub fn pixel_size(&self) -> (u64, u64) {
+        (self.width, self.height)
+    }
+}
+
+/// An image which owns its pixels, such as one copied out of a padded framebuffer
+/// Draw it by borrowing it as an `Image` with `as_image`.
+#[derive(Clone)]
+pub struct ImageBuf {
+    pub(crate) buf: Vec<u8>,
+    pub(crate) width: u64,
+    pub(crate) height: u64,
+}
+
+impl ImageBuf {
+    /// Create an image by copying the pixels out of a framebuffer whose rows are `stride` bytes
+    /// apart, such as a screen capture with padding at the end of each row
+    /// Pixels may be 3 bytes of RGB, or 4 bytes where the last one (alpha or padding) is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channels` is not 3 or 4, if `stride` is smaller than a row of pixels, or if
+    /// `buf` is too short to hold `height` rows.
+    #[must_use]
+    pub fn from_strided(
+        buf: &[u8],
+        width: usize,
+        height: usize,
+        stride: usize,
+        channels: usize,
+    ) -> Self {
+        assert!(
+            channels == 3 || channels == 4,
+            "images must have 3 or 4 channels, not {}",
+            channels
+        );
+        let row_len = width * channels;
+        assert!(
+            stride >= row_len,
+            "stride {} is shorter than a row of {} pixels",
+            stride,
+            width
+        );
+        assert!(
+            height == 0 || buf.len() >= stride * (height - 1) + row_len,
+            "buffer is too short for {} rows",
+            height
+        );
+        if row_len == 0 {
+            // There are no pixels to copy, and the stride may be 0
+            return Self {
+                buf: Vec::new(),
+                width: 0,
+                height: height as u64,
+            };
+        }
+        let mut packed = Vec::with_capacity(width * height * 3);
+        for row in buf.chunks(stride).take(height) {
+            for pixel in row[..row_len].chunks_exact(channels) {
+                packed.extend_from_slice(&pixel[..3]);
+            }
+        }
+        Self {
+            buf: packed,
+            width: width as u64,
+            height: height as u64,
+        }
+    }
+
+    /// Borrow the pixels as an `Image` which can be drawn
+    #[inline]
+    #[must_use]
+    pub fn as_image(&self) -> Image<'_> {
+        Image {
+            buf: &self.buf,
+            width: self.width,
+            height: self.height,
+            color_key: None,
+        }
     }
+
+    /// The width and height of the image in pixels
+    #[inline]
+    #[must_use]
+    pub fn pixel_size(&self) -> (u64, u64) {
+        (self.width, self.height)
+    }
+}
+
+/// The color space of raw pixel data, each with 8 bits per component
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// One byte per pixel
+    Gray,
+    /// Red, green, and blue bytes for each pixel
+    Rgb,
+    /// Cyan, magenta, yellow, and black bytes for each pixel
+    Cmyk,
+}
+
+impl ColorSpace {
+    /// The number of bytes in each pixel
+    pub(crate) fn components(self) -> u64 {
+        match self {
+            Self::Gray => 1,
+            Self::Rgb => 3,
+            Self::Cmyk => 4,
+        }
+    }
+
+    /// The name of the matching PDF device color space
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Gray => "/DeviceGray",
+            Self::Rgb => "/DeviceRGB",
+            Self::Cmyk => "/DeviceCMYK",
+        }
+    }
+}
+
+#[test]
+fn test_usize_dimensions() {
+    let pixels = vec![0_u8; 4 * 3 * 3];
+    let image = Image::new(&pixels, 4_usize, 3_usize);
+    assert_eq!(image.pixel_size(), (4, 3));
+}
+
+#[test]
+fn test_from_strided() {
+    let framebuffer = [
+        1, 2, 3, 4, 5, 6, 0, 0, // first row and two bytes of padding
+        7, 8, 9, 10, 11, 12, 0, 0,
+    ];
+    let image = ImageBuf::from_strided(&framebuffer, 2, 2, 8, 3);
+    assert_eq!(image.pixel_size(), (2, 2));
+    assert_eq!(
+        image.as_image().buf,
+        &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+    );
+}
+
+#[test]
+#[should_panic(expected = "left == right")]
+fn test_new_rejects_wrong_size() {
+    let pixels = vec![0_u8; 4 * 3 * 3];
+    let _ = Image::new(&pixels, 4_usize, 4_usize);
+}
+
+#[test]
+#[should_panic(expected = "is too large")]
+fn test_new_rejects_overflowing_size() {
+    let _ = Image::new(&[], u64::MAX, 2_u64);
+}
+
+#[test]
+#[should_panic(expected = "buffer is too short for 2 rows")]
+fn test_from_strided_rejects_short_buffer() {
+    let framebuffer = [1, 2, 3, 4, 5, 6, 0, 0, 7, 8, 9];
+    let _ = ImageBuf::from_strided(&framebuffer, 2, 2, 8, 3);
+}
+
+#[test]
+fn test_from_strided_empty_rows() {
+    let image = ImageBuf::from_strided(&[], 0, 2, 0, 3);
+    assert!(image.buf.is_empty());
+    assert_eq!((image.width, image.height), (0, 2));
 }
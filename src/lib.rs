@@ -1,4 +1,11 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery)]
+// Drawing code is written as plain arithmetic; mul_add and const setters only hurt readability
+#![allow(
+    clippy::suboptimal_flops,
+    clippy::missing_const_for_fn,
+    clippy::cast_precision_loss,
+    clippy::option_if_let_else
+)]
 //! A Pretty Darn Fast library for creating PDF files.
 //! Currently only supports basic images, simple vector graphics, and text with builtin fonts (but not UTF-8).
 //!
@@ -8,9 +15,10 @@
 //! ```rust
 //! use pdfpdf::{Color, Pdf, Point, Size, Alignment};
 //!
+//! # #[cfg(feature = "std")]
 //! Pdf::new()
-//!     .add_page(Size { x: 180.0, y: 240.0 })
-//!     .set_color(Color::rgb(0, 0, 248))
+//!     .add_page(Size { width: 180.0, height: 240.0 })
+//!     .set_color(Color { red: 0, green: 0, blue: 248 })
 //!     .draw_circle(Point{ x: 90.0, y: 120.0 }, 50.0)
 //!     .write_to("example.pdf")
 //!     .unwrap();
@@ -27,20 +35,35 @@
 //! More working examples can be found in [here](https://github.com/saethlin/pdfpdf/tree/master/examples).
 #![warn(missing_docs)]
 
-use std::fs::File;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::io;
+use std::time::{Duration, Instant};
 
+mod ascii;
+mod cursor;
+mod encoding;
+#[cfg(feature = "std")]
+mod file;
 mod fonts;
 mod graphicsstate;
 mod image;
+mod lzw;
+mod markup;
 mod text;
+mod truetype;
+mod validate;
 #[macro_use]
 mod util;
 
-pub use fonts::Font;
-pub use graphicsstate::{Color, Matrix};
-pub use image::Image;
-pub use text::Alignment;
+pub use cursor::TextCursor;
+pub use fonts::{font_metrics, Font, FontGlobalMetrics};
+pub use graphicsstate::{Color, Matrix, RenderingIntent, StrokeStyle};
+pub use image::{ColorSpace, Image, ImageBuf};
+pub use text::{Alignment, HAlign, ParseAlignmentError, TextDecoration, TextRenderMode, VAlign};
+pub use truetype::InvalidFontError;
+pub use validate::ValidationError;
 
 use util::Formattable;
 pub use util::{Point, Size};
@@ -58,40 +81,231 @@ pub enum Compression {
     /// Uncompressed PDF streams are both easier to debug and much faster to write.
     /// Some uncompressed PDFs may be slower due to the amount of disk reads required.
     Off,
+    /// LZW compresses worse than deflate, but is understood by some older consumers which lack
+    /// deflate support
+    Lzw,
 }
 
 impl Compression {
     fn to_deflate(self) -> Option<deflate::Compression> {
         match self {
-            Compression::Fast => Some(deflate::Compression::Fast),
-            Compression::Normal => Some(deflate::Compression::Default),
-            Compression::Best => Some(deflate::Compression::Best),
-            Compression::Off => None,
+            Self::Fast => Some(deflate::Compression::Fast),
+            Self::Normal => Some(deflate::Compression::Default),
+            Self::Best => Some(deflate::Compression::Best),
+            Self::Off | Self::Lzw => None,
         }
     }
 }
 
+/// Text encodings for streams, applied after compression so that they contain only printable
+/// characters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsciiFilter {
+    /// Streams are written as-is
+    None,
+    /// Every 4 bytes are written as 5 characters
+    Ascii85,
+    /// Every byte is written as 2 hexadecimal digits, which is larger but easy to read
+    AsciiHex,
+}
+
+impl AsciiFilter {
+    /// The name of the filter to decode `data` with, and the encoded data
+    fn encode(self, data: &[u8]) -> Option<(&'static str, Vec<u8>)> {
+        match self {
+            Self::None => None,
+            Self::Ascii85 => Some(("/ASCII85Decode", ascii::ascii85_encode(data))),
+            Self::AsciiHex => Some(("/ASCIIHexDecode", ascii::hex_encode(data))),
+        }
+    }
+}
+
+/// A report on a page which was just finished, passed to the callback given to
+/// `Pdf::set_progress_callback`
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    /// The number of pages in the document, including this one
+    pub pages: usize,
+    /// The size of the page's content stream before compression
+    pub content_bytes: usize,
+    /// The size of the page's content stream object as written to the document
+    pub stream_bytes: usize,
+    /// How long it took to compress and encode the content stream
+    pub compression_time: Duration,
+}
+
+/// A page which can be linked to before it is finished, returned by `Pdf::add_page_handle`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageRef(usize);
+
+/// Where a link annotation goes when it is clicked
+#[derive(Clone, Debug)]
+enum LinkTarget {
+    Page(PageRef),
+    Uri(String),
+}
+
+/// The error returned when a name is not one of the 14 standard fonts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownFontError(String);
+
+impl fmt::Display for UnknownFontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown font {:?}, expected a standard font name such as \"Times-Roman\"",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownFontError {}
+
+/// A TrueType font added to a document with `embed_font` or `embed_font_subset`, for drawing text
+/// with `draw_text_embedded`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmbeddedFont(usize);
+
+/// A TrueType font embedded in the document
+#[derive(Clone)]
+struct EmbeddedFontData {
+    font: std::sync::Arc<truetype::TrueTypeFont>,
+    /// Whether only the glyphs used on finished pages are written
+    subset: bool,
+    /// The glyphs used on finished pages, each with the first character it was drawn for
+    used: BTreeMap<u16, char>,
+    /// The ids of the font's objects, which are reserved when a finished page first uses it and
+    /// filled in by `write_embedded_fonts`
+    ids: Vec<usize>,
+}
+
+/// Where the baseline of line `l` of `num_lines` starts when text is drawn at `position` with
+/// `alignment`, for a line `line_width` wide in a font `height` tall
+fn line_origin(
+    (x, y): (f64, f64),
+    alignment: Alignment,
+    line_width: f64,
+    height: f64,
+    l: f64,
+    num_lines: f64,
+) -> (f64, f64) {
+    match alignment {
+        Alignment::TopLeft => (x, y - height * (l + 1.0)),
+        Alignment::TopRight => (x - line_width, y - height * (l + 1.0)),
+        Alignment::TopCenter => (x - line_width / 2.0, y - height * (l + 1.0)),
+        Alignment::CenterLeft => (
+            x,
+            (y - height / 3.0) - (l - (num_lines - 1.0) / 2.0) * height * 1.25,
+        ),
+        Alignment::CenterRight => (
+            x - line_width,
+            (y - height / 3.0) - (l - (num_lines - 1.0) / 2.0) * height * 1.25,
+        ),
+        Alignment::CenterCenter => (
+            x - line_width / 2.0,
+            (y - height / 3.0) - (l - (num_lines - 1.0) / 2.0) * height * 1.25,
+        ),
+        Alignment::BottomLeft => (x, y + (num_lines - l - 1.0) * 1.25 * height),
+        Alignment::BottomRight => (x - line_width, y + (num_lines - l - 1.0) * 1.25 * height),
+        Alignment::BottomCenter => (
+            x - line_width / 2.0,
+            y + (num_lines - l - 1.0) * 1.25 * height,
+        ),
+    }
+}
+
 /// Represents a PDF internal object
 struct PdfObject {
     contents: Vec<u8>,
     id: usize,
-    is_page: bool,
-    is_xobject: bool,
-    offset: Option<usize>,
+}
+
+/// The color that both `set_color` colors are known to be, then the fill color, stroke color, and
+/// line width
+type Style = (Option<Color>, Color, Color, f64);
+
+/// Everything a `Q` operator puts back that `Pdf` keeps track of: the style, then the alpha,
+/// dash pattern, horizontal text scale, and transformation matrix
+type GraphicsState = (Style, f64, (Vec<f64>, f64), f64, Matrix);
+
+/// Settings which apply to the whole document rather than to one page, which every page drawn
+/// by `render_pages_parallel` starts with
+#[derive(Clone)]
+#[allow(clippy::struct_excessive_bools)] // These are independent settings
+struct Settings {
+    precision: u8,
+    compression: Compression,
+    ascii_filter: AsciiFilter,
+    default_line_width: Option<f64>,
+    default_color: Option<Color>,
+    fallback_font: Option<Font>,
+    hyphenate: bool,
+    ligatures: bool,
+    show_missing_glyphs: bool,
+    baseline_grid: Option<(f64, f64)>,
+    footer_margin: f64,
+    text_outline_width: Option<f64>,
+    simulate_transparency: bool,
+    background: Color,
 }
 
 /// The top-level struct that represents a (partially) in-memory PDF file
 pub struct Pdf {
-    buffer: Vec<u8>,
+    version: (u8, u8),
     page_buffer: Vec<u8>,
     objects: Vec<PdfObject>,
+    page_order: Vec<usize>,
+    insert_page_index: Option<usize>,
+    page_start: (usize, usize, usize),
     width: f64,
     height: f64,
+    origin: (f64, f64),
+    default_page_size: Size<f64, f64>,
+    bounds: Option<(f64, f64, f64, f64)>,
+    ctm: Matrix,
+    settings: Settings,
     fonts: Vec<fonts::Font>,
     font_size: f64,
     current_font_index: usize,
-    compression: Compression,
-    precision: u8,
+    page_compression: Compression,
+    page_ascii_filter: AsciiFilter,
+    dash: (Vec<f64>, f64),
+    color: Option<Color>,
+    fill_color: Color,
+    stroke_color: Color,
+    line_width: f64,
+    page_start_style: Style,
+    alpha: f64,
+    background_content: Vec<u8>,
+    background_offset: usize,
+    horizontal_scale: f64,
+    xobjects: Vec<(String, usize)>,
+    /// The object ids of the images embedded so far, by a hash of their pixels, dimensions, and
+    /// color key
+    /// Images whose hashes collide are told apart by comparing their objects.
+    image_ids: HashMap<u64, Vec<usize>>,
+    ext_g_states: Vec<Vec<u8>>,
+    to_unicode_id: Option<usize>,
+    font_resources: HashMap<Vec<Font>, Vec<u8>>,
+    embedded_fonts: Vec<EmbeddedFontData>,
+    /// The glyphs of each embedded font used on the page in progress, by font index
+    page_embedded_fonts: BTreeMap<usize, BTreeMap<u16, char>>,
+    /// The problems found in the content of each finished page, for `validate`
+    page_errors: Vec<ValidationError>,
+    deterministic: bool,
+    seed_date: Option<String>,
+    progress: Option<Box<dyn FnMut(Progress) + Send + Sync>>,
+    /// The graphics state to go back to when each clip started by `push_clip` is removed
+    clips: Vec<GraphicsState>,
+    /// The page object id of each page from `add_page`, once it is finished
+    page_ids: Vec<Option<usize>>,
+    /// The index in `page_ids` of the page in progress
+    current_page: Option<usize>,
+    /// The area and target of each link on the page in progress
+    links: Vec<([f64; 4], LinkTarget)>,
+    /// The object id, area, and target of each link annotation, which are only written once
+    /// every page they could point to is finished
+    link_annotations: Vec<(usize, [f64; 4], LinkTarget)>,
 }
 
 impl Default for Pdf {
@@ -103,67 +317,269 @@ impl Default for Pdf {
 impl Pdf {
     /// Create a new blank PDF document
     #[inline]
+    #[must_use]
     pub fn new() -> Self {
         Self {
-            buffer: b"%PDF-1.7\n%\xB5\xED\xAE\xFB\n".to_vec(),
+            version: (1, 7),
             page_buffer: Vec::new(),
             objects: vec![
                 PdfObject {
                     contents: Vec::new(),
                     id: 1,
-                    is_page: false,
-                    is_xobject: false,
-                    offset: None,
                 },
                 PdfObject {
                     contents: Vec::new(),
                     id: 2,
-                    is_page: false,
-                    is_xobject: false,
-                    offset: None,
                 },
             ],
+            page_order: Vec::new(),
+            insert_page_index: None,
+            page_start: (2, 1, 0),
             width: 400.0,
             height: 400.0,
+            origin: (0.0, 0.0),
+            default_page_size: Size {
+                width: 400.0,
+                height: 400.0,
+            },
+            bounds: None,
+            ctm: Matrix::uniform_scale(1.0),
+            settings: Settings {
+                precision: 10,
+                compression: Compression::Fast,
+                ascii_filter: AsciiFilter::None,
+                default_line_width: None,
+                default_color: None,
+                fallback_font: None,
+                hyphenate: false,
+                ligatures: false,
+                show_missing_glyphs: false,
+                baseline_grid: None,
+                footer_margin: 36.0,
+                text_outline_width: None,
+                simulate_transparency: false,
+                background: Color::gray(255),
+            },
             fonts: vec![Font::Helvetica],
             font_size: 12.0,
             current_font_index: 0,
-            compression: Compression::Fast,
-            precision: 10,
+            page_compression: Compression::Fast,
+            page_ascii_filter: AsciiFilter::None,
+            dash: (Vec::new(), 0.0),
+            color: None,
+            fill_color: Color::gray(0),
+            stroke_color: Color::gray(0),
+            line_width: 1.0,
+            page_start_style: (None, Color::gray(0), Color::gray(0), 1.0),
+            alpha: 1.0,
+            background_content: Vec::new(),
+            background_offset: 0,
+            horizontal_scale: 100.0,
+            xobjects: Vec::new(),
+            image_ids: HashMap::new(),
+            ext_g_states: Vec::new(),
+            to_unicode_id: None,
+            font_resources: HashMap::new(),
+            embedded_fonts: Vec::new(),
+            page_embedded_fonts: BTreeMap::new(),
+            page_errors: Vec::new(),
+            deterministic: false,
+            seed_date: None,
+            progress: None,
+            clips: Vec::new(),
+            page_ids: Vec::new(),
+            current_page: None,
+            links: Vec::new(),
+            link_annotations: Vec::new(),
         }
     }
 
-    fn add_object(&mut self, data: Vec<u8>, is_page: bool, is_xobject: bool) -> usize {
+    /// Return to the state of a new document, keeping the memory already allocated for page
+    /// content and objects so that many documents can be built with one `Pdf`
+    pub fn clear(&mut self) -> &mut Self {
+        let mut page_buffer = std::mem::take(&mut self.page_buffer);
+        page_buffer.clear();
+        let mut objects = std::mem::take(&mut self.objects);
+        objects.truncate(2);
+        let mut page_order = std::mem::take(&mut self.page_order);
+        page_order.clear();
+        *self = Self {
+            page_buffer,
+            objects,
+            page_order,
+            ..Self::new()
+        };
+        self
+    }
+
+    fn add_object(&mut self, data: Vec<u8>) -> usize {
         let id = self.objects.iter().map(|o| o.id).max().unwrap_or(3) + 1;
-        self.objects.push(PdfObject {
-            contents: data,
-            id,
-            is_page,
-            is_xobject,
-            offset: None,
-        });
+        self.objects.push(PdfObject { contents: data, id });
         id
     }
 
+    /// Add object `id` to the current page's `XObject` resources, returning its resource name
+    fn add_xobject(&mut self, id: usize) -> String {
+        if let Some((name, _)) = self.xobjects.iter().find(|&&(_, x)| x == id) {
+            return name.clone();
+        }
+        let name = format!("/X{}", self.xobjects.len());
+        self.xobjects.push((name.clone(), id));
+        name
+    }
+
+    /// Create an image `XObject` for `image`, returning its resource name on the current page
+    /// An image which was already embedded in the document is reused instead of being
+    /// compressed and stored again.
+    fn add_image_xobject(&mut self, image: &Image) -> String {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (&image.buf, image.width, image.height, image.color_key).hash(&mut hasher);
+        let hash = hasher.finish();
+        let compressed = deflate::deflate_bytes_zlib_conf(image.buf, deflate::Compression::Best);
+        let object = self.image_xobject(
+            compressed,
+            image.width,
+            image.height,
+            ColorSpace::Rgb,
+            image.color_key,
+        );
+        // Compression is lossless, so only an image with the same pixels has the same object
+        let objects = &self.objects;
+        let embedded = self.image_ids.get(&hash).and_then(|ids| {
+            ids.iter().copied().find(|&id| {
+                objects
+                    .iter()
+                    .any(|embedded| embedded.id == id && embedded.contents == object)
+            })
+        });
+        if let Some(id) = embedded {
+            return self.add_xobject(id);
+        }
+
+        let id = self.add_object(object);
+        self.image_ids.entry(hash).or_default().push(id);
+        self.add_xobject(id)
+    }
+
+    /// Create an image `XObject` from zlib-compressed pixels, returning its object id
+    /// `color_key` is the range of each component of pixels which are left transparent.
+    fn add_compressed_image_xobject(
+        &mut self,
+        compressed: Vec<u8>,
+        width: u64,
+        height: u64,
+        color_space: ColorSpace,
+        color_key: Option<[u8; 6]>,
+    ) -> usize {
+        let object = self.image_xobject(compressed, width, height, color_space, color_key);
+        self.add_object(object)
+    }
+
+    /// The contents of an image `XObject` for zlib-compressed pixels
+    fn image_xobject(
+        &self,
+        compressed: Vec<u8>,
+        width: u64,
+        height: u64,
+        color_space: ColorSpace,
+        color_key: Option<[u8; 6]>,
+    ) -> Vec<u8> {
+        let mask = color_key.map_or_else(String::new, |key| {
+            let ranges: Vec<String> = key.iter().map(u8::to_string).collect();
+            format!("/Mask [{}] ", ranges.join(" "))
+        });
+        let (filters, compressed) = match self.settings.ascii_filter.encode(&compressed) {
+            Some((filter, encoded)) => (format!("{filter} /FlateDecode"), encoded),
+            None => ("/FlateDecode".to_string(), compressed),
+        };
+        let mut object = format!(
+            "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace {} \
+             /BitsPerComponent 8 {}/Filter [{}] /Length {} >>\nstream\n",
+            width,
+            height,
+            color_space.name(),
+            mask,
+            filters,
+            compressed.len()
+        )
+        .into_bytes();
+        object.extend(compressed);
+        object.extend(b"\nendstream\n");
+        object
+    }
+
     /// Sets the required precision for all values written after this call
     /// If this is set to a small value, repeated transformations may result in substantial
     /// numerical error, but if used carefully this can massively reduce the size of drawing-heavy
     /// documents.
     #[inline]
     pub fn precision(&mut self, precision: u8) -> &mut Self {
-        self.precision = precision;
+        self.settings.precision = precision;
         self
     }
 
     /// Sets the compression level for this document
-    /// Calls to this method do not affect data produced by operations before the last .add_page
+    /// A page's content is compressed at the level set when the page was added, so calls to this
+    /// method do not affect the page in progress.
     #[inline]
     pub fn compression(&mut self, compression: Compression) -> &mut Self {
-        self.compression = compression;
+        self.settings.compression = compression;
+        self
+    }
+
+    /// Encode the document's streams as text, for sending over channels which only allow printable
+    /// characters
+    /// Like `compression`, this does not affect the page in progress.
+    #[inline]
+    pub fn set_ascii_filter(&mut self, filter: AsciiFilter) -> &mut Self {
+        self.settings.ascii_filter = filter;
+        self
+    }
+
+    /// Set the PDF version that the document claims to conform to, which is 1.7 by default
+    /// Versions newer than 1.7 are also written in the document catalog.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version is not one of 1.0 through 1.7 or 2.0.
+    #[inline]
+    pub fn set_version(&mut self, major: u8, minor: u8) -> &mut Self {
+        assert!(
+            matches!((major, minor), (1, 0..=7) | (2, 0)),
+            "{}.{} is not a PDF version",
+            major,
+            minor
+        );
+        self.version = (major, minor);
+        self
+    }
+
+    /// Call `callback` each time a page is finished, with its size and how long it took to
+    /// compress, so long-running programs can show their progress
+    #[inline]
+    pub fn set_progress_callback<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnMut(Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Make the output byte-for-byte reproducible
+    /// The document `/ID` is derived from the content alone, and if `seed_date` is provided it is
+    /// used verbatim for `/CreationDate` and `/ModDate` (e.g. `"D:20190101000000Z"`).
+    #[inline]
+    pub fn set_deterministic(&mut self, seed_date: Option<&str>) -> &mut Self {
+        self.deterministic = true;
+        self.seed_date = seed_date.map(str::to_owned);
         self
     }
 
     /// Set the PDF clipping box for the current page
+    /// The clip can only be made smaller afterwards, use `push_clip` for a clip that can be
+    /// removed again.
     #[inline]
     pub fn set_clipping_box<X, Y, W, H>(
         &mut self,
@@ -181,7 +597,7 @@ impl Pdf {
 
         ryu!(
             self.page_buffer,
-            self.precision,
+            self.settings.precision,
             corner.x,
             corner.y,
             size.width,
@@ -191,35 +607,216 @@ impl Pdf {
         self
     }
 
-    /// Add an RGB image
+    /// Clip everything drawn afterwards to a box until the matching `pop_clip`
+    /// Clips can be nested, and any that are still in place when the page ends are removed then.
+    #[inline]
+    pub fn push_clip<X, Y, W, H>(&mut self, location: Point<X, Y>, size: Size<W, H>) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        self.clips.push((
+            self.style(),
+            self.alpha,
+            self.dash.clone(),
+            self.horizontal_scale,
+            self.ctm,
+        ));
+        self.page_buffer.extend(b"q\n");
+        self.set_clipping_box(location, size)
+    }
+
+    /// Remove the clip added by the last `push_clip`
+    /// Colors, the line width, alpha, dash pattern, horizontal text scale, and transformation go
+    /// back to what they were when the clip was added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no clip from `push_clip` left on the current page.
+    #[inline]
+    pub fn pop_clip(&mut self) -> &mut Self {
+        let (style, alpha, dash, horizontal_scale, ctm) = self
+            .clips
+            .pop()
+            .expect("pop_clip called without a matching push_clip");
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self.alpha = alpha;
+        self.dash = dash;
+        self.horizontal_scale = horizontal_scale;
+        self.ctm = ctm;
+        self
+    }
+
+    /// Remove any clips from `push_clip` which are still in place at the end of a page
+    fn close_clips(&mut self) {
+        for _ in self.clips.drain(..) {
+            self.page_buffer.extend(b"Q\n");
+        }
+    }
+
+    /// Add an RGB image with its bottom-left corner at `location`
+    /// Each pixel is one unit square, so the image covers `image.pixel_size()` units. Placing the
+    /// same image again, on any page, reuses the copy already embedded in the document.
     #[inline]
     pub fn add_image_at<X, Y>(&mut self, image: Image, location: Point<X, Y>) -> &mut Self
     where
         X: Into<f64>,
         Y: Into<f64>,
     {
-        use deflate::{deflate_bytes_zlib_conf, Compression};
-        use std::io::Write;
+        let location = location.into_f64();
+        self.extend_bounds_rect(
+            location,
+            Size {
+                width: image.width as f64,
+                height: image.height as f64,
+            },
+        );
+
+        let name = self.add_image_xobject(&image);
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            "q",
+            image.width as f64,
+            0.,
+            0.,
+            image.height as f64,
+            location.x,
+            location.y,
+            "cm",
+            name.as_str(),
+            "Do Q"
+        );
+        self
+    }
+
+    /// Add an image with its bottom-left corner at `location`, compressing the pixels as they are
+    /// read from `pixels` instead of holding them all in memory
+    /// Exactly `width * height` pixels of 8-bit components in `color_space` are read. Like
+    /// `add_image_at`, each pixel is one unit square.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from `pixels` fails or ends before the whole image was read.
+    pub fn add_image_streaming<R, X, Y>(
+        &mut self,
+        pixels: R,
+        width: u64,
+        height: u64,
+        color_space: ColorSpace,
+        location: Point<X, Y>,
+    ) -> io::Result<&mut Self>
+    where
+        R: io::Read,
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let expected = width * height * color_space.components();
+        let mut encoder = deflate::write::ZlibEncoder::new(Vec::new(), deflate::Compression::Best);
+        let read = io::copy(&mut pixels.take(expected), &mut encoder)?;
+        if read < expected {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("expected {expected} bytes of pixels but only {read} were available"),
+            ));
+        }
+        let compressed = encoder.finish()?;
+        let id = self.add_compressed_image_xobject(compressed, width, height, color_space, None);
+        let name = self.add_xobject(id);
 
         let location = location.into_f64();
+        let (width, height) = (width as f64, height as f64);
+        self.extend_bounds_rect(location, Size { width, height });
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            "q",
+            width,
+            0.,
+            0.,
+            height,
+            location.x,
+            location.y,
+            "cm",
+            name.as_str(),
+            "Do Q"
+        );
+        Ok(self)
+    }
 
-        let compressed = deflate_bytes_zlib_conf(image.buf, Compression::Best);
+    /// Fill an area with copies of `image` laid out edge to edge from the bottom-left corner
+    /// The image is only embedded once, and partial tiles at the top and right edges are clipped
+    /// to the area. Like `add_image_at`, each pixel is one unit in size.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn draw_image_tiled<X, Y, W, H>(
+        &mut self,
+        image: Image,
+        (corner, size): (Point<X, Y>, Size<W, H>),
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        if image.width == 0 || image.height == 0 {
+            return self;
+        }
+        self.extend_bounds_rect(corner, size);
+        let name = self.add_image_xobject(&image);
+        let tile_width = image.width as f64;
+        let tile_height = image.height as f64;
 
-        let _ = write!(
+        ryu!(
             self.page_buffer,
-            "q {} 0 0 {} {} {} cm\n\
-             BI\n\
-             /W {}\n\
-             /H {}\n\
-             /CS /RGB\n\
-             /BPC 8\n\
-             /F [/Fl]\n\
-             ID\n",
-            image.width, image.height, location.x, location.y, image.width, image.height
+            self.settings.precision,
+            "q",
+            corner.x,
+            corner.y,
+            size.width,
+            size.height,
+            "re W n"
         );
-        self.page_buffer.extend(compressed);
-        self.page_buffer.extend(b"\nEI Q\n");
+        let columns = (size.width / tile_width).ceil().max(0.0) as usize;
+        let rows = (size.height / tile_height).ceil().max(0.0) as usize;
+        for row in 0..rows {
+            for column in 0..columns {
+                ryu!(
+                    self.page_buffer,
+                    self.settings.precision,
+                    "q",
+                    tile_width,
+                    0.,
+                    0.,
+                    tile_height,
+                    corner.x + column as f64 * tile_width,
+                    corner.y + row as f64 * tile_height,
+                    "cm",
+                    name.as_str(),
+                    "Do Q"
+                );
+            }
+        }
+        self.page_buffer.extend(b"Q\n");
+        self
+    }
 
+    /// Add a comment to the page's content stream, which renderers ignore
+    /// This is mostly useful along with `Compression::Off` for debugging. Line breaks in `text`
+    /// are replaced with spaces so that they don't end the comment early.
+    #[inline]
+    pub fn comment(&mut self, text: &str) -> &mut Self {
+        self.page_buffer.extend(b"% ");
+        self.page_buffer.extend(
+            text.bytes()
+                .map(|b| if b == b'\n' || b == b'\r' { b' ' } else { b }),
+        );
+        self.page_buffer.push(b'\n');
         self
     }
 
@@ -231,7 +828,8 @@ impl Pdf {
         X: Into<f64>,
     {
         let p = p.into_f64();
-        ryu!(self.page_buffer, self.precision, p.x, p.y, "m");
+        self.extend_bounds(p.x, p.y);
+        ryu!(self.page_buffer, self.settings.precision, p.x, p.y, "m");
         self
     }
 
@@ -243,7 +841,8 @@ impl Pdf {
         X: Into<f64>,
     {
         let p = p.into_f64();
-        ryu!(self.page_buffer, self.precision, p.x, p.y, "l");
+        self.extend_bounds(p.x, p.y);
+        ryu!(self.page_buffer, self.settings.precision, p.x, p.y, "l");
         self
     }
 
@@ -255,9 +854,13 @@ impl Pdf {
         (x2, y2): (f64, f64),
         (x3, y3): (f64, f64),
     ) -> &mut Self {
+        // The curve lies within the hull of its control points
+        self.extend_bounds(x1, y1);
+        self.extend_bounds(x2, y2);
+        self.extend_bounds(x3, y3);
         ryu!(
             self.page_buffer,
-            self.precision,
+            self.settings.precision,
             x1,
             y1,
             x2,
@@ -269,490 +872,6369 @@ impl Pdf {
         self
     }
 
-    /// Set the current line width
+    /// Set the dash pattern for all subsequent strokes
+    /// `pattern` alternates the lengths of dashes and gaps, starting `phase` units into the
+    /// pattern. An empty pattern draws solid lines.
     #[inline]
-    pub fn set_line_width<N>(&mut self, width: N) -> &mut Self
-    where
-        N: Into<f64>,
-    {
-        ryu!(self.page_buffer, self.precision, width.into(), "w");
+    pub fn set_dash(&mut self, pattern: &[f64], phase: f64) -> &mut Self {
+        self.dash = (pattern.to_vec(), phase);
+        self.write_dash();
         self
     }
 
-    /// Set the color for all subsequent drawing operations
-    #[inline]
-    pub fn set_color(&mut self, color: Color) -> &mut Self {
-        let norm = |color| f64::from(color) / 255.0;
-        ryu!(
-            self.page_buffer,
-            self.precision,
-            norm(color.red),
-            norm(color.green),
-            norm(color.blue),
-            "SC"
-        );
-        ryu!(
-            self.page_buffer,
-            self.precision,
-            norm(color.red),
-            norm(color.green),
-            norm(color.blue),
-            "rg"
-        );
-        self
+    fn write_dash(&mut self) {
+        let mut ryubuf = ryu::Buffer::new();
+        self.page_buffer.push(b'[');
+        for (i, length) in self.dash.0.iter().enumerate() {
+            if i > 0 {
+                self.page_buffer.push(b' ');
+            }
+            length.ryu_format(&mut self.page_buffer, self.settings.precision, &mut ryubuf);
+        }
+        self.page_buffer.extend(b"] ");
+        self.dash
+            .1
+            .ryu_format(&mut self.page_buffer, self.settings.precision, &mut ryubuf);
+        self.page_buffer.extend(b" d\n");
     }
 
-    /// Apply a coordinate transformation to all subsequent drawing calls
-    /// Consecutive applications of this function are cumulative
-    #[inline]
-    pub fn transform(&mut self, m: Matrix) -> &mut Self {
-        ryu!(
-            self.page_buffer,
-            self.precision,
-            m.v[0],
-            m.v[1],
-            m.v[2],
-            m.v[3],
-            m.v[4],
-            m.v[5],
-            "cm"
-        );
+    /// Run `draw` with a temporary dash pattern, then go back to the previous pattern
+    fn with_dash<F>(&mut self, pattern: &[f64], draw: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        let previous = std::mem::replace(&mut self.dash, (pattern.to_vec(), 0.0));
+        self.write_dash();
+        draw(self);
+        self.dash = previous;
+        self.write_dash();
         self
     }
 
-    /// Draw a circle with the current drawing configuration,
-    /// based on http://spencermortensen.com/articles/bezier-circle/
+    /// Draw a circle with the given dash pattern, leaving the current dash pattern unchanged
     #[inline]
-    pub fn draw_circle<X, Y, N>(&mut self, center: Point<X, Y>, radius: N) -> &mut Self
+    pub fn draw_dashed_circle<X, Y, N>(
+        &mut self,
+        center: Point<X, Y>,
+        radius: N,
+        pattern: &[f64],
+    ) -> &mut Self
     where
         Y: Into<f64>,
         X: Into<f64>,
         N: Into<f64>,
     {
-        let center = center.into_f64();
-        let radius = radius.into();
-        let x = center.x;
-        let y = center.y;
-        let top = y - radius;
-        let bottom = y + radius;
-        let left = x - radius;
-        let right = x + radius;
-        let c = 0.551_915_024_494;
-        let leftp = x - (radius * c);
-        let rightp = x + (radius * c);
-        let topp = y - (radius * c);
-        let bottomp = y + (radius * c);
-        self.move_to(Point { x, y: top });
-        self.curve_to((leftp, top), (left, topp), (left, y));
-        self.curve_to((left, bottomp), (leftp, bottom), (x, bottom));
-        self.curve_to((rightp, bottom), (right, bottomp), (right, y));
-        self.curve_to((right, topp), (rightp, top), (x, top));
-        self.page_buffer.extend(b"S\n"); // close and stroke
-        self
+        self.with_dash(pattern, |pdf| {
+            pdf.draw_circle(center, radius);
+        })
     }
 
-    /// Draw a circle with the current drawing configuration,
-    /// based on http://spencermortensen.com/articles/bezier-circle/
+    /// Draw a rectangle with the given dash pattern, leaving the current dash pattern unchanged
     #[inline]
-    pub fn draw_circle_filled<X, Y, N>(&mut self, center: Point<X, Y>, radius: N) -> &mut Self
+    pub fn draw_dashed_rectangle<X, Y, W, H>(
+        &mut self,
+        corner: Point<X, Y>,
+        size: Size<W, H>,
+        pattern: &[f64],
+    ) -> &mut Self
     where
-        Y: Into<f64>,
         X: Into<f64>,
-        N: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
     {
-        let center = center.into_f64();
-        let x = center.x;
-        let y = center.y;
-        let radius = radius.into();
-        let top = y - radius;
-        let bottom = y + radius;
-        let left = x - radius;
-        let right = x + radius;
-        let c = 0.551_915_024_494;
-        let leftp = x - (radius * c);
-        let rightp = x + (radius * c);
-        let topp = y - (radius * c);
-        let bottomp = y + (radius * c);
-        self.move_to(Point { x, y: top });
-        self.curve_to((leftp, top), (left, topp), (left, y));
-        self.curve_to((left, bottomp), (leftp, bottom), (x, bottom));
-        self.curve_to((rightp, bottom), (right, bottomp), (right, y));
-        self.curve_to((right, topp), (rightp, top), (x, top));
-        self.page_buffer.extend(b"f\n"); // implicitly close and fill
-        self
+        self.with_dash(pattern, |pdf| {
+            pdf.draw_rectangle(corner, size);
+        })
     }
 
-    // TODO: This should actually be something like a
-    // let id = pdf.draw_xobject
-    /// Draw multiple dots using an XObject to save space
+    /// Draw a line with the given dash pattern, leaving the current dash pattern unchanged
     #[inline]
-    pub fn draw_dots(&mut self, x: &[f64], y: &[f64]) -> &mut Self {
-        let c = 0.551_915_024_494;
-        let mut dot = Vec::new();
-        ryu!(dot, self.precision, 0., -1., "m");
-        ryu!(dot, self.precision, -c, -1., -1., -c, -1., 0., "c");
-        ryu!(dot, self.precision, -1., c, -c, 1., 0., 1., "c");
-        ryu!(dot, self.precision, c, 1., 1., c, 1., 0., "c");
-        ryu!(dot, self.precision, 1., -c, c, -1., 0., -1., "c", "f");
-        let mut dot_obj = format!(
-            "<< /Type /XObject /Subtype /Form /BBox [ -2 -2 2 2 ] /Length {} >>\nstream\n",
-            dot.len()
-        )
-        .into_bytes();
-        dot_obj.extend_from_slice(&dot);
-        dot_obj.extend_from_slice(b"endstream\n");
+    pub fn draw_dashed_line<I1, I2>(&mut self, x_iter: I1, y_iter: I2, pattern: &[f64]) -> &mut Self
+    where
+        I1: IntoIterator<Item = f64>,
+        I2: IntoIterator<Item = f64>,
+    {
+        self.with_dash(pattern, |pdf| {
+            pdf.draw_line(x_iter, y_iter);
+        })
+    }
 
-        let id = self.add_object(dot_obj, false, false);
+    /// Set the opacity of all subsequent stroking and filling, from 0 (invisible) to 1 (opaque)
+    #[inline]
+    pub fn set_alpha(&mut self, alpha: f64) -> &mut Self {
+        let alpha = alpha.clamp(0.0, 1.0);
+        self.alpha = alpha;
+        if self.settings.simulate_transparency {
+            // Blend the color already in use with the new opacity
+            if let Some(color) = self.color.take() {
+                self.set_color(color);
+            }
+            return self;
+        }
+        let mut dict = Vec::new();
+        ryu!(dict, self.settings.precision, "/CA", alpha, "/ca", alpha);
+        dict.pop();
+        self.set_ext_g_state(&dict);
+        self
+    }
 
-        self.add_object(format!("<< /M0 {} 0 R >>\n", id).into_bytes(), false, true);
+    /// Apply a graphics state parameter dictionary with the contents `entries`
+    fn set_ext_g_state(&mut self, entries: &[u8]) {
+        let index = if let Some(index) = self.ext_g_states.iter().position(|e| e == entries) {
+            index
+        } else {
+            self.ext_g_states.push(entries.to_vec());
+            self.ext_g_states.len() - 1
+        };
+        self.page_buffer.extend(format!("/GS{index} gs\n").bytes());
+    }
 
-        for (x, y) in x.iter().zip(y) {
-            ryu!(
-                self.page_buffer,
-                self.precision,
-                "q",
-                1.,
-                0.,
-                0.,
-                1.,
-                x,
-                y,
-                "cm /M0 Do Q"
-            );
-        }
-        self.page_buffer.extend(b"Q\n");
+    /// Choose whether fills and strokes overprint the colors underneath instead of knocking
+    /// them out
+    /// Overprint mode 1 is used, so zero components of a CMYK color leave the inks below intact.
+    #[inline]
+    pub fn set_overprint(&mut self, fill: bool, stroke: bool) -> &mut Self {
+        let dict = format!("/OP {stroke} /op {fill} /OPM 1");
+        self.set_ext_g_state(dict.as_bytes());
+        self
+    }
 
+    /// Turn automatic stroke adjustment on or off, which keeps thin lines a consistent width at
+    /// low resolutions
+    #[inline]
+    pub fn set_stroke_adjustment(&mut self, adjust: bool) -> &mut Self {
+        self.set_ext_g_state(format!("/SA {adjust}").as_bytes());
         self
     }
 
-    /// Draw a line between all these points in the order they appear
+    /// Choose whether overlapping glyphs in a text object knock each other out, so that
+    /// translucent text is composited once instead of darkening where glyphs overlap
+    /// Knockout is on by default.
     #[inline]
-    pub fn draw_line<I1, I2>(&mut self, x_iter: I1, y_iter: I2) -> &mut Self
+    pub fn set_text_knockout(&mut self, knockout: bool) -> &mut Self {
+        self.set_ext_g_state(format!("/TK {knockout}").as_bytes());
+        self
+    }
+
+    /// Set the current line width
+    #[inline]
+    pub fn set_line_width<N>(&mut self, width: N) -> &mut Self
     where
-        I1: IntoIterator<Item = f64>,
-        I2: IntoIterator<Item = f64>,
+        N: Into<f64>,
     {
-        let mut x_iter = x_iter.into_iter();
-        let mut y_iter = y_iter.into_iter();
-        // Can't just loop because we have to move_to the first point, then we can line_to the rest
-        if let (Some(x), Some(y)) = (x_iter.next(), y_iter.next()) {
-            self.move_to(Point { x, y });
-            for (x, y) in x_iter.zip(y_iter) {
-                self.line_to(Point { x, y });
-            }
-        }
-        self.page_buffer.extend(b"S\n");
+        self.line_width = width.into();
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            self.line_width,
+            "w"
+        );
         self
     }
 
-    /// End a line
+    /// The color used to fill shapes and text
     #[inline]
-    pub fn end_line(&mut self) -> &mut Self {
-        self.page_buffer.extend(b"S\n");
-        self
+    #[must_use]
+    pub fn current_fill_color(&self) -> Color {
+        self.fill_color
     }
 
-    /// Draw a rectangle in the current color with bottom-left corner at with bottom-lef
-    /// corner at `corner` and dimensions `size`.
+    /// The color used to stroke lines and outlines
+    #[inline]
+    #[must_use]
+    pub fn current_stroke_color(&self) -> Color {
+        self.stroke_color
+    }
 
+    /// The width of stroked lines
     #[inline]
-    pub fn draw_rectangle_filled<X, Y, W, H>(
-        &mut self,
-        corner: Point<X, Y>,
-        size: Size<W, H>,
-    ) -> &mut Self
-    where
-        X: Into<f64>,
-        Y: Into<f64>,
-        W: Into<f64>,
-        H: Into<f64>,
-    {
-        let corner = corner.into_f64();
-        let size = size.into_f64();
+    #[must_use]
+    pub fn current_line_width(&self) -> f64 {
+        self.line_width
+    }
+
+    /// The font and font size used for text
+    #[inline]
+    #[must_use]
+    pub fn current_font(&self) -> (&Font, f64) {
+        (&self.fonts[self.current_font_index], self.font_size)
+    }
+
+    /// The uncompressed content stream of the page in progress, for checking which operators
+    /// have been written
+    /// Anything drawn with `draw_background` is not included until the page is finished.
+    #[inline]
+    #[must_use]
+    pub fn current_page_stream(&self) -> &[u8] {
+        &self.page_buffer
+    }
+
+    /// The colors and line width, to be put back with `restore_style` after a `Q`
+    fn style(&self) -> Style {
+        (
+            self.color,
+            self.fill_color,
+            self.stroke_color,
+            self.line_width,
+        )
+    }
+
+    fn restore_style(&mut self, style: Style) {
+        (
+            self.color,
+            self.fill_color,
+            self.stroke_color,
+            self.line_width,
+        ) = style;
+    }
+
+    /// Set the flatness tolerance, the largest distance in device pixels allowed between a curve
+    /// and the straight lines a viewer draws in its place
+    /// The PDF specification allows values from 0 to 100, and others are clamped to that range.
+    #[inline]
+    pub fn set_flatness(&mut self, tolerance: f64) -> &mut Self {
         ryu!(
             self.page_buffer,
-            self.precision,
-            corner.x,
-            corner.y,
-            size.width,
-            size.height,
-            "re f" // Fill path using Nonzero Winding Number Rule
+            self.settings.precision,
+            tolerance.clamp(0.0, 100.0),
+            "i"
         );
         self
     }
 
-    /// Draw a shaded rectangle in the current color with bottom-left corner at with bottom-left
-    /// corner at `corner` and dimensions `size`.
+    /// Set how colors are mapped to the colors an output device can reproduce
     #[inline]
-    pub fn draw_rectangle<X, Y, W, H>(&mut self, corner: Point<X, Y>, size: Size<W, H>) -> &mut Self
-    where
-        X: Into<f64>,
-        Y: Into<f64>,
-        W: Into<f64>,
-        H: Into<f64>,
-    {
-        let corner = corner.into_f64();
-        let size = size.into_f64();
+    pub fn set_rendering_intent(&mut self, intent: RenderingIntent) -> &mut Self {
+        self.page_buffer
+            .extend(format!("{} ri\n", intent.name()).bytes());
+        self
+    }
+
+    /// Draw semi-transparent colors as opaque colors, for viewers which ignore transparency
+    /// While this is on, `set_alpha` does not make anything transparent. Instead each color is
+    /// mixed with the background color according to the current alpha. The result only looks
+    /// right where the page behind the drawing really is the color set with
+    /// `set_background_color`, so the background must be known ahead of time.
+    #[inline]
+    pub fn set_simulate_transparency(&mut self, simulate: bool) -> &mut Self {
+        self.settings.simulate_transparency = simulate;
+        self
+    }
+
+    /// Set the background color that simulated transparency blends toward, white by default
+    #[inline]
+    pub fn set_background_color(&mut self, color: Color) -> &mut Self {
+        self.settings.background = color;
+        self
+    }
 
+    /// Set the miter limit, which bounds how far a mitered join may extend past a sharp corner
+    /// Values below 1 are not allowed by the PDF specification, and are raised to 1.
+    #[inline]
+    pub fn set_miter_limit(&mut self, limit: f64) -> &mut Self {
         ryu!(
             self.page_buffer,
-            self.precision,
-            corner.x,
-            corner.y,
-            size.width,
-            size.height,
-            "re S" // Fill path using Nonzero Winding Number Rule
+            self.settings.precision,
+            limit.max(1.0),
+            "M"
         );
         self
     }
 
-    /// Set the font for all subsequent drawing calls
+    /// Set the line width that every page added after this call starts with
     #[inline]
-    pub fn font<N>(&mut self, font: Font, size: N) -> &mut Self
+    pub fn set_default_line_width<N>(&mut self, width: N) -> &mut Self
     where
         N: Into<f64>,
     {
-        match self.fonts.iter().position(|f| *f == font) {
-            Some(index) => {
-                self.current_font_index = index;
-            }
-            None => {
-                self.fonts.push(font);
-                self.current_font_index = self.fonts.len() - 1;
-            }
-        }
-        self.font_size = size.into();
+        self.settings.default_line_width = Some(width.into());
         self
     }
 
-    /// Convienence method to figure out the width of a string
-    /// May be required for some users to position text properly
-    pub fn width_of(&self, text: &str) -> f64 {
-        let current_font = &self.fonts[self.current_font_index];
-        text.chars()
-            .filter(|c| *c != '\n')
-            .map(|c| fonts::glyph_width(current_font, c))
-            .sum::<f64>()
-            * self.font_size
+    /// Set the color that every page added after this call starts with
+    #[inline]
+    pub fn set_default_color(&mut self, color: Color) -> &mut Self {
+        self.settings.default_color = Some(color);
+        self
     }
 
-    /// Draw text at a given location with the current settings
+    /// Set the color for all subsequent drawing operations
+    /// Setting the color that is already in use emits nothing.
     #[inline]
-    pub fn draw_text<X, Y>(
-        &mut self,
-        position: Point<X, Y>,
-        alignment: Alignment,
-        text: &str,
-    ) -> &mut Self
-    where
-        X: Into<f64>,
-        Y: Into<f64>,
-    {
-        let x = position.x.into();
-        let y = position.y.into();
-        let height = self.font_size;
+    pub fn set_color(&mut self, color: Color) -> &mut Self {
+        if self.color == Some(color) {
+            return self;
+        }
+        self.color = Some(color);
+        self.fill_color = color;
+        self.stroke_color = color;
+        self.write_color(color, "SC");
+        self.write_color(color, "rg");
+        self
+    }
 
-        self.page_buffer
-            .extend(format!("BT\n/F{} {} Tf\n", self.current_font_index, self.font_size).bytes());
+    /// Set the color used to fill shapes and text, leaving the stroke color unchanged
+    #[inline]
+    pub fn set_fill_color(&mut self, color: Color) -> &mut Self {
+        self.color = None;
+        self.fill_color = color;
+        self.write_color(color, "rg");
+        self
+    }
 
-        let num_lines = text.split('\n').count() as f64;
-        for (l, line) in text.split('\n').enumerate() {
-            let line_width = self.width_of(line);
-            let l = l as f64;
+    /// Set the color used to stroke lines and outlines, leaving the fill color unchanged
+    #[inline]
+    pub fn set_stroke_color(&mut self, color: Color) -> &mut Self {
+        self.color = None;
+        self.stroke_color = color;
+        self.write_color(color, "SC");
+        self
+    }
 
-            let (line_x, line_y) = match alignment {
-                Alignment::TopLeft => (x, y - height * (l + 1.0)),
-                Alignment::TopRight => (x - line_width, y - height * (l + 1.0)),
-                Alignment::TopCenter => (x - line_width / 2.0, y - height * (l + 1.0)),
-                Alignment::CenterLeft => (
-                    x,
-                    (y - height / 3.0) - (l - (num_lines - 1.0) / 2.0) * height * 1.25,
-                ),
-                Alignment::CenterRight => (
-                    x - line_width,
-                    (y - height / 3.0) - (l - (num_lines - 1.0) / 2.0) * height * 1.25,
-                ),
-                Alignment::CenterCenter => (
-                    x - line_width / 2.0,
-                    (y - height / 3.0) - (l - (num_lines - 1.0) / 2.0) * height * 1.25,
-                ),
-                Alignment::BottomLeft => (x, y + (num_lines - l - 1.0) * 1.25 * height),
-                Alignment::BottomRight => {
-                    (x - line_width, y + (num_lines - l - 1.0) * 1.25 * height)
-                }
-                Alignment::BottomCenter => (
-                    x - line_width / 2.0,
-                    y + (num_lines - l - 1.0) * 1.25 * height,
-                ),
-            };
+    /// Write `color` followed by `operator`, blending it if transparency is simulated
+    fn write_color(&mut self, color: Color, operator: &str) {
+        let color = if self.settings.simulate_transparency {
+            self.settings.background.lerp(color, self.alpha)
+        } else {
+            color
+        };
+        let norm = |color| f64::from(color) / 255.0;
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            norm(color.red),
+            norm(color.green),
+            norm(color.blue),
+            operator
+        );
+    }
 
-            ryu!(
-                self.page_buffer,
-                self.precision,
-                1.,
-                0.,
-                0.,
-                1.,
-                line_x,
-                line_y
-            );
-            self.page_buffer.extend_from_slice(b"Tm (");
-            for c in line.chars() {
-                let data = format!("\\{:o}", c as u32);
-                self.page_buffer.extend(data.bytes());
-            }
-            self.page_buffer.extend(b") Tj\n");
-        }
-        self.page_buffer.extend(b"ET\n");
+    /// Apply a coordinate transformation to all subsequent drawing calls
+    /// Consecutive applications of this function are cumulative
+    #[inline]
+    pub fn transform(&mut self, m: Matrix) -> &mut Self {
+        self.ctm = m * self.ctm;
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            m.v[0],
+            m.v[1],
+            m.v[2],
+            m.v[3],
+            m.v[4],
+            m.v[5],
+            "cm"
+        );
         self
     }
 
-    /// Move to a new page in the PDF document
+    /// Draw a circle with the current drawing configuration,
+    /// based on <http://spencermortensen.com/articles/bezier-circle/>
     #[inline]
-    pub fn add_page<W, H>(&mut self, size: Size<W, H>) -> &mut Self
+    pub fn draw_circle<X, Y, N>(&mut self, center: Point<X, Y>, radius: N) -> &mut Self
     where
-        W: Into<f64>,
-        H: Into<f64>,
+        Y: Into<f64>,
+        X: Into<f64>,
+        N: Into<f64>,
     {
-        // Compress and write out the previous page if it exists
-        if !self.page_buffer.is_empty() {
-            self.end_page();
-            self.page_buffer.clear();
-        }
-
-        self.page_buffer
-            .extend("/DeviceRGB cs /DeviceRGB CS\n1 j 1 J\n".bytes());
-        self.width = size.width.into();
-        self.height = size.height.into();
+        let center = center.into_f64();
+        let radius = radius.into();
+        let x = center.x;
+        let y = center.y;
+        let top = y - radius;
+        let bottom = y + radius;
+        let left = x - radius;
+        let right = x + radius;
+        let c = 0.551_915_024_494;
+        let leftp = x - (radius * c);
+        let rightp = x + (radius * c);
+        let topp = y - (radius * c);
+        let bottomp = y + (radius * c);
+        self.move_to(Point { x, y: top });
+        self.curve_to((leftp, top), (left, topp), (left, y));
+        self.curve_to((left, bottomp), (leftp, bottom), (x, bottom));
+        self.curve_to((rightp, bottom), (right, bottomp), (right, y));
+        self.curve_to((right, topp), (rightp, top), (x, top));
+        self.page_buffer.extend(b"S\n"); // close and stroke
         self
     }
 
-    /// Dump a page out to disk
-    fn end_page(&mut self) {
-        // Write out any images associated with this page
-        // TODO: are images global or associated with a page?
+    /// Draw a circle approximated by `segments` bezier curves instead of the usual four
+    /// More segments follow the circle more closely, which only matters for very large circles
+    /// or extreme zoom. Fewer than four segments are not allowed.
+    pub fn draw_circle_n<X, Y, N>(
+        &mut self,
+        center: Point<X, Y>,
+        radius: N,
+        segments: usize,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        N: Into<f64>,
+    {
+        use std::f64::consts::PI;
 
-        let page_stream = if let Some(level) = self.compression.to_deflate() {
-            let compressed = deflate::deflate_bytes_zlib_conf(&self.page_buffer, level);
-            let mut page = format!(
-                "<< /Length {} /Filter [/FlateDecode] >>\nstream\n",
-                compressed.len()
-            )
-            .into_bytes();
-            page.extend_from_slice(&compressed);
-            page.extend(b"endstream\n");
-            page
-        } else {
-            let mut page = Vec::new();
-            page.extend(format!("<< /Length {} >>\nstream\n", self.page_buffer.len()).bytes());
-            page.extend(&self.page_buffer);
-            page.extend(b"endstream\n");
-            page
+        let center = center.into_f64();
+        let radius = radius.into();
+        let segments = segments.max(4);
+        let step = 2.0 * PI / segments as f64;
+        // Distance from each end of an arc to its control point, for a unit circle
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let point = |angle: f64| {
+            let (sin, cos) = angle.sin_cos();
+            (center.x + radius * cos, center.y + radius * sin, sin, cos)
         };
 
-        // Create the stream object for this page
-        let stream_object_id = self.add_object(page_stream, false, false);
-
-        // Create the page object, which describes settings for the whole page
-        let mut page_object = b"<< /Type /Page\n \
-            /Parent 2 0 R\n \
-            /Resources <<\n"
-            .to_vec();
-
-        for obj in self.objects.iter().filter(|o| o.is_xobject) {
-            page_object.extend(format!("/XObject {} 0 R ", obj.id).bytes());
+        let start = -PI / 2.0;
+        let (x, y, _, _) = point(start);
+        self.move_to(Point { x, y });
+        for i in 0..segments {
+            let (x0, y0, sin0, cos0) = point(start + step * i as f64);
+            let (x1, y1, sin1, cos1) = point(start + step * (i + 1) as f64);
+            self.curve_to(
+                (x0 - k * radius * sin0, y0 + k * radius * cos0),
+                (x1 + k * radius * sin1, y1 - k * radius * cos1),
+                (x1, y1),
+            );
         }
-
-        for (f, font) in self.fonts.iter().enumerate() {
-            page_object.extend(
-                format!(
-                    "  /Font <<\n   /F{} <<\n    /Type /Font\n    /Subtype /Type1\n    /BaseFont \
-                     /{:?}\n    /Encoding /WinAnsiEncoding\n   >>\n  >>\n",
-                    f, font
-                )
-                .bytes(),
+        self.page_buffer.extend(b"S\n");
+        self
+    }
+
+    /// Draw a circle with the current drawing configuration,
+    /// based on <http://spencermortensen.com/articles/bezier-circle/>
+    #[inline]
+    pub fn draw_circle_filled<X, Y, N>(&mut self, center: Point<X, Y>, radius: N) -> &mut Self
+    where
+        Y: Into<f64>,
+        X: Into<f64>,
+        N: Into<f64>,
+    {
+        let center = center.into_f64();
+        let x = center.x;
+        let y = center.y;
+        let radius = radius.into();
+        let top = y - radius;
+        let bottom = y + radius;
+        let left = x - radius;
+        let right = x + radius;
+        let c = 0.551_915_024_494;
+        let leftp = x - (radius * c);
+        let rightp = x + (radius * c);
+        let topp = y - (radius * c);
+        let bottomp = y + (radius * c);
+        self.move_to(Point { x, y: top });
+        self.curve_to((leftp, top), (left, topp), (left, y));
+        self.curve_to((left, bottomp), (leftp, bottom), (x, bottom));
+        self.curve_to((rightp, bottom), (right, bottomp), (right, y));
+        self.curve_to((right, topp), (rightp, top), (x, top));
+        self.page_buffer.extend(b"f\n"); // implicitly close and fill
+        self
+    }
+
+    // TODO: This should actually be something like a
+    // let id = pdf.draw_xobject
+    /// Draw multiple dots using an `XObject` to save space
+    #[inline]
+    pub fn draw_dots(&mut self, x: &[f64], y: &[f64]) -> &mut Self {
+        self.draw_dots_with_radius(x, y, 1.0)
+    }
+
+    /// Draw multiple dots which are `radius` in size on the page, no matter what scaling has been
+    /// applied with `transform`
+    /// Nothing is drawn if the current transformation collapses the page to a line or a point.
+    pub fn draw_dots_sized(&mut self, x: &[f64], y: &[f64], radius: f64) -> &mut Self {
+        let m = self.ctm.v;
+        // The square root of the determinant is the average scale of the transformation
+        let scale = (m[0] * m[3] - m[1] * m[2]).abs().sqrt();
+        if scale == 0.0 {
+            return self;
+        }
+        self.draw_dots_with_radius(x, y, radius / scale)
+    }
+
+    /// Draw dots of `radius` in the current coordinate system
+    fn draw_dots_with_radius(&mut self, x: &[f64], y: &[f64], radius: f64) -> &mut Self {
+        let name = self.add_dot_xobject(radius);
+        self.stamp_dots(&name, x, y, radius)
+    }
+
+    /// Draw the dot `XObject` called `name`, which is `radius` in size, at each point
+    fn stamp_dots(&mut self, name: &str, x: &[f64], y: &[f64], radius: f64) -> &mut Self {
+        for (x, y) in x.iter().zip(y) {
+            self.extend_bounds_rect(
+                Point {
+                    x: x - radius,
+                    y: y - radius,
+                },
+                Size {
+                    width: 2.0 * radius,
+                    height: 2.0 * radius,
+                },
+            );
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                "q",
+                1.,
+                0.,
+                0.,
+                1.,
+                x,
+                y,
+                "cm",
+                name,
+                "Do Q"
             );
         }
-        page_object.extend_from_slice(
-            format!(
-                " >>\n \
-                 /MediaBox [0 0 {} {}]\n \
-                 /Contents {} 0 R\n\
-                 >>\n",
-                self.width, self.height, stream_object_id
-            )
-            .as_bytes(),
+        self
+    }
+
+    /// Draw a dot at each point, scaling the unit-sized dot drawn by `draw_dots` by the point's
+    /// entry in `sizes`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x`, `y`, and `sizes` are not all the same length.
+    pub fn draw_dots_weighted(&mut self, x: &[f64], y: &[f64], sizes: &[f64]) -> &mut Self {
+        assert!(
+            x.len() == y.len() && y.len() == sizes.len(),
+            "draw_dots_weighted needs the same number of x values ({}), y values ({}), and sizes \
+             ({})",
+            x.len(),
+            y.len(),
+            sizes.len()
+        );
+        let name = self.add_dot_xobject(1.0);
+        for ((x, y), size) in x.iter().zip(y).zip(sizes) {
+            self.extend_bounds_rect(
+                Point {
+                    x: x - size,
+                    y: y - size,
+                },
+                Size {
+                    width: 2.0 * size,
+                    height: 2.0 * size,
+                },
+            );
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                "q",
+                size,
+                0.,
+                0.,
+                size,
+                x,
+                y,
+                "cm",
+                name.as_str(),
+                "Do Q"
+            );
+        }
+        self
+    }
+
+    /// Draw a dot like `draw_dots` at each point in the matching entry of `colors`
+    /// Points are grouped by color so each color is only set once, which means dots of one color
+    /// may be drawn over dots that came later in the slices. The current colors are unchanged
+    /// afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x`, `y`, and `colors` are not all the same length.
+    pub fn draw_dots_colored(&mut self, x: &[f64], y: &[f64], colors: &[Color]) -> &mut Self {
+        assert!(
+            x.len() == y.len() && y.len() == colors.len(),
+            "draw_dots_colored needs the same number of x values ({}), y values ({}), and colors \
+             ({})",
+            x.len(),
+            y.len(),
+            colors.len()
         );
-        self.add_object(page_object, true, false);
+        // Groups are kept in the order each color first appears
+        let mut group_index = HashMap::new();
+        let mut groups: Vec<(Color, Vec<f64>, Vec<f64>)> = Vec::new();
+        for ((&x, &y), &color) in x.iter().zip(y).zip(colors) {
+            let i = *group_index.entry(color).or_insert_with(|| {
+                groups.push((color, Vec::new(), Vec::new()));
+                groups.len() - 1
+            });
+            groups[i].1.push(x);
+            groups[i].2.push(y);
+        }
+        if groups.is_empty() {
+            return self;
+        }
 
-        self.fonts.truncate(1);
+        let name = self.add_dot_xobject(1.0);
+        let style = self.style();
+        self.page_buffer.extend(b"q\n");
+        for (color, x, y) in groups {
+            self.set_fill_color(color).stamp_dots(&name, &x, &y, 1.0);
+        }
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self
     }
 
-    /// Write the in-memory PDF representation to disk
-    pub fn write_to<F>(&mut self, filename: F) -> io::Result<()> where F: AsRef<std::path::Path> {
-        use std::io::Write;
+    /// Create a form `XObject` which fills a circle of `radius` around the origin, returning its
+    /// resource name on the current page
+    fn add_dot_xobject(&mut self, radius: f64) -> String {
+        let c = 0.551_915_024_494 * radius;
+        let r = radius;
+        let mut dot = Vec::new();
+        ryu!(dot, self.settings.precision, 0., -r, "m");
+        ryu!(dot, self.settings.precision, -c, -r, -r, -c, -r, 0., "c");
+        ryu!(dot, self.settings.precision, -r, c, -c, r, 0., r, "c");
+        ryu!(dot, self.settings.precision, c, r, r, c, r, 0., "c");
+        ryu!(dot, self.settings.precision, r, -c, c, -r, 0., -r, "c", "f");
+        let mut dot_obj = format!(
+            "<< /Type /XObject /Subtype /Form /BBox [ {} {} {} {} ] /Length {} >>\nstream\n",
+            -2.0 * r,
+            -2.0 * r,
+            2.0 * r,
+            2.0 * r,
+            dot.len()
+        )
+        .into_bytes();
+        dot_obj.extend_from_slice(&dot);
+        dot_obj.extend_from_slice(b"endstream\n");
+
+        let id = self.add_object(dot_obj);
+        self.add_xobject(id)
+    }
 
-        if !self.page_buffer.is_empty() {
-            self.end_page();
+    /// Draw a line between all these points in the order they appear
+    #[inline]
+    pub fn draw_line<I1, I2>(&mut self, x_iter: I1, y_iter: I2) -> &mut Self
+    where
+        I1: IntoIterator<Item = f64>,
+        I2: IntoIterator<Item = f64>,
+    {
+        let mut x_iter = x_iter.into_iter();
+        let mut y_iter = y_iter.into_iter();
+        // Can't just loop because we have to move_to the first point, then we can line_to the rest
+        if let (Some(x), Some(y)) = (x_iter.next(), y_iter.next()) {
+            self.move_to(Point { x, y });
+            for (x, y) in x_iter.zip(y_iter) {
+                self.line_to(Point { x, y });
+            }
         }
+        self.page_buffer.extend(b"S\n");
+        self
+    }
 
-        // Write out each object
-        for obj in self.objects.iter_mut().skip(2) {
-            obj.offset = Some(self.buffer.len());
-            self.buffer.extend(format!("{} 0 obj\n", obj.id).as_bytes());
-            self.buffer.extend_from_slice(&obj.contents);
-            self.buffer.extend_from_slice(b"endobj\n");
+    /// Draw a line through each of `points` in order, like `draw_line` but with the coordinates
+    /// already paired up
+    #[inline]
+    pub fn draw_path(&mut self, points: &[(f64, f64)]) -> &mut Self {
+        self.trace_path(points);
+        self.page_buffer.extend(b"S\n");
+        self
+    }
+
+    /// Fill the shape with `points` as its corners in the current fill color
+    #[inline]
+    pub fn fill_path(&mut self, points: &[(f64, f64)]) -> &mut Self {
+        self.trace_path(points);
+        self.page_buffer.extend(b"f\n");
+        self
+    }
+
+    fn trace_path(&mut self, points: &[(f64, f64)]) {
+        if let Some((&(x, y), rest)) = points.split_first() {
+            self.move_to(Point { x, y });
+            for &(x, y) in rest {
+                self.line_to(Point { x, y });
+            }
         }
+    }
 
-        // Write out the page tree object
-        self.objects[1].offset = Some(self.buffer.len());
-        self.buffer.extend(b"2 0 obj\n");
-        self.buffer.extend(b"<< /Type /Pages\n");
-        self.buffer.extend(
-            format!(
-                "/Count {}\n",
-                self.objects.iter().filter(|o| o.is_page).count()
-            )
-            .bytes(),
+    /// Draw a line through each of `points` in order, with each segment in its own style
+    /// `styles` has one style per segment, or a single style for every segment. Neighboring
+    /// segments in the same style are stroked together so their joins are drawn. The current
+    /// colors and line width are unchanged afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not one or `points.len() - 1` styles.
+    pub fn draw_styled_polyline(
+        &mut self,
+        points: &[Point<f64, f64>],
+        styles: &[StrokeStyle],
+    ) -> &mut Self {
+        let segments = points.len().saturating_sub(1);
+        assert!(
+            styles.len() == 1 || styles.len() == segments,
+            "expected 1 or {} styles for {} points, not {}",
+            segments,
+            points.len(),
+            styles.len()
         );
-        self.buffer.extend(b"/Kids [");
-        for obj in self.objects.iter().filter(|obj| obj.is_page) {
-            self.buffer.extend(format!("{} 0 R ", obj.id).bytes());
+        let style_of = |segment: usize| styles[segment.min(styles.len() - 1)];
+
+        let style = self.style();
+        self.page_buffer.extend(b"q\n");
+        let mut start = 0;
+        while start < segments {
+            let run_style = style_of(start);
+            let end = (start..segments)
+                .find(|&segment| style_of(segment) != run_style)
+                .unwrap_or(segments);
+            self.set_stroke_color(run_style.color);
+            self.set_line_width(run_style.width);
+            self.move_to(points[start]);
+            for &point in &points[start + 1..=end] {
+                self.line_to(point);
+            }
+            self.page_buffer.extend(b"S\n");
+            start = end;
         }
-        self.buffer.pop();
-        self.buffer.extend(b"] >>\nendobj\n");
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self
+    }
 
-        // Write out the catalog dictionary object
-        self.objects[0].offset = Some(self.buffer.len());
-        self.buffer
-            .extend_from_slice(b"1 0 obj\n<< /Type /Catalog\n/Pages 2 0 R >>\nendobj\n");
+    /// Fill the area between the line through these points and the horizontal line `baseline_y`
+    #[inline]
+    pub fn fill_area_under<I1, I2>(&mut self, x_iter: I1, y_iter: I2, baseline_y: f64) -> &mut Self
+    where
+        I1: IntoIterator<Item = f64>,
+        I2: IntoIterator<Item = f64>,
+    {
+        let mut points = x_iter.into_iter().zip(y_iter);
+        if let Some((first_x, y)) = points.next() {
+            self.move_to(Point { x: first_x, y });
+            let mut last_x = first_x;
+            for (x, y) in points {
+                self.line_to(Point { x, y });
+                last_x = x;
+            }
+            self.line_to(Point {
+                x: last_x,
+                y: baseline_y,
+            });
+            self.line_to(Point {
+                x: first_x,
+                y: baseline_y,
+            });
+            self.page_buffer.extend(b"h f\n");
+        }
+        self
+    }
 
-        // Write the cross-reference table
-        let startxref = self.buffer.len() + 1; // NOTE: apparently there's some 1-based indexing??
-        self.buffer.extend(b"xref\n");
-        self.buffer
-            .extend(format!("0 {}\n", self.objects.len() + 1).bytes());
-        self.buffer.extend(b"0000000000 65535 f \n");
-        self.objects.sort_by(|a, b| a.id.cmp(&b.id));
+    /// Stroke a smooth curve through all of these points
+    /// The curve is a cardinal spline, where `tension` scales the tangent at each point; `0.5`
+    /// gives a Catmull-Rom spline and `0.0` gives straight lines. With fewer than three points
+    /// this draws a straight line.
+    pub fn draw_spline(&mut self, points: &[Point<f64, f64>], tension: f64) -> &mut Self {
+        if points.len() < 3 {
+            return self.draw_line(points.iter().map(|p| p.x), points.iter().map(|p| p.y));
+        }
+        self.move_to(points[0]);
+        for i in 0..points.len() - 1 {
+            let p0 = points[i.saturating_sub(1)];
+            let p1 = points[i];
+            let p2 = points[i + 1];
+            let p3 = points[(i + 2).min(points.len() - 1)];
+            let k = tension / 3.0;
+            self.curve_to(
+                (p1.x + (p2.x - p0.x) * k, p1.y + (p2.y - p0.y) * k),
+                (p2.x - (p3.x - p1.x) * k, p2.y - (p3.y - p1.y) * k),
+                (p2.x, p2.y),
+            );
+        }
+        self.page_buffer.extend(b"S\n");
+        self
+    }
 
-        for obj in &self.objects {
-            self.buffer
-                .extend(format!("{:010} 00000 f \n", obj.offset.unwrap()).bytes());
+    /// Draw an arrow from `from` to `to`, with a filled head `head_size` long at `to`
+    /// Nothing is drawn if the two points are the same.
+    pub fn draw_arrow<X1, Y1, X2, Y2>(
+        &mut self,
+        from: Point<X1, Y1>,
+        to: Point<X2, Y2>,
+        head_size: f64,
+    ) -> &mut Self
+    where
+        X1: Into<f64>,
+        Y1: Into<f64>,
+        X2: Into<f64>,
+        Y2: Into<f64>,
+    {
+        let from = from.into_f64();
+        let to = to.into_f64();
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        if dx == 0.0 && dy == 0.0 {
+            return self;
         }
+        let angle = dy.atan2(dx);
+        // The head is an isosceles triangle with a 60 degree point
+        let spread = std::f64::consts::FRAC_PI_6;
+        let corner = |side: f64| {
+            let (sin, cos) = (angle + side * spread).sin_cos();
+            (to.x - head_size * cos, to.y - head_size * sin)
+        };
+        let (left, right) = (corner(1.0), corner(-1.0));
+        // Stop the shaft at the back of the head so a wide line doesn't poke through the point
+        let back = (
+            to.x - head_size * spread.cos() * angle.cos(),
+            to.y - head_size * spread.cos() * angle.sin(),
+        );
+        self.extend_bounds(from.x, from.y);
+        self.extend_bounds(to.x, to.y);
+        self.extend_bounds(left.0, left.1);
+        self.extend_bounds(right.0, right.1);
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            from.x,
+            from.y,
+            "m"
+        );
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            back.0,
+            back.1,
+            "l"
+        );
+        self.page_buffer.extend(b"S\n");
+        ryu!(self.page_buffer, self.settings.precision, to.x, to.y, "m");
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            left.0,
+            left.1,
+            "l"
+        );
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            right.0,
+            right.1,
+            "l"
+        );
+        self.page_buffer.extend(b"f\n");
+        self
+    }
 
-        // Write the document trailer
-        self.buffer.extend(b"trailer\n");
-        self.buffer
-            .extend(format!("<< /Size {}\n", self.objects.len()).bytes());
-        self.buffer.extend(b"/Root 1 0 R >>\n");
+    /// End a line
+    #[inline]
+    pub fn end_line(&mut self) -> &mut Self {
+        self.page_buffer.extend(b"S\n");
+        self
+    }
 
-        // Write the offset to the xref table
-        self.buffer
-            .extend(format!("startxref\n{}\n", startxref).bytes());
+    /// Draw a rectangle in the current color with bottom-left corner at with bottom-lef
+    /// corner at `corner` and dimensions `size`.
+    #[inline]
+    pub fn draw_rectangle_filled<X, Y, W, H>(
+        &mut self,
+        corner: Point<X, Y>,
+        size: Size<W, H>,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        self.extend_bounds_rect(corner, size);
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            corner.x,
+            corner.y,
+            size.width,
+            size.height,
+            "re f" // Fill path using Nonzero Winding Number Rule
+        );
+        self
+    }
 
-        // Write the PDF EOF
-        self.buffer.extend(b"%%EOF");
+    /// Fill a rectangle in `fill` over a shadow in `shadow` moved by `offset`
+    /// The shadow is softened by drawing `blur_steps` layers of it, each one unit larger and more
+    /// transparent than the last, or it has a hard edge if `blur_steps` is 0. The current colors
+    /// and opacity are unchanged afterwards.
+    pub fn draw_rectangle_shadowed<X, Y, W, H>(
+        &mut self,
+        (corner, size): (Point<X, Y>, Size<W, H>),
+        fill: Color,
+        shadow: Color,
+        offset: Point<f64, f64>,
+        blur_steps: usize,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        let (style, alpha) = (self.style(), self.alpha);
+        self.page_buffer.extend(b"q\n");
+        self.set_fill_color(shadow);
+        // The largest, faintest layer is drawn first
+        let layers = blur_steps as f64 + 1.0;
+        for step in (0..=blur_steps).rev() {
+            let spread = step as f64;
+            self.set_alpha(alpha * (layers - spread) / layers);
+            self.draw_rectangle_filled(
+                Point {
+                    x: corner.x + offset.x - spread,
+                    y: corner.y + offset.y - spread,
+                },
+                Size {
+                    width: size.width + 2.0 * spread,
+                    height: size.height + 2.0 * spread,
+                },
+            );
+        }
+        self.set_alpha(alpha);
+        self.set_fill_color(fill);
+        self.draw_rectangle_filled(corner, size);
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self.alpha = alpha;
+        self
+    }
+
+    /// Draw a histogram of `samples` as `bins` filled bars with the bottom-left corner at `origin`
+    /// The bins evenly divide the range of the samples, the bars together span `width`, and the
+    /// fullest bin is drawn `height` tall. Nothing is drawn if there are no samples or no bins.
+    pub fn draw_histogram<X, Y>(
+        &mut self,
+        origin: Point<X, Y>,
+        samples: &[f64],
+        bins: usize,
+        height: f64,
+        width: f64,
+        color: Color,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        if samples.is_empty() || bins == 0 {
+            return self;
+        }
+        let origin = origin.into_f64();
+        let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = if max > min { max - min } else { 1.0 };
+
+        let mut counts = vec![0_usize; bins];
+        for &sample in samples {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let bin = ((sample - min) / range * bins as f64) as usize;
+            counts[bin.min(bins - 1)] += 1;
+        }
+        let tallest = counts.iter().copied().max().unwrap_or(0) as f64;
+        let bar_width = width / bins as f64;
 
-        File::create(filename)?.write_all(self.buffer.as_slice())
+        self.set_color(color);
+        for (i, &count) in counts.iter().enumerate() {
+            if count > 0 {
+                self.draw_rectangle_filled(
+                    Point {
+                        x: origin.x + i as f64 * bar_width,
+                        y: origin.y,
+                    },
+                    Size {
+                        width: bar_width,
+                        height: count as f64 / tallest * height,
+                    },
+                );
+            }
+        }
+        self
     }
+
+    /// Draw a small line chart of `values` stretched across `rect` in `color`, with dots marking
+    /// the lowest and highest values
+    /// The current colors and line width are left unchanged. Nothing is drawn if `values` is empty.
+    pub fn draw_sparkline<X, Y, W, H>(
+        &mut self,
+        (corner, size): (Point<X, Y>, Size<W, H>),
+        values: &[f64],
+        color: Color,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        if values.is_empty() {
+            return self;
+        }
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        let (mut min_index, mut max_index) = (0, 0);
+        for (i, &value) in values.iter().enumerate() {
+            if value < values[min_index] {
+                min_index = i;
+            }
+            if value > values[max_index] {
+                max_index = i;
+            }
+        }
+        let (min, max) = (values[min_index], values[max_index]);
+
+        let step = if values.len() > 1 {
+            size.width / (values.len() - 1) as f64
+        } else {
+            0.0
+        };
+        let x: Vec<f64> = (0..values.len())
+            .map(|i| corner.x + i as f64 * step)
+            .collect();
+        // A flat series is drawn halfway up the rectangle
+        let y: Vec<f64> = values
+            .iter()
+            .map(|value| {
+                if max > min {
+                    corner.y + (value - min) / (max - min) * size.height
+                } else {
+                    corner.y + size.height / 2.0
+                }
+            })
+            .collect();
+
+        let style = self.style();
+        self.page_buffer.extend(b"q\n");
+        self.set_color(color)
+            .set_line_width(0.5)
+            .draw_line(x.iter().copied(), y.iter().copied())
+            .draw_dots_with_radius(
+                &[x[min_index], x[max_index]],
+                &[y[min_index], y[max_index]],
+                1.0,
+            );
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self
+    }
+
+    /// Fill the `true` cells of a grid of square modules, such as a QR code, in `color`
+    /// `modules` holds the rows from top to bottom, with the top-left corner of the grid at
+    /// `origin`. Adjacent modules in a row are merged into one rectangle, and all of them are
+    /// filled together. The current colors are unchanged afterwards.
+    pub fn draw_module_grid<X, Y>(
+        &mut self,
+        origin: Point<X, Y>,
+        module_size: f64,
+        modules: &[Vec<bool>],
+        color: Color,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let origin = origin.into_f64();
+        let mut runs = Vec::new();
+        for (r, row) in modules.iter().enumerate() {
+            let mut start = None;
+            for (c, &filled) in row.iter().chain(&[false]).enumerate() {
+                match (filled, start) {
+                    (true, None) => start = Some(c),
+                    (false, Some(first)) => {
+                        runs.push((r, first, c - first));
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if runs.is_empty() {
+            return self;
+        }
+
+        let style = self.style();
+        self.page_buffer.extend(b"q\n");
+        self.set_color(color);
+        for (r, c, len) in runs {
+            let corner = Point {
+                x: origin.x + c as f64 * module_size,
+                y: origin.y - (r + 1) as f64 * module_size,
+            };
+            let size = Size {
+                width: len as f64 * module_size,
+                height: module_size,
+            };
+            self.extend_bounds_rect(corner, size);
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                corner.x,
+                corner.y,
+                size.width,
+                size.height,
+                "re"
+            );
+        }
+        self.page_buffer.extend(b"f\nQ\n");
+        self.restore_style(style);
+        self
+    }
+
+    /// Fill a grid of cells with the color `colormap` gives for each value in `grid`
+    /// `grid` holds the rows from top to bottom, with the top-left corner of the grid at `origin`
+    /// and each cell `cell` in size. Adjacent cells in a row with the same color are merged into
+    /// one rectangle. The current colors are unchanged afterwards.
+    pub fn draw_heatmap<X, Y, W, H, F>(
+        &mut self,
+        origin: Point<X, Y>,
+        cell: Size<W, H>,
+        grid: &[Vec<f64>],
+        colormap: F,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+        F: Fn(f64) -> Color,
+    {
+        let origin = origin.into_f64();
+        let cell = cell.into_f64();
+        let mut runs = Vec::new();
+        for (r, row) in grid.iter().enumerate() {
+            let colors: Vec<Color> = row.iter().map(|&value| colormap(value)).collect();
+            let mut start = 0;
+            for c in 1..=colors.len() {
+                if c == colors.len() || colors[c] != colors[start] {
+                    runs.push((colors[start], r, start, c - start));
+                    start = c;
+                }
+            }
+        }
+        if runs.is_empty() {
+            return self;
+        }
+        // Group the runs by color, in the order each color first appears
+        let mut first_seen = HashMap::new();
+        for (i, run) in runs.iter().enumerate() {
+            first_seen.entry(run.0).or_insert(i);
+        }
+        runs.sort_by_key(|run| first_seen[&run.0]);
+
+        let style = self.style();
+        self.page_buffer.extend(b"q\n");
+        for (i, &(color, r, c, len)) in runs.iter().enumerate() {
+            if i == 0 || runs[i - 1].0 != color {
+                self.set_fill_color(color);
+            }
+            let corner = Point {
+                x: origin.x + c as f64 * cell.width,
+                y: origin.y - (r + 1) as f64 * cell.height,
+            };
+            let size = Size {
+                width: len as f64 * cell.width,
+                height: cell.height,
+            };
+            self.extend_bounds_rect(corner, size);
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                corner.x,
+                corner.y,
+                size.width,
+                size.height,
+                "re"
+            );
+            if runs.get(i + 1).map_or(true, |next| next.0 != color) {
+                self.page_buffer.extend(b"f\n");
+            }
+        }
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self
+    }
+
+    /// Draw a shaded rectangle in the current color with bottom-left corner at with bottom-left
+    /// corner at `corner` and dimensions `size`.
+    #[inline]
+    pub fn draw_rectangle<X, Y, W, H>(&mut self, corner: Point<X, Y>, size: Size<W, H>) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+
+        self.extend_bounds_rect(corner, size);
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            corner.x,
+            corner.y,
+            size.width,
+            size.height,
+            "re S" // Fill path using Nonzero Winding Number Rule
+        );
+        self
+    }
+
+    /// Outline a rectangle like `draw_rectangle`, with each corner rounded by its own radius
+    /// `radii` are `[top_left, top_right, bottom_right, bottom_left]`. A radius of zero leaves
+    /// that corner sharp, and each radius is clamped so it fits within both sides it touches.
+    pub fn draw_rectangle_rounded_corners<X, Y, W, H>(
+        &mut self,
+        corner: Point<X, Y>,
+        size: Size<W, H>,
+        radii: [f64; 4],
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        // Distance from each end of a quarter circle to its control point, for a unit circle
+        const K: f64 = 0.551_915_024_494;
+
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        let limit = size.width.min(size.height) / 2.0;
+        let [top_left, top_right, bottom_right, bottom_left] = radii.map(|r| r.clamp(0.0, limit));
+        let (left, bottom) = (corner.x, corner.y);
+        let (right, top) = (left + size.width, bottom + size.height);
+
+        self.move_to(Point {
+            x: left + top_left,
+            y: top,
+        });
+        self.line_to(Point {
+            x: right - top_right,
+            y: top,
+        });
+        if top_right > 0.0 {
+            let r = top_right;
+            self.curve_to(
+                (right - r + K * r, top),
+                (right, top - r + K * r),
+                (right, top - r),
+            );
+        }
+        self.line_to(Point {
+            x: right,
+            y: bottom + bottom_right,
+        });
+        if bottom_right > 0.0 {
+            let r = bottom_right;
+            self.curve_to(
+                (right, bottom + r - K * r),
+                (right - r + K * r, bottom),
+                (right - r, bottom),
+            );
+        }
+        self.line_to(Point {
+            x: left + bottom_left,
+            y: bottom,
+        });
+        if bottom_left > 0.0 {
+            let r = bottom_left;
+            self.curve_to(
+                (left + r - K * r, bottom),
+                (left, bottom + r - K * r),
+                (left, bottom + r),
+            );
+        }
+        self.line_to(Point {
+            x: left,
+            y: top - top_left,
+        });
+        if top_left > 0.0 {
+            let r = top_left;
+            self.curve_to(
+                (left, top - r + K * r),
+                (left + r - K * r, top),
+                (left + r, top),
+            );
+        }
+        self.page_buffer.extend(b"s\n"); // close and stroke
+        self
+    }
+
+    /// Draw a rectangle filled with one color and outlined with another in a single path
+    /// `stroke` is the outline's color and line width. The fill and stroke colors and the line
+    /// width stay set afterwards, and nothing is drawn if both are `None`.
+    pub fn draw_rectangle_styled<X, Y, W, H>(
+        &mut self,
+        corner: Point<X, Y>,
+        size: Size<W, H>,
+        fill: Option<Color>,
+        stroke: Option<(Color, f64)>,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let operator = match (fill, stroke) {
+            (None, None) => return self,
+            (Some(_), None) => "f",
+            (None, Some(_)) => "S",
+            (Some(_), Some(_)) => "B",
+        };
+        if let Some(color) = fill {
+            self.set_fill_color(color);
+        }
+        if let Some((color, width)) = stroke {
+            self.set_stroke_color(color);
+            self.set_line_width(width);
+        }
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        self.extend_bounds_rect(corner, size);
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            corner.x,
+            corner.y,
+            size.width,
+            size.height,
+            "re",
+            operator
+        );
+        self
+    }
+
+    /// Draw a rectangle like `draw_rectangle`, but between two opposite corners in either order
+    #[inline]
+    pub fn draw_rectangle_corners<X1, Y1, X2, Y2>(
+        &mut self,
+        a: Point<X1, Y1>,
+        b: Point<X2, Y2>,
+    ) -> &mut Self
+    where
+        X1: Into<f64>,
+        Y1: Into<f64>,
+        X2: Into<f64>,
+        Y2: Into<f64>,
+    {
+        let (corner, size) = util::corners_to_rect(a.into_f64(), b.into_f64());
+        self.draw_rectangle(corner, size)
+    }
+
+    /// Draw a rectangle like `draw_rectangle_filled`, but between two opposite corners in either
+    /// order
+    #[inline]
+    pub fn draw_rectangle_corners_filled<X1, Y1, X2, Y2>(
+        &mut self,
+        a: Point<X1, Y1>,
+        b: Point<X2, Y2>,
+    ) -> &mut Self
+    where
+        X1: Into<f64>,
+        Y1: Into<f64>,
+        X2: Into<f64>,
+        Y2: Into<f64>,
+    {
+        let (corner, size) = util::corners_to_rect(a.into_f64(), b.into_f64());
+        self.draw_rectangle_filled(corner, size)
+    }
+
+    /// Set the font for all subsequent drawing calls
+    #[inline]
+    pub fn font<N>(&mut self, font: Font, size: N) -> &mut Self
+    where
+        N: Into<f64>,
+    {
+        self.current_font_index = self.font_index(font);
+        self.font_size = size.into();
+        self
+    }
+
+    /// Set the font like `font`, but by its `PostScript` name such as `"Helvetica-Bold"`, for
+    /// fonts chosen in configuration files
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is not one of the 14 standard fonts.
+    #[inline]
+    pub fn set_font_str(&mut self, name: &str, size: f64) -> Result<&mut Self, UnknownFontError> {
+        let font = fonts::font_from_name(name).ok_or_else(|| UnknownFontError(name.to_string()))?;
+        Ok(self.font(font, size))
+    }
+
+    /// The index of `font` in the current page's resources, adding it if necessary
+    fn font_index(&mut self, font: Font) -> usize {
+        if let Some(index) = self.fonts.iter().position(|f| *f == font) {
+            index
+        } else {
+            self.fonts.push(font);
+            self.fonts.len() - 1
+        }
+    }
+
+    /// Draw characters which the current font doesn't have in `fallback` instead, such as
+    /// `Font::Symbol` for mathematical symbols and Greek letters
+    /// Each run of such characters is drawn in the fallback font, switching back to the current
+    /// font afterwards. Passing `None` turns this off.
+    #[inline]
+    pub fn set_font_fallback(&mut self, fallback: Option<Font>) -> &mut Self {
+        self.settings.fallback_font = fallback;
+        self
+    }
+
+    /// Mark characters which can't be drawn in the current font or the fallback font with a
+    /// small hollow box, to make them easy to spot
+    /// Each such character takes up the width of a space.
+    #[inline]
+    pub fn set_show_missing_glyphs(&mut self, show: bool) -> &mut Self {
+        self.settings.show_missing_glyphs = show;
+        self
+    }
+
+    /// Move the baseline of each line of horizontal text to the nearest line of a grid spaced
+    /// `spacing` apart and passing through `origin_y`, so text lines up across columns
+    /// A `spacing` of zero or less turns snapping off.
+    #[inline]
+    pub fn set_baseline_grid(&mut self, spacing: f64, origin_y: f64) -> &mut Self {
+        self.settings.baseline_grid = if spacing > 0.0 {
+            Some((spacing, origin_y))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// The baseline grid line closest to `y`, or `y` if there is no grid
+    fn snap_baseline(&self, y: f64) -> f64 {
+        match self.settings.baseline_grid {
+            Some((spacing, origin)) => origin + ((y - origin) / spacing).round() * spacing,
+            None => y,
+        }
+    }
+
+    /// The baseline grid line for a line of text requested at `y`, given the requested and
+    /// snapped baselines of the line before it, if any
+    /// The first line goes to the closest grid line. Each later line goes to the first grid line
+    /// at least one line spacing below the line before it, so that no two lines share one.
+    fn snap_line_baseline(&self, y: f64, previous: Option<(f64, f64)>) -> f64 {
+        match (self.settings.baseline_grid, previous) {
+            (Some((spacing, origin)), Some((previous_y, previous_snapped))) => {
+                let target = previous_snapped - (previous_y - y);
+                // Allow for rounding error when the target is already on a grid line
+                origin + ((target - origin) / spacing + 1e-9).floor() * spacing
+            }
+            _ => self.snap_baseline(y),
+        }
+    }
+
+    /// Choose whether text is filled, stroked, both, or not painted at all, until the end of the
+    /// page
+    #[inline]
+    pub fn set_text_render_mode(&mut self, mode: TextRenderMode) -> &mut Self {
+        self.page_buffer
+            .extend(format!("{} Tr\n", mode as u8).bytes());
+        self
+    }
+
+    /// Set the width of the outlines of stroked text separately from the line width used for
+    /// everything else
+    /// With `None`, text is stroked with the current line width.
+    #[inline]
+    pub fn set_text_outline_width(&mut self, width: Option<f64>) -> &mut Self {
+        self.settings.text_outline_width = width;
+        self
+    }
+
+    /// Start a text object, switching to the text outline width if there is one
+    fn begin_text(&mut self) {
+        self.page_buffer.extend(b"BT\n");
+        if let Some(width) = self.settings.text_outline_width {
+            ryu!(self.page_buffer, self.settings.precision, width, "w");
+        }
+    }
+
+    /// End a text object, putting back the line width if `begin_text` changed it
+    fn end_text(&mut self) {
+        self.page_buffer.extend(b"ET\n");
+        if self.settings.text_outline_width.is_some() {
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                self.line_width,
+                "w"
+            );
+        }
+    }
+
+    /// Whether `c` should be drawn as a box because neither `font` nor the fallback has it
+    fn is_missing_glyph(&self, font: &Font, c: char) -> bool {
+        self.settings.show_missing_glyphs
+            && fonts::glyph_width(font, c) == 0.0
+            && !self.needs_fallback(font, c)
+    }
+
+    /// Whether `c` should be drawn in the fallback font instead of `font`
+    fn needs_fallback(&self, font: &Font, c: char) -> bool {
+        self.settings
+            .fallback_font
+            .as_ref()
+            .is_some_and(|fallback| {
+                fonts::glyph_width(font, c) == 0.0 && fonts::glyph_width(fallback, c) > 0.0
+            })
+    }
+
+    /// Set the font used for text drawn before any call to `font`, on this and every later page
+    /// This should be called before any text is drawn on the current page.
+    #[inline]
+    pub fn set_default_font<N>(&mut self, font: Font, size: N) -> &mut Self
+    where
+        N: Into<f64>,
+    {
+        // Text already on the page refers to the other fonts by index, so this may leave
+        // `font` in the resources twice rather than removing an entry
+        self.fonts[0] = font;
+        self.current_font_index = 0;
+        self.font_size = size.into();
+        self
+    }
+
+    /// Draw and measure "ff", "fi", "fl", "ffi", and "ffl" as single ligature glyphs in fonts
+    /// which have them
+    /// Of the builtin fonts, only "fi" and "fl" are available, and not in `Font::Symbol` or
+    /// `Font::ZapfDingbats`. Text extracted from the document still reads as separate letters.
+    #[inline]
+    pub fn enable_ligatures(&mut self, enable: bool) -> &mut Self {
+        self.settings.ligatures = enable;
+        self
+    }
+
+    /// Replace letter sequences in `text` with the ligatures that `font` has glyphs for, if
+    /// ligatures are enabled
+    fn apply_ligatures<'t>(&self, font: &Font, text: &'t str) -> Cow<'t, str> {
+        if !self.settings.ligatures {
+            return Cow::Borrowed(text);
+        }
+        let mut text = Cow::Borrowed(text);
+        for (letters, ligature, _) in encoding::LIGATURES {
+            if text.contains(letters) && fonts::glyph_width(font, ligature) > 0.0 {
+                text = Cow::Owned(text.replace(letters, ligature.encode_utf8(&mut [0; 4])));
+            }
+        }
+        text
+    }
+
+    /// Convienence method to figure out the width of a string
+    /// May be required for some users to position text properly
+    #[must_use]
+    pub fn width_of(&self, text: &str) -> f64 {
+        let current_font = &self.fonts[self.current_font_index];
+        self.apply_ligatures(current_font, text)
+            .chars()
+            .filter(|c| *c != '\n')
+            .map(|c| match &self.settings.fallback_font {
+                Some(fallback) if self.needs_fallback(current_font, c) => {
+                    fonts::glyph_width(fallback, c)
+                }
+                _ if self.is_missing_glyph(current_font, c) => {
+                    fonts::glyph_width(current_font, ' ')
+                }
+                _ => fonts::glyph_width(current_font, c),
+            })
+            .sum::<f64>()
+            * self.font_size
+            * self.horizontal_scale
+            / 100.0
+    }
+
+    /// How far the current font rises above the baseline at the current size, for lining up
+    /// other drawing with the top of the text
+    #[inline]
+    #[must_use]
+    pub fn font_ascent(&self) -> f64 {
+        fonts::font_metrics(&self.fonts[self.current_font_index]).ascender * self.font_size
+    }
+
+    /// How far the current font drops below the baseline at the current size, which is negative
+    #[inline]
+    #[must_use]
+    pub fn font_descent(&self) -> f64 {
+        fonts::font_metrics(&self.fonts[self.current_font_index]).descender * self.font_size
+    }
+
+    /// Embed the TrueType font in `data` in the document, for drawing text which the standard
+    /// fonts can't, with `draw_text_embedded`
+    /// The whole font program is written if any finished page uses the font.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a TrueType font. `OpenType` fonts with CFF outlines are
+    /// not supported.
+    pub fn embed_font(&mut self, data: Vec<u8>) -> Result<EmbeddedFont, InvalidFontError> {
+        self.add_embedded_font(data, false)
+    }
+
+    /// Embed the TrueType font in `data` like `embed_font`, but only write the glyphs drawn on
+    /// finished pages, which usually makes the file far smaller
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a TrueType font. `OpenType` fonts with CFF outlines are
+    /// not supported.
+    pub fn embed_font_subset(&mut self, data: Vec<u8>) -> Result<EmbeddedFont, InvalidFontError> {
+        self.add_embedded_font(data, true)
+    }
+
+    fn add_embedded_font(
+        &mut self,
+        data: Vec<u8>,
+        subset: bool,
+    ) -> Result<EmbeddedFont, InvalidFontError> {
+        let font = truetype::TrueTypeFont::parse(data)?;
+        self.embedded_fonts.push(EmbeddedFontData {
+            font: std::sync::Arc::new(font),
+            subset,
+            used: BTreeMap::new(),
+            ids: Vec::new(),
+        });
+        Ok(EmbeddedFont(self.embedded_fonts.len() - 1))
+    }
+
+    /// Draw `text` as one line in the embedded `font` at `size`, starting at `position` on the
+    /// baseline
+    /// Characters the font has no glyph for are drawn as its `.notdef` glyph. Ligatures, the
+    /// fallback font, and missing glyph boxes only apply to the standard fonts.
+    pub fn draw_text_embedded<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        font: EmbeddedFont,
+        size: f64,
+        text: &str,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        use fmt::Write;
+
+        let x = position.x.into();
+        let y = self.snap_baseline(position.y.into());
+        let width = self.width_of_embedded(font, size, text);
+        self.extend_bounds_rect(
+            Point { x, y },
+            Size {
+                width,
+                height: size,
+            },
+        );
+
+        let embedded = &self.embedded_fonts[font.0].font;
+        let glyphs = self.page_embedded_fonts.entry(font.0).or_default();
+        let mut shown = String::with_capacity(4 * text.len() + 2);
+        shown.push('<');
+        for c in text.chars() {
+            let glyph = embedded.glyph_id(c);
+            glyphs.entry(glyph).or_insert(c);
+            let _ = write!(shown, "{glyph:04X}");
+        }
+        shown.push('>');
+
+        self.begin_text();
+        self.page_buffer
+            .extend(format!("/E{} {} Tf\n", font.0, size).bytes());
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            1.,
+            0.,
+            0.,
+            1.,
+            x,
+            y
+        );
+        self.page_buffer.extend_from_slice(b"Tm ");
+        self.page_buffer.extend(shown.bytes());
+        self.page_buffer.extend(b" Tj\n");
+        self.end_text();
+        self
+    }
+
+    /// The width of `text` drawn with `draw_text_embedded` in `font` at `size`
+    #[must_use]
+    pub fn width_of_embedded(&self, font: EmbeddedFont, size: f64, text: &str) -> f64 {
+        let font = &self.embedded_fonts[font.0].font;
+        text.chars()
+            .map(|c| font.advance(font.glyph_id(c)))
+            .sum::<f64>()
+            / 1000.0
+            * size
+            * self.horizontal_scale
+            / 100.0
+    }
+
+    /// Stretch or compress all subsequent text horizontally, as a percentage of its normal width
+    /// The default is 100, and `width_of` and text alignment take the scale into account.
+    #[inline]
+    pub fn set_horizontal_scale(&mut self, percent: f64) -> &mut Self {
+        self.horizontal_scale = percent;
+        ryu!(self.page_buffer, self.settings.precision, percent, "Tz");
+        self
+    }
+
+    /// Draw text at a given location with the current settings
+    #[inline]
+    pub fn draw_text<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        alignment: Alignment,
+        text: &str,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let text = util::normalize_newlines(text);
+        let x = position.x.into();
+        let y = position.y.into();
+        let height = self.font_size;
+
+        self.begin_text();
+        self.page_buffer
+            .extend(format!("/F{} {} Tf\n", self.current_font_index, self.font_size).bytes());
+
+        let num_lines = text.split('\n').count() as f64;
+        let mut missing = Vec::new();
+        let mut previous = None;
+        for (l, line) in text.split('\n').enumerate() {
+            let line_width = self.width_of(line);
+            let l = l as f64;
+
+            let (line_x, line_y) = line_origin((x, y), alignment, line_width, height, l, num_lines);
+            let snapped_y = self.snap_line_baseline(line_y, previous);
+            previous = Some((line_y, snapped_y));
+            let line_y = snapped_y;
+
+            self.extend_bounds_rect(
+                Point {
+                    x: line_x,
+                    y: line_y,
+                },
+                Size {
+                    width: line_width,
+                    height,
+                },
+            );
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                1.,
+                0.,
+                0.,
+                1.,
+                line_x,
+                line_y
+            );
+            self.page_buffer.extend_from_slice(b"Tm ");
+            if self.settings.show_missing_glyphs {
+                let font = self.fonts[self.current_font_index].clone();
+                let mut shown = String::with_capacity(line.len());
+                for c in line.chars() {
+                    if self.is_missing_glyph(&font, c) {
+                        missing.push((line_x + self.width_of(&shown), line_y));
+                        shown.push(' ');
+                    } else {
+                        shown.push(c);
+                    }
+                }
+                self.write_text_with_fallback(&shown);
+            } else {
+                self.write_text_with_fallback(line);
+            }
+        }
+        self.end_text();
+
+        // Paths can't be drawn inside a text object, so the boxes come afterwards
+        let space = self.width_of(" ");
+        for (x, y) in missing {
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                x + space * 0.15,
+                y,
+                space * 0.7,
+                height * 0.7,
+                "re S"
+            );
+        }
+        self
+    }
+
+    /// Draw `text` with every line horizontally centered on `center`, and the lines' baselines
+    /// evenly spaced `1.25` times the font size apart around `center`
+    /// With an odd number of lines the middle line's baseline is at `center`.
+    pub fn draw_text_centered_block<X, Y>(&mut self, center: Point<X, Y>, text: &str) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let text = util::normalize_newlines(text);
+        let center = center.into_f64();
+        let leading = 1.25 * self.font_size;
+        let num_lines = text.split('\n').count() as f64;
+        for (l, line) in text.split('\n').enumerate() {
+            let baseline = center.y + ((num_lines - 1.0) / 2.0 - l as f64) * leading;
+            self.draw_text(
+                Point {
+                    x: center.x,
+                    y: baseline,
+                },
+                Alignment::BottomCenter,
+                line,
+            );
+        }
+        self
+    }
+
+    /// Draw one line of right-to-left text, with the first character ending at `position` and
+    /// each following character placed to the left of the one before
+    /// Only the order of the glyphs is changed; no shaping is done.
+    pub fn draw_text_rtl<X, Y>(&mut self, position: Point<X, Y>, text: &str) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        let position = Point {
+            x: position.x,
+            y: self.snap_baseline(position.y),
+        };
+        self.begin_text();
+        self.page_buffer
+            .extend(format!("/F{} ", self.current_font_index).bytes());
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            self.font_size,
+            "Tf"
+        );
+        let mut x = position.x;
+        for c in text.chars() {
+            let mut buf = [0; 4];
+            let glyph = c.encode_utf8(&mut buf);
+            x -= self.width_of(glyph);
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                1.,
+                0.,
+                0.,
+                1.,
+                x,
+                position.y,
+                "Tm"
+            );
+            self.write_text_with_fallback(glyph);
+        }
+        self.extend_bounds_rect(
+            Point { x, y: position.y },
+            Size {
+                width: position.x - x,
+                height: self.font_size,
+            },
+        );
+        self.end_text();
+        self
+    }
+
+    /// Draw one line of text with its baseline starting at `position`, changing its color, size,
+    /// or font partway through with tags such as `"plain {color:#f00}red{/} plain"`
+    /// The tags are `{color:#rgb}` or `{color:#rrggbb}`, `{size:N}`, and `{font:Name}` with a
+    /// standard font name such as `Times-Bold`, and each lasts until the next unmatched `{/}`.
+    /// Anything else in braces is drawn as it is written. The style is the same afterwards as
+    /// before.
+    pub fn draw_markup<X, Y>(&mut self, position: Point<X, Y>, markup: &str) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let mut position = position.into_f64();
+        let style = self.style();
+        let font = (self.current_font_index, self.font_size);
+        // The fill color, font, and size to go back to at each `{/}`
+        let mut stack = Vec::new();
+        for token in markup::parse(markup) {
+            match token {
+                markup::Token::Text(text) => position = self.draw_text_run(position, text),
+                markup::Token::Open(change) => {
+                    stack.push((self.fill_color, self.current_font_index, self.font_size));
+                    match change {
+                        markup::Change::Color(color) => {
+                            self.set_fill_color(color);
+                        }
+                        markup::Change::Size(size) => self.font_size = size,
+                        markup::Change::Font(font) => {
+                            self.current_font_index = self.font_index(font);
+                        }
+                    }
+                }
+                markup::Token::Close => {
+                    // The parser only closes tags which were opened
+                    if let Some((color, font_index, size)) = stack.pop() {
+                        if color != self.fill_color {
+                            self.set_fill_color(color);
+                        }
+                        self.current_font_index = font_index;
+                        self.font_size = size;
+                    }
+                }
+            }
+        }
+        if style.1 != self.fill_color {
+            self.set_fill_color(style.1);
+        }
+        self.restore_style(style);
+        (self.current_font_index, self.font_size) = font;
+        self
+    }
+
+    /// Draw `text` in `color`, leaving the current fill color unchanged afterwards
+    pub fn draw_text_colored<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        alignment: Alignment,
+        color: Color,
+        text: &str,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let style = self.style();
+        self.page_buffer.extend(b"q\n");
+        self.set_fill_color(color);
+        self.draw_text(position, alignment, text);
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self
+    }
+
+    /// Write `text` as a string literal followed by `Tj`
+    fn write_text_string(&mut self, text: &str) {
+        let font = &self.fonts[self.current_font_index];
+        let codes: Vec<u32> = self
+            .apply_ligatures(font, text)
+            .chars()
+            .map(|c| {
+                fonts::builtin_code(font, c)
+                    .or_else(|| encoding::ligature_code(c))
+                    .or_else(|| encoding::unicode_to_win_ansi(c))
+                    .map_or(c as u32, u32::from)
+            })
+            .collect();
+        self.page_buffer.push(b'(');
+        for code in codes {
+            let data = format!("\\{code:o}");
+            self.page_buffer.extend(data.bytes());
+        }
+        self.page_buffer.extend(b") Tj\n");
+    }
+
+    /// Write `text` like `write_text_string`, drawing runs of characters that the current font
+    /// lacks in the fallback font
+    fn write_text_with_fallback(&mut self, text: &str) {
+        let fallback = match &self.settings.fallback_font {
+            Some(fallback) => fallback.clone(),
+            None => return self.write_text_string(text),
+        };
+        let current_font_index = self.current_font_index;
+        let current_font = self.fonts[current_font_index].clone();
+
+        let mut rest = text;
+        while let Some(first) = rest.chars().next() {
+            let in_fallback = self.needs_fallback(&current_font, first);
+            let end = rest
+                .char_indices()
+                .find(|&(_, c)| self.needs_fallback(&current_font, c) != in_fallback)
+                .map_or(rest.len(), |(i, _)| i);
+            let (run, tail) = rest.split_at(end);
+            if in_fallback {
+                self.current_font_index = self.font_index(fallback.clone());
+                self.page_buffer.extend(
+                    format!("/F{} {} Tf\n", self.current_font_index, self.font_size).bytes(),
+                );
+                self.write_text_string(run);
+                self.current_font_index = current_font_index;
+                self.page_buffer.extend(
+                    format!("/F{} {} Tf\n", self.current_font_index, self.font_size).bytes(),
+                );
+            } else {
+                self.write_text_string(run);
+            }
+            rest = tail;
+        }
+    }
+
+    /// The width of already-encoded bytes drawn with `draw_raw_text`
+    /// Bytes are measured as the `WinAnsi` characters and ligatures they draw in the builtin
+    /// fonts, so text for `Symbol` or `ZapfDingbats` may be measured wrongly.
+    fn width_of_raw(&self, bytes: &[u8]) -> f64 {
+        let font = &self.fonts[self.current_font_index];
+        bytes
+            .iter()
+            .filter_map(|&code| {
+                encoding::LIGATURES
+                    .iter()
+                    .find(|&&(_, _, ligature)| ligature == code)
+                    .map(|&(_, c, _)| c)
+                    .or_else(|| encoding::win_ansi_to_unicode(code))
+            })
+            .map(|c| fonts::glyph_width(font, c))
+            .sum::<f64>()
+            * self.font_size
+            * self.horizontal_scale
+            / 100.0
+    }
+
+    /// Draw already-encoded bytes as one line of text with its baseline starting at `position`
+    /// The bytes are written as-is into a PDF string, escaping only `(`, `)`, and `\\`, so they
+    /// must already be in the font's encoding.
+    pub fn draw_raw_text<X, Y>(&mut self, position: Point<X, Y>, bytes: &[u8]) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        let position = Point {
+            x: position.x,
+            y: self.snap_baseline(position.y),
+        };
+        let size = Size {
+            width: self.width_of_raw(bytes),
+            height: self.font_size,
+        };
+        self.extend_bounds_rect(position, size);
+        self.begin_text();
+        self.page_buffer
+            .extend(format!("/F{} ", self.current_font_index).bytes());
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            self.font_size,
+            "Tf"
+        );
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            1.,
+            0.,
+            0.,
+            1.,
+            position.x,
+            position.y,
+            "Tm"
+        );
+        self.page_buffer.push(b'(');
+        for &b in bytes {
+            if matches!(b, b'(' | b')' | b'\\') {
+                self.page_buffer.push(b'\\');
+            }
+            self.page_buffer.push(b);
+        }
+        self.page_buffer.extend(b") Tj\n");
+        self.end_text();
+        self
+    }
+
+    /// Draw text with its characters stacked top to bottom, starting below `position`
+    /// Each character is centered on the column and advances down by the font size. Each line of
+    /// `text` starts a new column to the left of the previous one.
+    pub fn draw_text_vertical<X, Y>(&mut self, position: Point<X, Y>, text: &str) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let text = util::normalize_newlines(text);
+        let position = position.into_f64();
+        let size = self.font_size;
+        self.begin_text();
+        self.page_buffer
+            .extend(format!("/F{} ", self.current_font_index).bytes());
+        ryu!(self.page_buffer, self.settings.precision, size, "Tf");
+        for (column, line) in text.split('\n').enumerate() {
+            let x = position.x - column as f64 * size * 1.25;
+            for (row, c) in line.chars().enumerate() {
+                let width = self.width_of(c.encode_utf8(&mut [0; 4]));
+                let y = position.y - (row + 1) as f64 * size;
+                self.extend_bounds_rect(
+                    Point {
+                        x: x - width / 2.0,
+                        y,
+                    },
+                    Size {
+                        width,
+                        height: size,
+                    },
+                );
+                ryu!(
+                    self.page_buffer,
+                    self.settings.precision,
+                    1.,
+                    0.,
+                    0.,
+                    1.,
+                    x - width / 2.0,
+                    y,
+                    "Tm"
+                );
+                let mut buf = [0; 4];
+                self.write_text_string(c.encode_utf8(&mut buf));
+            }
+        }
+        self.end_text();
+        self
+    }
+
+    /// Draw `text` large, diagonally across the center of the page in the current font
+    /// The text is drawn in `color` with opacity `alpha`, and the graphics state is restored
+    /// afterwards so later drawing is unaffected.
+    pub fn draw_watermark(&mut self, text: &str, color: Color, alpha: f64) -> &mut Self {
+        let unit_width = self.width_of(text) / self.font_size;
+        if unit_width <= 0.0 {
+            return self;
+        }
+        let angle = self.height.atan2(self.width);
+        let (sin, cos) = angle.sin_cos();
+        let size = 0.8 * self.width.hypot(self.height) / unit_width;
+        let half_width = unit_width * size / 2.0;
+        // Start half the text's width back along the diagonal, and drop by a third of the size so
+        // the text is centered vertically as well
+        let x = self.width / 2.0 - half_width * cos + sin * size / 3.0;
+        let y = self.height / 2.0 - half_width * sin - cos * size / 3.0;
+
+        let style = self.style();
+        let previous_alpha = self.alpha;
+        self.page_buffer.extend(b"q\n");
+        self.set_alpha(alpha);
+        self.set_color(color);
+        self.begin_text();
+        self.page_buffer
+            .extend(format!("/F{} ", self.current_font_index).bytes());
+        ryu!(self.page_buffer, self.settings.precision, size, "Tf");
+        ryu!(
+            self.page_buffer,
+            self.settings.precision,
+            cos,
+            sin,
+            -sin,
+            cos,
+            x,
+            y,
+            "Tm"
+        );
+        self.write_text_string(text);
+        self.end_text();
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+        self.alpha = previous_alpha;
+        self
+    }
+
+    /// Choose whether `wrap_text` may break words at soft hyphens (U+00AD)
+    /// When a word doesn't fit at the end of a line it is broken at the last soft hyphen that
+    /// fits, and a visible hyphen is drawn there. Otherwise lines only break at spaces. Soft
+    /// hyphens are never drawn anywhere else.
+    #[inline]
+    pub fn set_word_wrap_hyphenation(&mut self, hyphenate: bool) -> &mut Self {
+        self.settings.hyphenate = hyphenate;
+        self
+    }
+
+    /// Split `text` into lines no wider than `max_width` in the current font, breaking at spaces
+    /// Newlines in `text` always start a new line, and a single word that is too wide is put on a
+    /// line of its own.
+    #[must_use]
+    pub fn wrap_text(&self, text: &str, max_width: f64) -> Vec<String> {
+        const SOFT_HYPHEN: char = '\u{AD}';
+        let visible = |word: &str| word.replace(SOFT_HYPHEN, "");
+
+        let mut lines = Vec::new();
+        for paragraph in util::normalize_newlines(text).split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let mut word = word.to_string();
+                loop {
+                    let candidate = if line.is_empty() {
+                        visible(&word)
+                    } else {
+                        format!("{line} {}", visible(&word))
+                    };
+                    if self.width_of(&candidate) <= max_width {
+                        line = candidate;
+                        break;
+                    }
+                    // Take as much of the word as fits before a soft hyphen
+                    let prefix = line.clone() + if line.is_empty() { "" } else { " " };
+                    let split = if self.settings.hyphenate {
+                        word.match_indices(SOFT_HYPHEN).rev().find(|&(i, _)| {
+                            let head = format!("{prefix}{}-", visible(&word[..i]));
+                            self.width_of(&head) <= max_width
+                        })
+                    } else {
+                        None
+                    };
+                    if let Some((i, _)) = split {
+                        lines.push(format!("{prefix}{}-", visible(&word[..i])));
+                        word = word[i + SOFT_HYPHEN.len_utf8()..].to_string();
+                        line.clear();
+                    } else if line.is_empty() {
+                        line = visible(&word);
+                        break;
+                    } else {
+                        lines.push(std::mem::take(&mut line));
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Draw `text` wrapped to lines no wider than `max_width`, as with `wrap_text`
+    pub fn draw_paragraph<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        alignment: Alignment,
+        text: &str,
+        max_width: f64,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let lines = self.wrap_text(text, max_width);
+        self.draw_text(position, alignment, &lines.join("\n"))
+    }
+
+    /// Draw rows of tab-separated fields, with each field starting at the matching offset from
+    /// `origin` in `tab_stops`
+    /// The first row hangs down from `origin` like `Alignment::TopLeft`, and each row is one line
+    /// below the last. Fields past the last tab stop are drawn after it, separated by spaces.
+    pub fn draw_tabular<X, Y>(
+        &mut self,
+        origin: Point<X, Y>,
+        tab_stops: &[f64],
+        rows: &[&str],
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let origin = origin.into_f64();
+        let last_stop = tab_stops.len().saturating_sub(1);
+        for (r, row) in rows.iter().enumerate() {
+            let y = origin.y - r as f64 * self.font_size;
+            let mut fields = row.split('\t');
+            for (i, field) in fields.by_ref().take(last_stop).enumerate() {
+                if !field.is_empty() {
+                    let x = origin.x + tab_stops[i];
+                    self.draw_text(Point { x, y }, Alignment::TopLeft, field);
+                }
+            }
+            let rest = fields.collect::<Vec<_>>().join(" ");
+            if !rest.is_empty() {
+                let x = origin.x + tab_stops.get(last_stop).copied().unwrap_or(0.0);
+                self.draw_text(Point { x, y }, Alignment::TopLeft, &rest);
+            }
+        }
+        self
+    }
+
+    /// Draw `text` wrapped like `draw_paragraph`, but with no more than `max_lines` lines
+    /// If the text is too long, the last line drawn ends with an ellipsis, and `true` is returned.
+    pub fn draw_text_clamped<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        alignment: Alignment,
+        text: &str,
+        max_width: f64,
+        max_lines: usize,
+    ) -> bool
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let mut lines = self.wrap_text(text, max_width);
+        let truncated = lines.len() > max_lines;
+        if truncated {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                // Trim the line until it has room for the ellipsis
+                while !last.is_empty() && self.width_of(&format!("{last}…")) > max_width {
+                    last.pop();
+                }
+                last.truncate(last.trim_end().len());
+                last.push('…');
+            }
+        }
+        if !lines.is_empty() {
+            self.draw_text(position, alignment, &lines.join("\n"));
+        }
+        truncated
+    }
+
+    /// Draw `text` in the largest font size up to `max_size` that fits inside the rectangle
+    /// The text is placed against the side or corner of the rectangle given by `alignment`, and
+    /// the chosen size is returned. The current font size is unchanged afterwards.
+    pub fn draw_text_fitted<X, Y, W, H>(
+        &mut self,
+        (corner, size): (Point<X, Y>, Size<W, H>),
+        alignment: Alignment,
+        text: &str,
+        max_size: f64,
+    ) -> f64
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let text = util::normalize_newlines(text);
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        let previous_size = self.font_size;
+
+        // Text width is proportional to the font size, so the widest line at size 1 determines
+        // the largest size that fits across
+        self.font_size = 1.0;
+        let unit_width = text
+            .split('\n')
+            .map(|line| self.width_of(line))
+            .fold(0.0, f64::max);
+        let num_lines = text.split('\n').count() as f64;
+        let unit_height = 1.25 * (num_lines - 1.0) + 1.0;
+        let mut fitted = max_size.min(size.height / unit_height);
+        if unit_width > 0.0 {
+            fitted = fitted.min(size.width / unit_width);
+        }
+        self.font_size = fitted;
+
+        let (left, center, right) = (corner.x, corner.x + size.width / 2.0, corner.x + size.width);
+        let (bottom, middle, top) = (
+            corner.y,
+            corner.y + size.height / 2.0,
+            corner.y + size.height,
+        );
+        let anchor = match alignment {
+            Alignment::TopLeft => (left, top),
+            Alignment::TopCenter => (center, top),
+            Alignment::TopRight => (right, top),
+            Alignment::CenterLeft => (left, middle),
+            Alignment::CenterCenter => (center, middle),
+            Alignment::CenterRight => (right, middle),
+            Alignment::BottomLeft => (left, bottom),
+            Alignment::BottomCenter => (center, bottom),
+            Alignment::BottomRight => (right, bottom),
+        };
+        self.draw_text(anchor.into(), alignment, &text);
+
+        self.font_size = previous_size;
+        fitted
+    }
+
+    /// Draw a single line of left-aligned text with its baseline at `position`
+    /// Returns the baseline position just after the last glyph, which is where a following run
+    /// should start to continue the line, possibly in a different color or font.
+    #[inline]
+    pub fn draw_text_run<X, Y>(&mut self, position: Point<X, Y>, text: &str) -> Point<f64, f64>
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        self.draw_text(position, Alignment::BottomLeft, text);
+        let text = util::normalize_newlines(text);
+        let last_line = text.rsplit('\n').next().unwrap_or_default();
+        Point {
+            x: position.x + self.width_of(last_line),
+            y: position.y,
+        }
+    }
+
+    /// Draw text like `draw_text`, with a line under or through each line of it in the fill color
+    /// The line is placed and sized from the current font's metrics, so it sits in the same
+    /// place relative to the letters at any font size.
+    pub fn draw_text_decorated<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        alignment: Alignment,
+        text: &str,
+        decoration: TextDecoration,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        self.draw_text(position, alignment, text);
+
+        let metrics = fonts::font_metrics(&self.fonts[self.current_font_index]);
+        let height = self.font_size;
+        let offset = match decoration {
+            TextDecoration::Underline => metrics.underline_position,
+            // Fonts without lowercase letters are struck through at about the same place
+            TextDecoration::StrikeThrough => metrics.x_height.map_or(0.25, |x| x / 2.0),
+        } * height;
+        let thickness = metrics.underline_thickness * height;
+
+        let text = util::normalize_newlines(text);
+        let num_lines = text.split('\n').count() as f64;
+        let mut previous = None;
+        for (l, line) in text.split('\n').enumerate() {
+            let line_width = self.width_of(line);
+            let (line_x, line_y) = line_origin(
+                (position.x, position.y),
+                alignment,
+                line_width,
+                height,
+                l as f64,
+                num_lines,
+            );
+            let snapped_y = self.snap_line_baseline(line_y, previous);
+            previous = Some((line_y, snapped_y));
+            let line_y = snapped_y;
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                line_x,
+                line_y + offset - thickness / 2.0,
+                line_width,
+                thickness,
+                "re f"
+            );
+        }
+        self
+    }
+
+    /// Draw one line of text with its baseline starting at `position`, spread out to exactly
+    /// `width`
+    /// The extra space goes between words, unless that would widen each space by more than the
+    /// font size, such as for a line of few or no spaces. Then it is spread evenly between all
+    /// of the characters instead.
+    pub fn draw_text_justified<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        text: &str,
+        width: f64,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let slack = width - self.width_of(text);
+        let spaces = text.chars().filter(|&c| c == ' ').count();
+        let gaps = text.chars().count().saturating_sub(1);
+        // Spacing is applied before horizontal scaling
+        let scale = self.horizontal_scale / 100.0;
+        let operator = if spaces > 0 && slack / spaces as f64 <= self.font_size {
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                slack / spaces as f64 / scale,
+                "Tw"
+            );
+            "Tw"
+        } else if gaps > 0 {
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                slack / gaps as f64 / scale,
+                "Tc"
+            );
+            "Tc"
+        } else {
+            return self.draw_text(position, Alignment::BottomLeft, text);
+        };
+        self.draw_text(position, Alignment::BottomLeft, text);
+        ryu!(self.page_buffer, self.settings.precision, 0., operator);
+        self
+    }
+
+    /// Draw text like `draw_text`, but in small capitals
+    /// Lowercase letters are drawn as capitals at 80% of the font size, and lines are aligned by
+    /// their width with the smaller letters.
+    pub fn draw_text_smallcaps<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        alignment: Alignment,
+        text: &str,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        const SMALL_CAPS_SCALE: f64 = 0.8;
+
+        let text = util::normalize_newlines(text);
+        let position = position.into_f64();
+        let height = self.font_size;
+        let sizes = [height, height * SMALL_CAPS_SCALE];
+
+        self.begin_text();
+        self.page_buffer
+            .extend(format!("/F{} {} Tf\n", self.current_font_index, height).bytes());
+        let mut current_size = height;
+
+        let num_lines = text.split('\n').count() as f64;
+        let mut previous = None;
+        for (l, line) in text.split('\n').enumerate() {
+            // Runs of letters drawn at full size and at the small size
+            let mut runs: Vec<(bool, String)> = Vec::new();
+            for c in line.chars() {
+                let small = c.is_lowercase();
+                match runs.last_mut() {
+                    Some((run_small, run)) if *run_small == small => run.extend(c.to_uppercase()),
+                    _ => runs.push((small, c.to_uppercase().collect())),
+                }
+            }
+            let line_width: f64 = runs
+                .iter()
+                .map(|(small, run)| {
+                    self.font_size = sizes[usize::from(*small)];
+                    self.width_of(run)
+                })
+                .sum();
+            self.font_size = height;
+
+            let (line_x, line_y) = line_origin(
+                (position.x, position.y),
+                alignment,
+                line_width,
+                height,
+                l as f64,
+                num_lines,
+            );
+            let snapped_y = self.snap_line_baseline(line_y, previous);
+            previous = Some((line_y, snapped_y));
+            let line_y = snapped_y;
+            self.extend_bounds_rect(
+                Point {
+                    x: line_x,
+                    y: line_y,
+                },
+                Size {
+                    width: line_width,
+                    height,
+                },
+            );
+            ryu!(
+                self.page_buffer,
+                self.settings.precision,
+                1.,
+                0.,
+                0.,
+                1.,
+                line_x,
+                line_y,
+                "Tm"
+            );
+            for (small, run) in &runs {
+                self.font_size = sizes[usize::from(*small)];
+                #[allow(clippy::float_cmp)]
+                if self.font_size != current_size {
+                    current_size = self.font_size;
+                    self.page_buffer.extend(
+                        format!("/F{} {} Tf\n", self.current_font_index, current_size).bytes(),
+                    );
+                }
+                self.write_text_with_fallback(run);
+            }
+            self.font_size = height;
+        }
+        self.end_text();
+        self
+    }
+
+    /// Set how far `draw_footer` keeps its left and right text from the edges of the page, which
+    /// is 36 (half an inch) by default
+    #[inline]
+    pub fn set_footer_margin(&mut self, margin: f64) -> &mut Self {
+        self.settings.footer_margin = margin;
+        self
+    }
+
+    /// Draw a line of text with its baseline at `y` in three parts, such as a title and page
+    /// number at the bottom of each page
+    /// `left` starts at the left margin, `center` is centered on the page, and `right` ends at
+    /// the right margin. Empty parts are skipped.
+    pub fn draw_footer(&mut self, y: f64, left: &str, center: &str, right: &str) -> &mut Self {
+        let (x, width) = (self.origin.0, self.width);
+        let parts = [
+            (x + self.settings.footer_margin, Alignment::BottomLeft, left),
+            (x + width / 2.0, Alignment::BottomCenter, center),
+            (
+                x + width - self.settings.footer_margin,
+                Alignment::BottomRight,
+                right,
+            ),
+        ];
+        for (x, alignment, text) in parts {
+            if !text.is_empty() {
+                self.draw_text(Point { x, y }, alignment, text);
+            }
+        }
+        self
+    }
+
+    /// Draw a single line of left-aligned text with its baseline at `position` like
+    /// `draw_text_run`, returning how far from `position` each character starts
+    /// The offsets are useful for placing carets or highlights over parts of the text. Letters
+    /// drawn as one ligature glyph split its width evenly between them.
+    pub fn draw_text_with_positions<X, Y>(&mut self, position: Point<X, Y>, text: &str) -> Vec<f64>
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        self.draw_text_run(position, text);
+        let font = self.fonts[self.current_font_index].clone();
+        let drawn = self.apply_ligatures(&font, text);
+        let mut letters = text.chars().peekable();
+        let mut offsets = Vec::with_capacity(text.len());
+        let mut offset = 0.0;
+        for glyph in drawn.chars() {
+            // Either the glyph is the next letter, or it is a ligature which replaced several
+            let count = if letters.peek() == Some(&glyph) {
+                1
+            } else {
+                encoding::LIGATURES
+                    .iter()
+                    .find(|&&(_, ligature, _)| ligature == glyph)
+                    .map_or(1, |(sequence, _, _)| sequence.len())
+            };
+            let width = self.width_of(glyph.encode_utf8(&mut [0; 4]));
+            for i in 0..count {
+                offsets.push(offset + width * i as f64 / count as f64);
+                letters.next();
+            }
+            offset += width;
+        }
+        offsets
+    }
+
+    /// Draw a block of lines that each have their own font and size, such as a title and subtitle
+    /// Each line is `1.25` times its font size tall, and the block as a whole is aligned to
+    /// `position`. Lines must not contain newlines. The current font is unchanged afterwards.
+    pub fn draw_text_block<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        lines: &[(String, Font, f64)],
+        alignment: Alignment,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        let block_height: f64 = lines.iter().map(|(_, _, size)| size * 1.25).sum();
+        let top = match alignment {
+            Alignment::TopLeft | Alignment::TopCenter | Alignment::TopRight => position.y,
+            Alignment::CenterLeft | Alignment::CenterCenter | Alignment::CenterRight => {
+                position.y + block_height / 2.0
+            }
+            Alignment::BottomLeft | Alignment::BottomCenter | Alignment::BottomRight => {
+                position.y + block_height
+            }
+        };
+
+        let previous_font = self.current_font_index;
+        let previous_size = self.font_size;
+
+        let mut baseline = top;
+        for (text, font, size) in lines {
+            self.font(font.clone(), *size);
+            baseline -= size * 1.25;
+            let width = self.width_of(text);
+            let x = match alignment {
+                Alignment::TopLeft | Alignment::CenterLeft | Alignment::BottomLeft => position.x,
+                Alignment::TopCenter | Alignment::CenterCenter | Alignment::BottomCenter => {
+                    position.x - width / 2.0
+                }
+                Alignment::TopRight | Alignment::CenterRight | Alignment::BottomRight => {
+                    position.x - width
+                }
+            };
+            self.draw_text(Point { x, y: baseline }, Alignment::BottomLeft, text);
+        }
+
+        self.current_font_index = previous_font;
+        self.font_size = previous_size;
+        self
+    }
+
+    /// Start a new page which will be placed at `index` in the document instead of at the end
+    /// The page is positioned when it is finished, so an index of 0 makes it the first page even
+    /// if more pages are added after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of finished pages, counting the page in
+    /// progress, which this finishes. The document is left unchanged.
+    #[inline]
+    pub fn insert_page_at<W, H>(&mut self, index: usize, size: Size<W, H>) -> &mut Self
+    where
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let pages = self.num_pages();
+        assert!(
+            index <= pages,
+            "cannot insert a page at {} in a document with {} pages",
+            index,
+            pages
+        );
+        self.add_page(size);
+        self.insert_page_index = Some(index);
+        self
+    }
+
+    /// Throw away the page in progress along with any objects and fonts that were added for it
+    /// Nothing from the page will be written, and `add_page` must be called before drawing again.
+    #[inline]
+    pub fn discard_current_page(&mut self) -> &mut Self {
+        if self.page_buffer.is_empty() {
+            return self;
+        }
+        let (objects, fonts, current_font_index) = self.page_start;
+        self.page_buffer.clear();
+        self.background_content.clear();
+        // The next page carries over the style from before the discarded one
+        self.restore_style(self.page_start_style);
+        self.objects.truncate(objects);
+        let kept = &self.objects;
+        self.image_ids.retain(|_, ids| {
+            ids.retain(|&id| kept.iter().any(|object| object.id == id));
+            !ids.is_empty()
+        });
+        self.fonts.truncate(fonts);
+        self.current_font_index = current_font_index;
+        self.xobjects.clear();
+        self.ext_g_states.clear();
+        self.clips.clear();
+        self.links.clear();
+        self.page_embedded_fonts.clear();
+        self.insert_page_index = None;
+        self
+    }
+
+    /// The number of pages in the document, including the one in progress
+    #[inline]
+    #[must_use]
+    pub fn num_pages(&self) -> usize {
+        self.page_order.len() + usize::from(!self.page_buffer.is_empty())
+    }
+
+    /// Add all the pages of `other` after the pages of this document
+    /// Any page in progress in either document is finished first. Settings such as compression
+    /// only affect pages drawn after this call, so `other`'s pages are copied as they were written.
+    pub fn append(&mut self, mut other: Self) -> &mut Self {
+        self.finish();
+        other.finish();
+        other.resolve_links();
+        other.write_embedded_fonts();
+
+        // Give every object from other an id in this document, then fix up the references to
+        // them, which only page, link, and font dictionaries contain
+        let next_id = self.objects.iter().map(|o| o.id).max().unwrap_or(3) + 1;
+        let ids: HashMap<usize, usize> = other.objects[2..]
+            .iter()
+            .enumerate()
+            .map(|(i, obj)| (obj.id, next_id + i))
+            .collect();
+        // The other objects of an embedded font are streams, which could contain anything
+        let font_dictionaries: Vec<usize> = other
+            .embedded_fonts
+            .iter()
+            .flat_map(|font| font.ids.iter().take(3).copied())
+            .collect();
+        for obj in other.objects.drain(2..) {
+            let is_link = other
+                .link_annotations
+                .iter()
+                .any(|&(id, _, _)| id == obj.id);
+            let contents = if other.page_order.contains(&obj.id)
+                || is_link
+                || font_dictionaries.contains(&obj.id)
+            {
+                util::remap_references(&obj.contents, &ids)
+            } else {
+                obj.contents
+            };
+            self.objects.push(PdfObject {
+                contents,
+                id: ids[&obj.id],
+            });
+        }
+        self.page_order
+            .extend(other.page_order.iter().map(|id| ids[id]));
+        self.page_errors.append(&mut other.page_errors);
+        self
+    }
+
+    /// Set the size of pages added by `add_default_page`, which is 400 by 400 for a new document
+    #[inline]
+    pub fn set_default_page_size<W, H>(&mut self, size: Size<W, H>) -> &mut Self
+    where
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        self.default_page_size = size.into_f64();
+        self
+    }
+
+    /// Move to a new page of the size set with `set_default_page_size`
+    #[inline]
+    pub fn add_default_page(&mut self) -> &mut Self {
+        self.add_page(self.default_page_size)
+    }
+
+    /// Move to a new page the same size as the current one
+    ///
+    /// # Panics
+    ///
+    /// Panics if no page has been added yet.
+    #[inline]
+    pub fn add_page_same_size(&mut self) -> &mut Self {
+        assert!(
+            !self.page_buffer.is_empty() || !self.page_order.is_empty(),
+            "add_page_same_size requires an earlier page to copy the size from"
+        );
+        self.add_page(self.page_dimensions())
+    }
+
+    /// Check every page for common mistakes, such as unbalanced `q`/`Q` or `BT`/`ET` pairs and
+    /// references to fonts which are not in the page's resources
+    /// Each page is checked as it is finished, before its content is compressed, and the page
+    /// in progress is checked by this call. Image sizes are not checked, because every way of
+    /// adding an image already rejects pixels that don't match its dimensions: `Image::new` and
+    /// `ImageBuf::from_strided` panic, and `add_image_streaming` returns an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns every problem found, in the order the pages were finished and then in the order
+    /// they appear in each content stream.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = self.page_errors.clone();
+        errors.extend(self.check_page(&self.page_buffer));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The problems in `content`, drawn with the current page's fonts
+    fn check_page(&self, content: &[u8]) -> Vec<ValidationError> {
+        let embedded_fonts: Vec<usize> = self.page_embedded_fonts.keys().copied().collect();
+        validate::check_stream(content, self.fonts.len(), &embedded_fonts)
+    }
+
+    /// Move to a new page in the PDF document
+    /// The colors and line width in use carry over to the new page, unless defaults were set
+    /// with `set_default_color` or `set_default_line_width`.
+    #[inline]
+    pub fn add_page<W, H>(&mut self, size: Size<W, H>) -> &mut Self
+    where
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        // Compress and write out the previous page if it exists
+        if !self.page_buffer.is_empty() {
+            self.end_page();
+            self.page_buffer.clear();
+        }
+
+        self.page_compression = self.settings.compression;
+        self.page_ascii_filter = self.settings.ascii_filter;
+        self.current_page = Some(self.page_ids.len());
+        self.page_ids.push(None);
+        self.page_start = (
+            self.objects.len(),
+            self.fonts.len(),
+            self.current_font_index,
+        );
+        self.page_buffer
+            .extend("/DeviceRGB cs /DeviceRGB CS\n1 j 1 J\n".bytes());
+        // Every page starts with the default graphics state, so the colors and line width in use
+        // on the last page are set again unless there are defaults for them
+        let (color, fill_color, stroke_color, line_width) = self.style();
+        let initial = (None, Color::gray(0), Color::gray(0), 1.0);
+        self.dash = (Vec::new(), 0.0);
+        self.restore_style(initial);
+        self.alpha = 1.0;
+        self.horizontal_scale = 100.0;
+        #[allow(clippy::float_cmp)]
+        match self.settings.default_line_width {
+            Some(width) => {
+                self.set_line_width(width);
+            }
+            None if line_width != initial.3 => {
+                self.set_line_width(line_width);
+            }
+            None => {}
+        }
+        match (self.settings.default_color, color) {
+            (Some(color), _) | (None, Some(color)) => {
+                self.set_color(color);
+            }
+            (None, None) => {
+                if stroke_color != initial.2 {
+                    self.set_stroke_color(stroke_color);
+                }
+                if fill_color != initial.1 {
+                    self.set_fill_color(fill_color);
+                }
+            }
+        }
+        self.background_offset = self.page_buffer.len();
+        self.page_start_style = self.style();
+        self.origin = (0.0, 0.0);
+        self.bounds = None;
+        self.ctm = Matrix::uniform_scale(1.0);
+        self.width = size.width.into();
+        self.height = size.height.into();
+        self
+    }
+
+    /// Move to a new page like `add_page`, returning a handle for linking to it
+    /// Links can point to the page before it is finished, because they are only resolved when
+    /// the document is written.
+    #[inline]
+    pub fn add_page_handle<W, H>(&mut self, size: Size<W, H>) -> PageRef
+    where
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        self.add_page(size);
+        PageRef(self.page_ids.len() - 1)
+    }
+
+    /// Make an area of the current page a link to `target`, which is shown in full when the link
+    /// is clicked
+    /// The area is in page coordinates, ignoring `transform`. Nothing is drawn, so the area
+    /// usually covers some text or an image. Links to a page which is discarded do nothing.
+    pub fn add_internal_link<X, Y, W, H>(
+        &mut self,
+        (corner, size): (Point<X, Y>, Size<W, H>),
+        target: PageRef,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        self.add_link(corner.into_f64(), size.into_f64(), LinkTarget::Page(target))
+    }
+
+    /// Make an area of the current page a link to `uri`, such as a web page, like
+    /// `add_internal_link`
+    pub fn add_uri_link<X, Y, W, H>(
+        &mut self,
+        (corner, size): (Point<X, Y>, Size<W, H>),
+        uri: &str,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        self.add_link(
+            corner.into_f64(),
+            size.into_f64(),
+            LinkTarget::Uri(uri.to_string()),
+        )
+    }
+
+    fn add_link(
+        &mut self,
+        corner: Point<f64, f64>,
+        size: Size<f64, f64>,
+        target: LinkTarget,
+    ) -> &mut Self {
+        let (x1, y1) = (corner.x, corner.y);
+        let (x2, y2) = (x1 + size.width, y1 + size.height);
+        let rect = [x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)];
+        self.links.push((rect, target));
+        self
+    }
+
+    /// Draw one line of text with its baseline starting at `position` in blue and underlined, as
+    /// a link to `uri`
+    /// The link covers the text from the font's descent to its ascent. The current colors are
+    /// unchanged afterwards.
+    pub fn draw_hyperlink<X, Y>(
+        &mut self,
+        position: Point<X, Y>,
+        text: &str,
+        uri: &str,
+    ) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        let position = position.into_f64();
+        let style = self.style();
+        self.page_buffer.extend(b"q\n");
+        self.set_fill_color(Color::rgb(0, 0, 238));
+        self.draw_text_decorated(
+            position,
+            Alignment::BottomLeft,
+            text,
+            TextDecoration::Underline,
+        );
+        self.page_buffer.extend(b"Q\n");
+        self.restore_style(style);
+
+        // The text may have been moved onto the baseline grid
+        let baseline = self.snap_baseline(position.y);
+        let (descent, ascent) = (self.font_descent(), self.font_ascent());
+        let corner = Point {
+            x: position.x,
+            y: baseline + descent,
+        };
+        let size = Size {
+            width: self.width_of(text),
+            height: ascent - descent,
+        };
+        self.add_link(corner, size, LinkTarget::Uri(uri.to_string()))
+    }
+
+    /// Write the contents of every link annotation, now that the pages they point to have ids
+    fn resolve_links(&mut self) {
+        for (id, [x1, y1, x2, y2], target) in &self.link_annotations {
+            let action = match target {
+                LinkTarget::Page(PageRef(page)) => {
+                    match self.page_ids.get(*page).copied().flatten() {
+                        Some(page_id) => format!(" /Dest [{page_id} 0 R /Fit]"),
+                        None => String::new(),
+                    }
+                }
+                LinkTarget::Uri(uri) => {
+                    use fmt::Write;
+
+                    // URIs are 7-bit ASCII, so anything else is percent-encoded as UTF-8
+                    let mut escaped = String::with_capacity(uri.len());
+                    for c in uri.chars() {
+                        if !c.is_ascii() || c.is_ascii_control() {
+                            let mut buf = [0; 4];
+                            for byte in c.encode_utf8(&mut buf).bytes() {
+                                let _ = write!(escaped, "%{byte:02X}");
+                            }
+                            continue;
+                        }
+                        if matches!(c, '(' | ')' | '\\') {
+                            escaped.push('\\');
+                        }
+                        escaped.push(c);
+                    }
+                    format!(" /A << /Type /Action /S /URI /URI ({escaped}) >>")
+                }
+            };
+            let contents = format!(
+                "<< /Type /Annot /Subtype /Link /Rect [{x1} {y1} {x2} {y2}] /Border [0 0 0]{action} \
+                 >>\n"
+            );
+            if let Some(object) = self.objects.iter_mut().find(|object| object.id == *id) {
+                object.contents = contents.into_bytes();
+            }
+        }
+    }
+
+    /// Draw with `draw` underneath everything else on the current page, even what has already
+    /// been drawn
+    /// The drawing is inserted at the start of the page when the page is finished, and changes to
+    /// the graphics state made by `draw` don't affect drawing after this call.
+    pub fn draw_background<F>(&mut self, draw: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        let foreground = std::mem::take(&mut self.page_buffer);
+        let style = self.style();
+        self.restore_style(self.page_start_style);
+        let alpha = std::mem::replace(&mut self.alpha, 1.0);
+        let dash = std::mem::take(&mut self.dash);
+        let horizontal_scale = std::mem::replace(&mut self.horizontal_scale, 100.0);
+        let ctm = std::mem::replace(&mut self.ctm, Matrix::uniform_scale(1.0));
+        let (current_font_index, font_size) = (self.current_font_index, self.font_size);
+
+        draw(self);
+
+        let background = std::mem::replace(&mut self.page_buffer, foreground);
+        self.background_content.extend(b"q\n");
+        self.background_content.extend(background);
+        self.background_content.extend(b"Q\n");
+        self.restore_style(style);
+        self.alpha = alpha;
+        self.dash = dash;
+        self.horizontal_scale = horizontal_scale;
+        self.ctm = ctm;
+        self.current_font_index = current_font_index;
+        self.font_size = font_size;
+        self
+    }
+
+    /// The content stream of the current page, with any background drawing in place
+    fn page_content(&self) -> Cow<'_, [u8]> {
+        if self.background_content.is_empty() {
+            return Cow::Borrowed(&self.page_buffer);
+        }
+        let (setup, foreground) = self.page_buffer.split_at(self.background_offset);
+        Cow::Owned([setup, &self.background_content, foreground].concat())
+    }
+
+    /// Wrap `data` in a stream object, compressed according to the current settings
+    fn stream_object(&self, data: &[u8]) -> Vec<u8> {
+        Self::stream_object_with(data, self.settings.compression, self.settings.ascii_filter)
+    }
+
+    /// Wrap `data` in a stream object, compressed at `compression` and then encoded with
+    /// `ascii_filter`
+    fn stream_object_with(
+        data: &[u8],
+        compression: Compression,
+        ascii_filter: AsciiFilter,
+    ) -> Vec<u8> {
+        let compressed = match compression {
+            Compression::Lzw => Some(("/LZWDecode", lzw::compress(data))),
+            _ => compression.to_deflate().map(|level| {
+                (
+                    "/FlateDecode",
+                    deflate::deflate_bytes_zlib_conf(data, level),
+                )
+            }),
+        };
+        let mut data = Cow::Borrowed(data);
+        // Filters are listed in the order they are decoded, which is the reverse of encoding
+        let mut filters = Vec::new();
+        if let Some((filter, compressed)) = compressed {
+            filters.push(filter);
+            data = Cow::Owned(compressed);
+        }
+        if let Some((filter, encoded)) = ascii_filter.encode(&data) {
+            filters.insert(0, filter);
+            data = Cow::Owned(encoded);
+        }
+        let mut stream = if filters.is_empty() {
+            format!("<< /Length {} >>\nstream\n", data.len())
+        } else {
+            format!(
+                "<< /Length {} /Filter [{}] >>\nstream\n",
+                data.len(),
+                filters.join(" ")
+            )
+        }
+        .into_bytes();
+        stream.extend_from_slice(&data);
+        stream.extend(b"endstream\n");
+        stream
+    }
+
+    /// Decode the data of a stream object written by `stream_object_with`, returning it along with
+    /// the text encoding it had, or `None` if it uses a filter this library doesn't write
+    #[cfg(feature = "recompress")]
+    fn decode_stream_object(object: &[u8]) -> Option<(Vec<u8>, AsciiFilter)> {
+        let header_end = object.windows(7).position(|w| w == b"stream\n")? + 7;
+        let header = std::str::from_utf8(&object[..header_end]).ok()?;
+        let length: usize = header
+            .split("/Length ")
+            .nth(1)?
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()?;
+        let filters = header
+            .split("/Filter [")
+            .nth(1)
+            .and_then(|rest| rest.split(']').next())
+            .unwrap_or_default();
+        let mut data = object.get(header_end..header_end + length)?.to_vec();
+        let mut ascii_filter = AsciiFilter::None;
+        for filter in filters.split_whitespace() {
+            data = match filter {
+                "/ASCII85Decode" => {
+                    ascii_filter = AsciiFilter::Ascii85;
+                    ascii::ascii85_decode(&data)
+                }
+                "/ASCIIHexDecode" => {
+                    ascii_filter = AsciiFilter::AsciiHex;
+                    ascii::hex_decode(&data)
+                }
+                "/FlateDecode" => inflate::inflate_bytes_zlib(&data).ok()?,
+                "/LZWDecode" => lzw::decompress(&data)?,
+                _ => return None,
+            };
+        }
+        Some((data, ascii_filter))
+    }
+
+    /// Compress the content of every finished page at `level`
+    /// This is for documents built without compression while debugging, which can then be written
+    /// compressed. Text encodings from `set_ascii_filter` are kept as they were. Like with
+    /// `compression`, the page in progress keeps the level it was added with.
+    #[cfg(feature = "recompress")]
+    pub fn recompress_all(&mut self, level: Compression) -> &mut Self {
+        let contents: Vec<usize> = self
+            .objects
+            .iter()
+            .filter(|object| self.page_order.contains(&object.id))
+            .filter_map(|page| {
+                let page = std::str::from_utf8(&page.contents).ok()?;
+                page.split("/Contents ")
+                    .nth(1)?
+                    .split(' ')
+                    .next()?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        for object in &mut self.objects {
+            if !contents.contains(&object.id) {
+                continue;
+            }
+            if let Some((data, ascii_filter)) = Self::decode_stream_object(&object.contents) {
+                object.contents = Self::stream_object_with(&data, level, ascii_filter);
+            }
+        }
+        self
+    }
+
+    /// Draw one page per item on a thread pool, then add the pages in order
+    /// Each page is drawn by `render` into its own `Pdf` which starts with the current page size,
+    /// default font, and document settings, and is compressed on the same thread. Every page
+    /// starts with the colors and line width in use before this call, rather than those left by
+    /// the page before it, and the last page's carry over to the pages after these. `render`
+    /// must not call `add_page`. Any page in progress is finished first.
+    #[cfg(feature = "parallel")]
+    pub fn render_pages_parallel<T, F>(&mut self, items: &[T], render: F) -> &mut Self
+    where
+        T: Sync,
+        F: Fn(&mut Self, &T) + Sync,
+    {
+        use rayon::prelude::*;
+
+        if !self.page_buffer.is_empty() {
+            self.end_page();
+            self.page_buffer.clear();
+        }
+
+        // Only the settings are shared with the threads, because each page is drawn into a new
+        // `Pdf`, and the compression is only timed for the progress callback
+        let timed = self.progress.is_some();
+        let settings = self.settings.clone();
+        let style = self.style();
+        let (font, font_size) = (self.fonts[0].clone(), self.font_size);
+        let embedded_fonts = self.embedded_fonts.clone();
+        let size = Size {
+            width: self.width,
+            height: self.height,
+        };
+        let pages: Vec<(Self, Vec<u8>, usize, Duration)> = items
+            .par_iter()
+            .map(|item| {
+                let mut page = Self::new();
+                page.settings = settings.clone();
+                page.restore_style(style);
+                page.fonts = vec![font.clone()];
+                page.font_size = font_size;
+                page.embedded_fonts.clone_from(&embedded_fonts);
+                page.add_page(size);
+                render(&mut page, item);
+                debug_assert!(page.page_order.is_empty());
+                page.close_clips();
+                let content = page.page_content();
+                let errors = page.check_page(&content);
+                let start = timed.then(Instant::now);
+                let stream = Self::stream_object_with(
+                    &content,
+                    page.page_compression,
+                    page.page_ascii_filter,
+                );
+                let compression_time =
+                    start.map_or_else(Duration::default, |start| start.elapsed());
+                let content_bytes = content.len();
+                drop(content);
+                page.page_errors = errors;
+                (page, stream, content_bytes, compression_time)
+            })
+            .collect();
+
+        for (mut page, stream, content_bytes, compression_time) in pages {
+            self.restore_style(page.style());
+            // Objects created while drawing the page need ids in this document
+            let mut ids = HashMap::new();
+            for obj in page.objects.drain(2..) {
+                ids.insert(obj.id, self.add_object(obj.contents));
+            }
+            self.xobjects = page
+                .xobjects
+                .into_iter()
+                .map(|(name, id)| (name, ids[&id]))
+                .collect();
+            self.ext_g_states = page.ext_g_states;
+            self.links = page.links;
+            self.page_embedded_fonts = page.page_embedded_fonts;
+            self.page_errors.extend(page.page_errors);
+            self.fonts = page.fonts;
+            self.current_font_index = page.current_font_index;
+            self.font_size = page.font_size;
+            self.origin = page.origin;
+            self.width = page.width;
+            self.height = page.height;
+            let stream_bytes = stream.len();
+            self.finish_page(stream);
+            self.report_progress(content_bytes, stream_bytes, compression_time);
+        }
+        self
+    }
+
+    /// Grow the current page's content bounds to include the point (`x`, `y`), after mapping it
+    /// through the current transformation
+    fn extend_bounds(&mut self, x: f64, y: f64) {
+        let m = self.ctm.v;
+        let (x, y) = (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5]);
+        self.bounds = Some(match self.bounds {
+            Some((min_x, min_y, max_x, max_y)) => {
+                (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+            }
+            None => (x, y, x, y),
+        });
+    }
+
+    /// Grow the current page's content bounds to include a rectangle
+    /// Every corner is included, since a rotation can move any of them to the edge.
+    fn extend_bounds_rect(&mut self, corner: Point<f64, f64>, size: Size<f64, f64>) {
+        self.extend_bounds(corner.x, corner.y);
+        self.extend_bounds(corner.x + size.width, corner.y);
+        self.extend_bounds(corner.x, corner.y + size.height);
+        self.extend_bounds(corner.x + size.width, corner.y + size.height);
+    }
+
+    /// The smallest rectangle containing everything drawn on the current page so far, as its
+    /// bottom-left corner and size
+    /// The bounds are in the page's own coordinates, after `transform` is applied, and ignore the
+    /// width of stroked lines. Curves are bounded by their control points, and text by
+    /// its advance width and font size.
+    #[must_use]
+    pub fn content_bounds(&self) -> Option<(Point<f64, f64>, Size<f64, f64>)> {
+        self.bounds.map(|(min_x, min_y, max_x, max_y)| {
+            (
+                Point { x: min_x, y: min_y },
+                Size {
+                    width: max_x - min_x,
+                    height: max_y - min_y,
+                },
+            )
+        })
+    }
+
+    /// Set the current page's media box to its content bounds with `margin` added on every side
+    /// Nothing changes if nothing has been drawn on the page.
+    pub fn fit_page_to_content(&mut self, margin: f64) -> &mut Self {
+        if let Some((corner, size)) = self.content_bounds() {
+            self.set_media_box(
+                Point {
+                    x: corner.x - margin,
+                    y: corner.y - margin,
+                },
+                Size {
+                    width: size.width + 2.0 * margin,
+                    height: size.height + 2.0 * margin,
+                },
+            );
+        }
+        self
+    }
+
+    /// Replace the current page's media box, which need not start at the origin
+    /// This can be called at any point before the page is finished, for example once the extent
+    /// of the drawing is known.
+    #[inline]
+    pub fn set_media_box<X, Y, W, H>(&mut self, corner: Point<X, Y>, size: Size<W, H>) -> &mut Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        let corner = corner.into_f64();
+        let size = size.into_f64();
+        self.origin = (corner.x, corner.y);
+        self.width = size.width;
+        self.height = size.height;
+        self
+    }
+
+    /// The size of the current page's media box
+    #[inline]
+    #[must_use]
+    pub fn page_dimensions(&self) -> Size<f64, f64> {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Dump a page out to disk
+    fn end_page(&mut self) {
+        // Write out any images associated with this page
+        // TODO: are images global or associated with a page?
+
+        self.close_clips();
+        let content = self.page_content();
+        let errors = self.check_page(&content);
+        // Reading the clock panics on some targets, so it is only read for the progress callback
+        let start = self.progress.as_ref().map(|_| Instant::now());
+        let page_stream =
+            Self::stream_object_with(&content, self.page_compression, self.page_ascii_filter);
+        let compression_time = start.map_or_else(Duration::default, |start| start.elapsed());
+        let content_bytes = content.len();
+        self.page_errors.extend(errors);
+        self.background_content.clear();
+        let stream_bytes = page_stream.len();
+        self.finish_page(page_stream);
+        self.report_progress(content_bytes, stream_bytes, compression_time);
+    }
+
+    /// Pass the sizes and compression time of the page that was just finished to the progress
+    /// callback, if there is one
+    fn report_progress(
+        &mut self,
+        content_bytes: usize,
+        stream_bytes: usize,
+        compression_time: Duration,
+    ) {
+        let pages = self.page_order.len();
+        if let Some(callback) = &mut self.progress {
+            callback(Progress {
+                pages,
+                content_bytes,
+                stream_bytes,
+                compression_time,
+            });
+        }
+    }
+
+    /// Add a page with the already-encoded content stream object `page_stream`, using the
+    /// fonts, `XObject`s, and graphics states collected for the current page
+    fn finish_page(&mut self, page_stream: Vec<u8>) {
+        // Create the stream object for this page
+        let stream_object_id = self.add_object(page_stream);
+
+        // Create the page object, which describes settings for the whole page
+        let mut page_object = b"<< /Type /Page\n \
+            /Parent 2 0 R\n \
+            /Resources <<\n  \
+            /ProcSet [/PDF /Text /ImageB /ImageC /ImageI]\n"
+            .to_vec();
+
+        if !self.xobjects.is_empty() {
+            page_object.extend(b"  /XObject <<");
+            for (name, id) in self.xobjects.drain(..) {
+                page_object.extend(format!(" {name} {id} 0 R").bytes());
+            }
+            page_object.extend(b" >>\n");
+        }
+
+        if !self.ext_g_states.is_empty() {
+            page_object.extend(b"  /ExtGState <<");
+            for (index, entries) in self.ext_g_states.drain(..).enumerate() {
+                page_object.extend(format!(" /GS{index} << ").bytes());
+                page_object.extend(entries);
+                page_object.extend(b" >>");
+            }
+            page_object.extend(b" >>\n");
+        }
+
+        if !self.font_resources.contains_key(&self.fonts) {
+            let fragment = self.font_resource_fragment();
+            self.font_resources.insert(self.fonts.clone(), fragment);
+        }
+        let fragment = &self.font_resources[&self.fonts];
+        if self.page_embedded_fonts.is_empty() {
+            page_object.extend_from_slice(fragment);
+        } else {
+            // Embedded fonts go in the same dictionary, before it is closed
+            page_object.extend_from_slice(&fragment[..fragment.len() - b"  >>\n".len()]);
+            for (index, glyphs) in std::mem::take(&mut self.page_embedded_fonts) {
+                if self.embedded_fonts[index].ids.is_empty() {
+                    let count = if self.embedded_fonts[index].subset {
+                        6
+                    } else {
+                        5
+                    };
+                    // Filled in by write_embedded_fonts
+                    let ids = (0..count).map(|_| self.add_object(Vec::new())).collect();
+                    self.embedded_fonts[index].ids = ids;
+                }
+                let font = &mut self.embedded_fonts[index];
+                for (glyph, c) in glyphs {
+                    font.used.entry(glyph).or_insert(c);
+                }
+                page_object.extend(format!("   /E{index} {} 0 R\n", font.ids[0]).bytes());
+            }
+            page_object.extend(b"  >>\n");
+        }
+        page_object.extend(b" >>\n");
+
+        if !self.links.is_empty() {
+            page_object.extend(b" /Annots [");
+            for (i, (rect, target)) in std::mem::take(&mut self.links).into_iter().enumerate() {
+                // Filled in by resolve_links
+                let id = self.add_object(Vec::new());
+                self.link_annotations.push((id, rect, target));
+                let separator = if i == 0 { "" } else { " " };
+                page_object.extend(format!("{separator}{id} 0 R").bytes());
+            }
+            page_object.extend(b"]\n");
+        }
+
+        page_object.extend_from_slice(
+            format!(
+                " /MediaBox [{} {} {} {}]\n \
+                 /Contents {} 0 R\n\
+                 >>\n",
+                self.origin.0,
+                self.origin.1,
+                self.origin.0 + self.width,
+                self.origin.1 + self.height,
+                stream_object_id
+            )
+            .as_bytes(),
+        );
+        let page_id = self.add_object(page_object);
+        if let Some(page) = self.current_page.take() {
+            self.page_ids[page] = Some(page_id);
+        }
+        let index = self
+            .insert_page_index
+            .take()
+            .unwrap_or(self.page_order.len());
+        self.page_order.insert(index, page_id);
+
+        // Only the default font and the one in use carry over to the next page
+        let current_font = self.fonts[self.current_font_index].clone();
+        self.fonts.truncate(1);
+        if current_font == self.fonts[0] {
+            self.current_font_index = 0;
+        } else {
+            self.fonts.push(current_font);
+            self.current_font_index = 1;
+        }
+    }
+
+    /// Build the `/Font` resource dictionary entry for the current set of fonts
+    fn font_resource_fragment(&mut self) -> Vec<u8> {
+        // All fonts share the WinAnsi encoding, so they can share one ToUnicode CMap
+        let encoding = encoding::encoding_dictionary();
+        let to_unicode_id = if let Some(id) = self.to_unicode_id {
+            id
+        } else {
+            let cmap = self.stream_object(&encoding::to_unicode_cmap());
+            let id = self.add_object(cmap);
+            self.to_unicode_id = Some(id);
+            id
+        };
+
+        let mut fragment = b"  /Font <<\n".to_vec();
+        for (f, font) in self.fonts.iter().enumerate() {
+            // Symbolic fonts are written in their own encoding, which the viewer already knows
+            if matches!(font, Font::Symbol | Font::ZapfDingbats) {
+                fragment.extend(
+                    format!(
+                        "   /F{f} <<\n    /Type /Font\n    /Subtype /Type1\n    /BaseFont \
+                         /{font:?}\n   >>\n"
+                    )
+                    .bytes(),
+                );
+                continue;
+            }
+            fragment.extend(
+                format!(
+                    "   /F{f} <<\n    /Type /Font\n    /Subtype /Type1\n    /BaseFont \
+                     /{font:?}\n    /Encoding {encoding}\n    /ToUnicode {to_unicode_id} 0 R\n   >>\n"
+                )
+                .bytes(),
+            );
+        }
+        fragment.extend(b"  >>\n");
+        fragment
+    }
+
+    /// Write the objects of every embedded font that a finished page uses, now that all the
+    /// glyphs they need are known
+    fn write_embedded_fonts(&mut self) {
+        for font in &self.embedded_fonts {
+            if font.ids.is_empty() {
+                continue;
+            }
+            for (id, contents) in font.ids.iter().zip(self.embedded_font_objects(font)) {
+                if let Some(object) = self.objects.iter_mut().find(|object| object.id == *id) {
+                    object.contents = contents;
+                }
+            }
+        }
+    }
+
+    /// The contents of the objects for `font`, in the order of its reserved ids: the `Type0`
+    /// font, the CID font, the font descriptor, the font program, the `ToUnicode` map, and for a
+    /// subset, the map from the original glyph ids used in content streams to the subset's
+    fn embedded_font_objects(&self, font: &EmbeddedFontData) -> Vec<Vec<u8>> {
+        use std::hash::{Hash, Hasher};
+
+        let ids = &font.ids;
+        let ttf = &font.font;
+        let (program, name, cid_to_gid_map) = if font.subset {
+            let (program, new_ids) = ttf.subset(&font.used);
+            let mut map = vec![0; 2 * new_ids.keys().last().map_or(1, |&last| last as usize + 1)];
+            for (old, new) in new_ids {
+                let old = usize::from(old);
+                map[2 * old..2 * old + 2].copy_from_slice(&new.to_be_bytes());
+            }
+            // A subset's name starts with a tag which is different for each set of glyphs
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            font.used.keys().collect::<Vec<_>>().hash(&mut hasher);
+            let mut hash = hasher.finish();
+            let mut name = String::new();
+            for _ in 0..6 {
+                name.push(char::from(b'A' + (hash % 26) as u8));
+                hash /= 26;
+            }
+            name.push('+');
+            name.push_str(&ttf.postscript_name);
+            (program, name, Some(map))
+        } else {
+            (ttf.data().to_vec(), ttf.postscript_name.clone(), None)
+        };
+        let cid_to_gid = match cid_to_gid_map {
+            Some(_) => format!("{} 0 R", ids[5]),
+            None => "/Identity".to_string(),
+        };
+        // Symbolic, because the glyphs are not in a standard character set
+        let mut flags = 4;
+        if ttf.fixed_pitch {
+            flags |= 1;
+        }
+        if ttf.italic_angle != 0.0 {
+            flags |= 64;
+        }
+        let [x_min, y_min, x_max, y_max] = ttf.bbox.map(|v| ttf.scale(v).round());
+
+        let mut objects = vec![
+            format!(
+                "<< /Type /Font\n\
+                 /Subtype /Type0\n\
+                 /BaseFont /{name}\n\
+                 /Encoding /Identity-H\n\
+                 /DescendantFonts [{} 0 R]\n\
+                 /ToUnicode {} 0 R >>\n",
+                ids[1], ids[4]
+            )
+            .into_bytes(),
+            format!(
+                "<< /Type /Font\n\
+                 /Subtype /CIDFontType2\n\
+                 /BaseFont /{name}\n\
+                 /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >>\n\
+                 /FontDescriptor {} 0 R\n\
+                 /W {}\n\
+                 /CIDToGIDMap {cid_to_gid} >>\n",
+                ids[2],
+                truetype::width_array(ttf, &font.used)
+            )
+            .into_bytes(),
+            format!(
+                "<< /Type /FontDescriptor\n\
+                 /FontName /{name}\n\
+                 /Flags {flags}\n\
+                 /FontBBox [{x_min} {y_min} {x_max} {y_max}]\n\
+                 /ItalicAngle {}\n\
+                 /Ascent {}\n\
+                 /Descent {}\n\
+                 /CapHeight {}\n\
+                 /StemV 80\n\
+                 /FontFile2 {} 0 R >>\n",
+                ttf.italic_angle,
+                ttf.scale(ttf.ascent).round(),
+                ttf.scale(ttf.descent).round(),
+                ttf.scale(ttf.cap_height).round(),
+                ids[3]
+            )
+            .into_bytes(),
+        ];
+        // The font program's stream also records its length before compression
+        let mut file = self.stream_object(&program);
+        file.splice(3..3, format!("/Length1 {} ", program.len()).bytes());
+        objects.push(file);
+        objects.push(self.stream_object(&truetype::to_unicode_cmap(&font.used)));
+        if let Some(map) = cid_to_gid_map {
+            objects.push(self.stream_object(&map));
+        }
+        objects
+    }
+
+    /// Finish the page in progress, if there is one
+    fn finish(&mut self) {
+        if !self.page_buffer.is_empty() {
+            self.end_page();
+            self.page_buffer.clear();
+        }
+    }
+
+    /// Finish the document and return the complete PDF file
+    /// The current page is ended, so this may be called repeatedly and always produces the same
+    /// bytes unless more pages are added in between.
+    pub fn to_bytes(&mut self) -> Vec<u8> {
+        self.finish();
+        self.resolve_links();
+        self.write_embedded_fonts();
+
+        let (major, minor) = self.version;
+        let mut body = format!("%PDF-{major}.{minor}\n").into_bytes();
+        // A comment with high bytes marks the file as binary for transfer programs
+        body.extend(b"%\xB5\xED\xAE\xFB\n");
+        let mut offsets = vec![0; self.objects.len()];
+
+        // Write out each object
+        for obj in self.objects.iter().skip(2) {
+            offsets[obj.id - 1] = body.len();
+            body.extend(format!("{} 0 obj\n", obj.id).as_bytes());
+            body.extend_from_slice(&obj.contents);
+            body.extend_from_slice(b"endobj\n");
+        }
+
+        // The info dictionary isn't kept with the other objects so that writing is repeatable
+        let info_id = match (&self.seed_date, self.deterministic) {
+            (Some(date), true) => {
+                let id = offsets.len() + 1;
+                offsets.push(body.len());
+                body.extend(
+                    format!(
+                        "{id} 0 obj\n<< /CreationDate ({date})\n/ModDate ({date}) >>\nendobj\n"
+                    )
+                    .bytes(),
+                );
+                Some(id)
+            }
+            _ => None,
+        };
+
+        // Write out the page tree object
+        offsets[1] = body.len();
+        body.extend(b"2 0 obj\n");
+        body.extend(b"<< /Type /Pages\n");
+        body.extend(format!("/Count {}\n", self.page_order.len()).bytes());
+        let kids: Vec<String> = self
+            .page_order
+            .iter()
+            .map(|id| format!("{id} 0 R"))
+            .collect();
+        body.extend(format!("/Kids [{}] >>\nendobj\n", kids.join(" ")).bytes());
+
+        // Write out the catalog dictionary object
+        offsets[0] = body.len();
+        body.extend_from_slice(b"1 0 obj\n<< /Type /Catalog\n/Pages 2 0 R");
+        // Readers which only look at the header may not know the newer versions, but they will
+        // find the version in the catalog
+        if self.version > (1, 7) {
+            body.extend(format!("\n/Version /{major}.{minor}").bytes());
+        }
+        body.extend_from_slice(b" >>\nendobj\n");
+
+        // The ID must be computed before the xref, which would otherwise depend on it
+        let id = if self.deterministic {
+            Some(util::content_id(&body))
+        } else {
+            None
+        };
+
+        // Write the cross-reference table
+        let startxref = body.len();
+        body.extend(b"xref\n");
+        body.extend(format!("0 {}\n", offsets.len() + 1).bytes());
+        body.extend(b"0000000000 65535 f \n");
+        for offset in &offsets {
+            body.extend(format!("{offset:010} 00000 n \n").bytes());
+        }
+
+        // Write the document trailer
+        body.extend(b"trailer\n");
+        body.extend(format!("<< /Size {}\n", offsets.len() + 1).bytes());
+        if let Some(info_id) = info_id {
+            body.extend(format!("/Info {info_id} 0 R\n").bytes());
+        }
+        if let Some(id) = id {
+            body.extend(format!("/ID [<{id}> <{id}>]\n").bytes());
+        }
+        body.extend(b"/Root 1 0 R >>\n");
+
+        // Write the offset to the xref table
+        body.extend(format!("startxref\n{startxref}\n").bytes());
+
+        // Write the PDF EOF
+        body.extend(b"%%EOF");
+
+        body
+    }
+}
+
+#[cfg(test)]
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[test]
+fn test_deterministic_output() {
+    let build = || {
+        Pdf::new()
+            .set_deterministic(Some("D:20190101000000Z"))
+            .add_page(Size {
+                width: 100,
+                height: 100,
+            })
+            .draw_dots(&[10.0, 20.0], &[30.0, 40.0])
+            .draw_circle(Point { x: 50, y: 50 }, 10)
+            .to_bytes()
+    };
+    let first = build();
+    assert_eq!(first, build());
+    assert!(contains(&first, b"/CreationDate (D:20190101000000Z)"));
+    assert!(contains(&first, b"/ID [<"));
+}
+
+#[test]
+fn test_dashed_circle_restores_solid() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_dashed_circle(Point { x: 50, y: 50 }, 10, &[3.0, 1.5]);
+    let stream = &pdf.page_buffer;
+    assert!(contains(stream, b"[3 1.5] 0 d\n"));
+    assert!(stream.ends_with(b"S\n[] 0 d\n"));
+}
+
+#[test]
+fn test_redundant_color_skipped() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .set_color(Color::gray(128))
+    .set_color(Color::rgb(128, 128, 128));
+    assert_eq!(
+        pdf.page_buffer.windows(3).filter(|w| w == b" rg").count(),
+        1
+    );
+}
+
+#[test]
+fn test_to_unicode_cmap() {
+    let bytes = Pdf::new()
+        .compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "é")
+        .to_bytes();
+    // Object 3 is the page's content stream
+    assert!(contains(&bytes, b"/ToUnicode 4 0 R"));
+    assert!(contains(&bytes, b"4 0 obj\n<< /Length"));
+    assert!(contains(&bytes, b"<E9> <00E9>\n"));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_text_runs_are_contiguous() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 100,
+    });
+    let end = pdf.draw_text_run(Point { x: 10, y: 20 }, "Hello");
+    assert_eq!(end.y, 20.0);
+    assert_eq!(end.x, 10.0 + pdf.width_of("Hello"));
+
+    pdf.font(Font::TimesBold, 12);
+    let end = pdf.draw_text_run(end, "World");
+
+    let mut expected = Vec::new();
+    ryu!(expected, 10, end.x - pdf.width_of("World"), 20.0);
+    expected.extend(b"Tm");
+    assert!(contains(&pdf.page_buffer, &expected));
+}
+
+#[test]
+fn test_text_block_mixed_sizes() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    })
+    .draw_text_block(
+        Point { x: 10, y: 100 },
+        &[
+            ("Title".to_string(), Font::HelveticaBold, 24.0),
+            ("Subtitle".to_string(), Font::Helvetica, 12.0),
+        ],
+        Alignment::TopLeft,
+    );
+    // The first line is 30 tall and the second 15, instead of a uniform leading
+    assert!(contains(&pdf.page_buffer, b"1 0 0 1 10 70\nTm"));
+    assert!(contains(&pdf.page_buffer, b"1 0 0 1 10 55\nTm"));
+    assert!(contains(&pdf.page_buffer, b"/F1 24 Tf"));
+}
+
+#[test]
+fn test_tuple_arguments() {
+    let mut pdf = Pdf::new();
+    pdf.add_page((100, 100).into())
+        .draw_circle((50.0, 50.0).into(), 10);
+    assert!(contains(&pdf.page_buffer, b"50 40 m\n"));
+}
+
+#[test]
+fn test_image_tiled() {
+    let pixels = [0_u8; 10 * 10 * 3];
+    let bytes = Pdf::new()
+        .compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_image_tiled(
+            Image::new(&pixels, 10_u32, 10_u32),
+            (
+                Point { x: 5, y: 5 },
+                Size {
+                    width: 25,
+                    height: 25,
+                },
+            ),
+        )
+        .to_bytes();
+    assert_eq!(bytes.windows(7).filter(|w| w == b"/X0 Do ").count(), 9);
+    assert_eq!(
+        bytes.windows(13).filter(|w| w == b"/Subtype /Ima").count(),
+        1
+    );
+    assert!(contains(&bytes, b"q 5 5 25 25 re W n\n"));
+    assert!(contains(&bytes, b"/XObject << /X0 3 0 R >>"));
+}
+
+#[test]
+fn test_watermark() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off)
+        .add_page(Size {
+            width: 300,
+            height: 400,
+        })
+        .set_color(Color::gray(0))
+        .draw_watermark("DRAFT", Color::rgb(255, 0, 0), 0.25);
+    assert!(pdf.page_buffer.ends_with(b") Tj\nET\nQ\n"));
+    // 0.6 and 0.8 are the cosine and sine of the diagonal of a 3:4 page
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    let tm: Vec<f64> = stream
+        .lines()
+        .find(|l| l.ends_with(" Tm"))
+        .unwrap()
+        .split(' ')
+        .take(4)
+        .map(|v| v.parse().unwrap())
+        .collect();
+    for (value, expected) in tm.iter().zip(&[0.6, 0.8, -0.8, 0.6]) {
+        assert!((value - expected).abs() < 1e-6);
+    }
+    assert!(contains(&pdf.page_buffer, b"/GS0 gs\n"));
+    // Q restores black, so only the watermark's red needs to be set again
+    pdf.set_color(Color::gray(0));
+    assert!(pdf.page_buffer.ends_with(b"Q\n"));
+    pdf.set_color(Color::rgb(255, 0, 0));
+    assert!(pdf.page_buffer.ends_with(b"1 0 0 rg\n"));
+    assert!(contains(
+        &pdf.to_bytes(),
+        b"/ExtGState << /GS0 << /CA 0.25 /ca 0.25 >> >>"
+    ));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_matches_serial() {
+    let items: Vec<f64> = (1..20).map(f64::from).collect();
+    let render = |pdf: &mut Pdf, radius: &f64| {
+        pdf.font(Font::Courier, 10)
+            .draw_text(Point { x: 10, y: 10 }, Alignment::BottomLeft, "page")
+            .draw_dots(&[*radius], &[*radius])
+            .set_alpha(0.5)
+            .draw_circle(Point { x: 50, y: 50 }, *radius);
+    };
+
+    // New documents default to 400x400 pages
+    let mut serial = Pdf::new();
+    for item in &items {
+        serial.add_page(Size {
+            width: 400,
+            height: 400,
+        });
+        render(&mut serial, item);
+    }
+
+    let mut parallel = Pdf::new();
+    parallel.render_pages_parallel(&items, render);
+
+    assert_eq!(serial.to_bytes(), parallel.to_bytes());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_uses_document_settings() {
+    let items = ["find", "flow"];
+    let render = |pdf: &mut Pdf, word: &&str| {
+        pdf.draw_text(Point { x: 10, y: 305 }, Alignment::BottomLeft, word);
+    };
+
+    let mut serial = Pdf::new();
+    serial.enable_ligatures(true).set_baseline_grid(12.0, 0.0);
+    for item in &items {
+        serial.add_page(Size {
+            width: 400,
+            height: 400,
+        });
+        render(&mut serial, item);
+    }
+
+    let mut parallel = Pdf::new();
+    parallel
+        .enable_ligatures(true)
+        .set_baseline_grid(12.0, 0.0)
+        .render_pages_parallel(&items, render);
+
+    assert_eq!(serial.to_bytes(), parallel.to_bytes());
+}
+
+#[test]
+fn test_font_resources_shared() {
+    let mut pdf = Pdf::new();
+    for _ in 0..3 {
+        pdf.add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "text");
+    }
+    // Only the last page uses a second font
+    pdf.font(Font::Courier, 12);
+    let bytes = pdf.to_bytes();
+    assert_eq!(pdf.font_resources.len(), 2);
+    let fragment = &pdf.font_resources[&vec![Font::Helvetica]];
+    assert_eq!(
+        bytes
+            .windows(fragment.len())
+            .filter(|w| w == fragment)
+            .count(),
+        2
+    );
+    let fragment = &pdf.font_resources[&vec![Font::Helvetica, Font::Courier]];
+    assert!(contains(fragment, b"/F0 <<"));
+    assert!(contains(fragment, b"/F1 <<"));
+    assert_eq!(fragment.windows(5).filter(|w| w == b"/Font").count(), 3);
+}
+
+#[test]
+fn test_page_dimensions() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 180,
+        height: 240,
+    });
+    let size = pdf.page_dimensions();
+    assert_eq!((size.width, size.height), (180.0, 240.0));
+}
+
+#[test]
+fn test_default_font() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off)
+        .set_default_font(Font::TimesRoman, 14)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "a")
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "b");
+    let bytes = pdf.to_bytes();
+    assert_eq!(bytes.windows(10).filter(|w| w == b"/F0 14 Tf\n").count(), 2);
+    assert_eq!(
+        bytes
+            .windows(20)
+            .filter(|w| w == b"/BaseFont /TimesRoma")
+            .count(),
+        2
+    );
+    assert!(!contains(&bytes, b"/Helvetica"));
+}
+
+#[test]
+fn test_default_font_keeps_font_indices() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .font(Font::Courier, 12)
+        .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "a")
+        .font(Font::TimesRoman, 12)
+        .draw_text(Point { x: 10, y: 30 }, Alignment::TopLeft, "b")
+        .set_default_font(Font::Courier, 12)
+        .draw_text(Point { x: 10, y: 50 }, Alignment::TopLeft, "c");
+    let bytes = pdf.to_bytes();
+    assert!(contains(&bytes, b"/F1 12 Tf\n"));
+    assert!(contains(&bytes, b"/F2 12 Tf\n"));
+    assert!(contains(
+        &bytes,
+        b"/F1 <<\n    /Type /Font\n    /Subtype /Type1\n    /BaseFont /Courier\n"
+    ));
+    assert!(contains(
+        &bytes,
+        b"/F2 <<\n    /Type /Font\n    /Subtype /Type1\n    /BaseFont /TimesRoman\n"
+    ));
+}
+
+#[test]
+fn test_font_survives_page_break() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .font(Font::Courier, 10)
+    .font(Font::Symbol, 10)
+    .add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "a");
+    assert_eq!(pdf.fonts, vec![Font::Helvetica, Font::Symbol]);
+    assert_eq!(pdf.validate(), Ok(()));
+}
+
+#[test]
+fn test_raw_text_escaping() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_raw_text(Point { x: 10, y: 20 }, b"a(b)\\\xE9");
+    assert!(contains(
+        &pdf.page_buffer,
+        b"1 0 0 1 10 20 Tm\n(a\\(b\\)\\\\\xE9) Tj\nET\n"
+    ));
+    assert_eq!(pdf.validate(), Ok(()));
+}
+
+#[test]
+fn test_validate_finished_pages() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.page_buffer.extend(b"Q\n/F9 10 Tf\n");
+    pdf.add_page_same_size();
+    pdf.page_buffer.extend(b"BT\n");
+    assert_eq!(
+        pdf.validate(),
+        Err(vec![
+            ValidationError::UnmatchedRestore,
+            ValidationError::UnknownFont("F9".to_string()),
+            ValidationError::UnclosedText,
+        ])
+    );
+}
+
+/// The translation of every text matrix set in `stream`
+#[cfg(test)]
+fn text_positions(stream: &[u8]) -> Vec<(f64, f64)> {
+    let stream = String::from_utf8_lossy(stream);
+    let tokens: Vec<&str> = stream.split_whitespace().collect();
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| **t == "Tm")
+        .map(|(i, _)| {
+            (
+                tokens[i - 2].parse().unwrap(),
+                tokens[i - 1].parse().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_vertical_text() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text_vertical(Point { x: 50, y: 90 }, "abc\nd");
+    let positions = text_positions(&pdf.page_buffer);
+    assert_eq!(positions.len(), 4);
+    assert!(positions[0].1 > positions[1].1 && positions[1].1 > positions[2].1);
+    // The second column starts back at the top, to the left
+    assert!(positions[3].0 < positions[0].0);
+    assert!((positions[3].1 - positions[0].1).abs() < 1e-9);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_horizontal_scale() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let width = pdf.width_of("scaled");
+    pdf.set_horizontal_scale(50.0);
+    assert!(pdf.page_buffer.ends_with(b"50 Tz\n"));
+    assert_eq!(pdf.width_of("scaled"), width / 2.0);
+
+    pdf.draw_text(Point { x: 90, y: 50 }, Alignment::TopRight, "scaled");
+    let (x, _) = text_positions(&pdf.page_buffer)[0];
+    assert!((x - (90.0 - width / 2.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_comment() {
+    let bytes = Pdf::new()
+        .compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .comment("axes\r\nstart here")
+        .draw_rectangle(
+            Point { x: 0, y: 0 },
+            Size {
+                width: 10,
+                height: 10,
+            },
+        )
+        .to_bytes();
+    assert!(contains(&bytes, b"% axes  start here\n0 0 10 10 re S\n"));
+}
+
+#[test]
+fn test_fill_area_under() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .fill_area_under(vec![10.0, 20.0, 30.0], vec![50.0, 70.0, 60.0], 5.0);
+    assert!(pdf
+        .page_buffer
+        .ends_with(b"10 50 m\n20 70 l\n30 60 l\n30 5 l\n10 5 l\nh f\n"));
+}
+
+#[test]
+fn test_add_page_same_size() {
+    let bytes = Pdf::new()
+        .add_page(Size {
+            width: 180,
+            height: 240,
+        })
+        .add_page_same_size()
+        .add_page_same_size()
+        .to_bytes();
+    assert_eq!(
+        bytes
+            .windows(22)
+            .filter(|w| w == b"/MediaBox [0 0 180 240")
+            .count(),
+        3
+    );
+}
+
+#[test]
+#[should_panic(expected = "requires an earlier page")]
+fn test_add_page_same_size_without_page() {
+    Pdf::new().add_page_same_size();
+}
+
+#[test]
+fn test_insert_page_at() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .add_page_same_size()
+    .insert_page_at(
+        0,
+        Size {
+            width: 200,
+            height: 200,
+        },
+    )
+    .add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let bytes = pdf.to_bytes();
+    let bytes = String::from_utf8_lossy(&bytes);
+    let cover_id = pdf.page_order[0];
+    assert!(bytes.contains(&format!(
+        "{cover_id} 0 obj\n<< /Type /Page\n /Parent 2 0 R\n /Resources <<\n"
+    )));
+    assert!(bytes.contains("/Count 4\n"));
+    assert!(bytes.contains(&format!("/Kids [{cover_id} 0 R ")));
+    let cover = &pdf
+        .objects
+        .iter()
+        .find(|o| o.id == cover_id)
+        .unwrap()
+        .contents;
+    assert!(contains(cover, b"/MediaBox [0 0 200 200]"));
+}
+
+#[test]
+fn test_insert_page_at_out_of_range() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let size = pdf.page_dimensions();
+    let inserted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        pdf.insert_page_at(2, size);
+    }));
+    assert!(inserted.is_err());
+    // The page in progress was not finished
+    assert!(pdf.page_order.is_empty());
+    assert_eq!(pdf.num_pages(), 1);
+}
+
+#[test]
+fn test_discard_current_page() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_circle(Point { x: 50, y: 50 }, 10)
+    .add_page_same_size()
+    .font(Font::Courier, 12)
+    .draw_dots(&[1.0], &[1.0]);
+    assert_eq!(pdf.num_pages(), 2);
+    pdf.discard_current_page();
+    assert_eq!(pdf.num_pages(), 1);
+    assert_eq!(pdf.fonts, vec![Font::Helvetica]);
+
+    let bytes = pdf.to_bytes();
+    assert_eq!(pdf.num_pages(), 1);
+    assert_eq!(pdf.objects.len(), 5);
+    assert!(contains(&bytes, b"/Count 1\n"));
+    assert!(!contains(&bytes, b"/XObject"));
+}
+
+#[test]
+fn test_spline_through_collinear_points() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let points: Vec<_> = [1.0, 2.0, 4.0, 8.0]
+        .iter()
+        .map(|&x| Point { x, y: 2.0 * x })
+        .collect();
+    pdf.draw_spline(&points, 0.5);
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    let curves: Vec<&str> = stream.lines().filter(|l| l.ends_with(" c")).collect();
+    assert_eq!(curves.len(), 3);
+    for curve in curves {
+        let v: Vec<f64> = curve
+            .split(' ')
+            .take(6)
+            .map(|v| v.parse().unwrap())
+            .collect();
+        for point in v.chunks(2) {
+            assert!((point[1] - 2.0 * point[0]).abs() < 1e-6);
+        }
+    }
+    assert!(stream.ends_with("S\n"));
+}
+
+#[test]
+fn test_default_line_width_and_color() {
+    let mut pdf = Pdf::new();
+    pdf.set_default_line_width(2.5).set_default_color(Color {
+        red: 255,
+        green: 0,
+        blue: 0,
+    });
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.set_line_width(1).set_color(Color {
+        red: 0,
+        green: 0,
+        blue: 255,
+    });
+    pdf.add_page_same_size();
+    assert_eq!(
+        pdf.page_buffer,
+        b"/DeviceRGB cs /DeviceRGB CS\n1 j 1 J\n2.5 w\n1 0 0 SC\n1 0 0 rg\n"
+    );
+}
+
+#[test]
+fn test_histogram_area() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    let samples: Vec<f64> = (0..100).map(f64::from).collect();
+    let black = Color {
+        red: 0,
+        green: 0,
+        blue: 0,
+    };
+    pdf.draw_histogram(Point { x: 10, y: 10 }, &samples, 10, 50.0, 100.0, black);
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    let bars: Vec<Vec<f64>> = stream
+        .lines()
+        .filter(|l| l.ends_with("re f"))
+        .map(|l| l.split(' ').take(4).map(|v| v.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(bars.len(), 10);
+    // The tallest bin holds 10 samples, so each sample is 10 wide and 5 tall
+    let area: f64 = bars.iter().map(|b| b[2] * b[3]).sum();
+    assert!((area / (10.0 * 5.0) - 100.0).abs() < 1e-6);
+
+    pdf.draw_histogram(Point { x: 10, y: 10 }, &[], 10, 50.0, 100.0, black);
+    pdf.draw_histogram(Point { x: 10, y: 10 }, &samples, 0, 50.0, 100.0, black);
+    assert_eq!(
+        String::from_utf8_lossy(&pdf.page_buffer)
+            .matches("re f")
+            .count(),
+        10
+    );
+}
+
+#[test]
+fn test_miter_limit() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.set_miter_limit(4.0);
+    assert!(pdf.page_buffer.ends_with(b"4 M\n"));
+    pdf.set_miter_limit(0.5);
+    assert!(pdf.page_buffer.ends_with(b"1 M\n"));
+}
+
+#[test]
+fn test_simulated_transparency() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.set_simulate_transparency(true)
+        .set_alpha(0.5)
+        .set_color(Color::rgb(255, 0, 0));
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    assert!(!stream.contains(" gs"));
+    assert!(stream.ends_with("1 0.5019607843 0.5019607843 rg\n"));
+}
+
+#[test]
+fn test_arrow() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.draw_arrow(Point { x: 10, y: 10 }, Point { x: 10, y: 10 }, 5.0);
+    assert!(!pdf.page_buffer.ends_with(b"f\n"));
+
+    pdf.draw_arrow(Point { x: 10, y: 10 }, Point { x: 50, y: 10 }, 5.0);
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    let ops: Vec<&str> = stream
+        .lines()
+        .rev()
+        .take(7)
+        .map(|l| l.rsplit(' ').next().unwrap())
+        .collect();
+    assert_eq!(ops, ["f", "l", "l", "m", "S", "l", "m"]);
+    assert!(stream.contains("10 10 m\n"));
+    assert!(stream.contains("50 10 m\n"));
+}
+
+#[test]
+fn test_flatness() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.set_flatness(1.0);
+    assert!(pdf.page_buffer.ends_with(b"1 i\n"));
+    pdf.set_flatness(250.0);
+    assert!(pdf.page_buffer.ends_with(b"100 i\n"));
+}
+
+#[test]
+fn test_overprint() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off);
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.set_overprint(true, false);
+    assert!(pdf.page_buffer.ends_with(b"/GS0 gs\n"));
+    let bytes = pdf.to_bytes();
+    assert!(contains(&bytes, b"/GS0 << /OP false /op true /OPM 1 >>"));
+}
+
+#[test]
+fn test_append() {
+    let mut first = Pdf::new();
+    first
+        .compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "first");
+    let mut second = Pdf::new();
+    second
+        .compression(Compression::Off)
+        .add_page(Size {
+            width: 200,
+            height: 200,
+        })
+        .draw_dots(&[1.0], &[1.0])
+        .draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "second");
+    first.append(second);
+    let bytes = first.to_bytes();
+    let text = String::from_utf8_lossy(&bytes);
+
+    assert!(text.contains("/Count 2\n"));
+    assert!(text.contains("(\\163\\145\\143\\157\\156\\144) Tj"));
+    // Every reference points at an object of the right kind in the merged document
+    let object = |id: usize| {
+        let start = text.find(&format!("\n{id} 0 obj\n")).unwrap();
+        &text[start..start + text[start..].find("endobj").unwrap()]
+    };
+    let second_page = object(first.page_order[1]);
+    assert!(second_page.contains("/MediaBox [0 0 200 200]"));
+    let reference = |key: &str| -> usize {
+        let rest = &second_page[second_page.find(key).unwrap() + key.len()..];
+        rest.split(' ')
+            .find(|t| !t.is_empty())
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+    assert!(object(reference("/Contents")).contains("Tj"));
+    assert!(object(reference("/X0")).contains("/Subtype /Form"));
+    assert!(object(reference("/ToUnicode")).contains("begincmap"));
+    assert_eq!(first.objects.len(), 2 + 3 + 4);
+}
+
+#[test]
+fn test_font_fallback() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let width = pdf.width_of("x∈Y");
+    pdf.set_font_fallback(Some(Font::Symbol));
+    assert!(pdf.width_of("x∈Y") > width);
+    pdf.draw_text(Point { x: 10, y: 10 }, Alignment::BottomLeft, "x∈Y");
+    // ∈ is code 0316 in the Symbol font's own encoding
+    assert!(contains(
+        &pdf.page_buffer,
+        b"Tm (\\170) Tj\n/F1 12 Tf\n(\\316) Tj\n/F0 12 Tf\n(\\131) Tj\nET\n"
+    ));
+    assert_eq!(pdf.fonts, vec![Font::Helvetica, Font::Symbol]);
+    assert_eq!(pdf.validate(), Ok(()));
+}
+
+#[test]
+fn test_wrap_text() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    let width = pdf.width_of("the quick brown");
+    assert_eq!(
+        pdf.wrap_text("the quick brown fox jumps\nover", width),
+        ["the quick brown", "fox jumps", "over"]
+    );
+}
+
+#[test]
+fn test_soft_hyphen_wrapping() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    let text = "a hy\u{AD}phen\u{AD}ation test";
+    let width = pdf.width_of("a hyphen-");
+    assert_eq!(pdf.wrap_text(text, width), ["a", "hyphenation", "test"]);
+    pdf.set_word_wrap_hyphenation(true);
+    assert_eq!(pdf.wrap_text(text, width), ["a hyphen-", "ation test"]);
+    // Soft hyphens where the line doesn't break are not drawn
+    assert_eq!(pdf.wrap_text(text, 1000.0), ["a hyphenation test"]);
+}
+
+#[test]
+fn test_text_fitted() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    let rect = (
+        Point { x: 10, y: 10 },
+        Size {
+            width: 60,
+            height: 20,
+        },
+    );
+    let short = pdf.draw_text_fitted(rect, Alignment::CenterCenter, "OK", 18.0);
+    let long = pdf.draw_text_fitted(rect, Alignment::CenterCenter, "A much longer label", 18.0);
+    assert!((short - 18.0).abs() < 1e-9);
+    assert!(long < short);
+    pdf.font_size = long;
+    assert!(pdf.width_of("A much longer label") <= 60.0 + 1e-9);
+}
+
+#[test]
+fn test_stroke_adjustment() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .set_stroke_adjustment(true);
+    assert!(pdf.page_buffer.ends_with(b"/GS0 gs\n"));
+    assert!(contains(
+        &pdf.to_bytes(),
+        b"/ExtGState << /GS0 << /SA true >> >>"
+    ));
+}
+
+#[test]
+fn test_rectangle_styled() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_rectangle_styled(
+        Point { x: 10, y: 10 },
+        Size {
+            width: 20,
+            height: 30,
+        },
+        Some(Color::rgb(255, 0, 0)),
+        Some((Color::rgb(0, 0, 255), 2.0)),
+    );
+    assert!(pdf
+        .page_buffer
+        .ends_with(b"1 0 0 rg\n0 0 1 SC\n2 w\n10 10 20 30 re B\n"));
+
+    pdf.draw_rectangle_styled(
+        Point { x: 10, y: 10 },
+        Size {
+            width: 20,
+            height: 30,
+        },
+        None,
+        None,
+    );
+    assert!(pdf.page_buffer.ends_with(b"re B\n"));
+}
+
+#[test]
+fn test_background_drawn_first() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .set_color(Color::rgb(255, 0, 0))
+        .draw_circle(Point { x: 50, y: 50 }, 10)
+        .draw_background(|pdf| {
+            pdf.set_color(Color::gray(128)).draw_rectangle_filled(
+                Point { x: 0, y: 0 },
+                Size {
+                    width: 100,
+                    height: 100,
+                },
+            );
+        });
+    // The red set before the background is still current afterwards
+    pdf.set_color(Color::rgb(255, 0, 0));
+    assert!(pdf.page_buffer.ends_with(b"S\n"));
+
+    let bytes = String::from_utf8_lossy(&pdf.to_bytes()).into_owned();
+    let background = bytes.find("0 0 100 100 re f").unwrap();
+    let foreground = bytes.find("50 40 m").unwrap();
+    assert!(background < foreground);
+    assert!(bytes.contains("1 j 1 J\nq\n"));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_background_keeps_font() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .font(Font::Courier, 12.0)
+    .draw_background(|pdf| {
+        pdf.font(Font::TimesBold, 30.0).draw_text(
+            Point { x: 10, y: 50 },
+            Alignment::BottomLeft,
+            "Draft",
+        );
+    });
+    assert_eq!(pdf.fonts[pdf.current_font_index], Font::Courier);
+    assert_eq!(pdf.font_size, 12.0);
+}
+
+#[test]
+fn test_windows_newlines() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text(Point { x: 10, y: 90 }, Alignment::TopLeft, "a\r\nb\rc");
+    assert_eq!(text_positions(&pdf.page_buffer).len(), 3);
+    assert!(!contains(&pdf.page_buffer, b"\\15"));
+    assert_eq!(pdf.wrap_text("a\r\nb", 100.0), ["a", "b"]);
+}
+
+#[test]
+fn test_circle_segments() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_circle_n(Point { x: 50, y: 50 }, 10, 8);
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    let curves: Vec<Vec<f64>> = stream
+        .lines()
+        .filter(|l| l.ends_with(" c"))
+        .map(|l| l.split(' ').take(6).map(|v| v.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(curves.len(), 8);
+    for curve in &curves {
+        assert!(((curve[4] - 50.0).hypot(curve[5] - 50.0) - 10.0).abs() < 1e-6);
+    }
+
+    pdf.draw_circle_n(Point { x: 50, y: 50 }, 10, 1);
+    assert_eq!(
+        String::from_utf8_lossy(&pdf.page_buffer)
+            .lines()
+            .filter(|l| l.ends_with(" c"))
+            .count(),
+        12
+    );
+}
+
+#[test]
+fn test_set_media_box() {
+    let bytes = Pdf::new()
+        .add_page(Size {
+            width: 200,
+            height: 200,
+        })
+        .draw_circle(Point { x: 60, y: 60 }, 40)
+        .set_media_box(
+            Point { x: 10, y: 10 },
+            Size {
+                width: 100,
+                height: 100,
+            },
+        )
+        .add_page_same_size()
+        .to_bytes();
+    assert!(contains(&bytes, b"/MediaBox [10 10 110 110]\n"));
+    assert!(contains(&bytes, b"/MediaBox [0 0 100 100]\n"));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_content_bounds() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    assert!(pdf.content_bounds().is_none());
+    pdf.draw_line(vec![10.0, 50.0], vec![10.0, 60.0]);
+    let (corner, size) = pdf.content_bounds().unwrap();
+    assert_eq!((corner.x, corner.y), (10.0, 10.0));
+    assert_eq!((size.width, size.height), (40.0, 50.0));
+
+    pdf.fit_page_to_content(5.0);
+    assert!(contains(&pdf.to_bytes(), b"/MediaBox [5 5 55 65]"));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_content_bounds_transformed() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    })
+    .transform(Matrix::scale(2, 2))
+    .draw_line(vec![10.0, 50.0], vec![10.0, 60.0]);
+    let (corner, size) = pdf.content_bounds().unwrap();
+    assert_eq!((corner.x, corner.y), (20.0, 20.0));
+    assert_eq!((size.width, size.height), (80.0, 100.0));
+
+    pdf.draw_raw_text(Point { x: 80, y: 5 }, b"fi\x1c");
+    let (corner, size) = pdf.content_bounds().unwrap();
+    assert_eq!((corner.x, corner.y), (20.0, 10.0));
+    assert!((corner.x + size.width - 2.0 * pdf.width_of("fifi") - 160.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_text_colored_restores_fill() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .set_color(Color::rgb(255, 0, 0))
+    .draw_text_colored(
+        Point { x: 10, y: 10 },
+        Alignment::BottomLeft,
+        Color::rgb(0, 0, 255),
+        "label",
+    )
+    // Q restored red, so setting it again is a no-op
+    .set_color(Color::rgb(255, 0, 0))
+    .draw_rectangle_filled(
+        Point { x: 0, y: 0 },
+        Size {
+            width: 10,
+            height: 10,
+        },
+    );
+    assert!(contains(&pdf.page_buffer, b"q\n0 0 1 rg\nBT\n"));
+    assert!(pdf.page_buffer.ends_with(b"ET\nQ\n0 0 10 10 re f\n"));
+}
+
+#[test]
+fn test_text_centered_block() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    })
+    .draw_text_centered_block(Point { x: 100, y: 100 }, "one\nthree\nfive");
+    let positions = text_positions(&pdf.page_buffer);
+    assert_eq!(positions.len(), 3);
+    assert!((positions[1].1 - 100.0).abs() < 1e-9);
+    assert!((positions[0].1 - 115.0).abs() < 1e-9);
+    assert!((positions[2].1 - 85.0).abs() < 1e-9);
+    let middle_width = pdf.width_of("three");
+    assert!((positions[1].0 - (100.0 - middle_width / 2.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_show_missing_glyphs() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let space = pdf.width_of(" ");
+    pdf.set_show_missing_glyphs(true);
+    assert!((pdf.width_of("a∈b") - pdf.width_of("a b")).abs() < 1e-9);
+    pdf.draw_text(Point { x: 10, y: 10 }, Alignment::BottomLeft, "a∈b");
+    assert!(contains(&pdf.page_buffer, b"(\\141\\40\\142) Tj\nET\n"));
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    let tofu: Vec<f64> = stream
+        .lines()
+        .last()
+        .unwrap()
+        .strip_suffix(" re S")
+        .unwrap()
+        .split(' ')
+        .map(|v| v.parse().unwrap())
+        .collect();
+    let a = pdf.width_of("a");
+    assert!((tofu[0] - (10.0 + a + space * 0.15)).abs() < 1e-6);
+    assert!((tofu[2] - space * 0.7).abs() < 1e-6);
+}
+
+#[test]
+fn test_dots_sized_ignore_scale() {
+    // The radius of the dot drawn on the page, from the XObject and the page's scale
+    let page_radius = |scale: f64, radius: f64| {
+        let mut pdf = Pdf::new();
+        pdf.add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .transform(Matrix::scale(scale, scale))
+        .draw_dots_sized(&[10.0], &[10.0], radius);
+        let dot = String::from_utf8_lossy(&pdf.objects.last().unwrap().contents).into_owned();
+        let start: f64 = dot
+            .lines()
+            .find(|l| l.ends_with(" m"))
+            .unwrap()
+            .split(' ')
+            .nth(1)
+            .unwrap()
+            .parse()
+            .unwrap();
+        -start * scale
+    };
+    assert!((page_radius(1.0, 3.0) - 3.0).abs() < 1e-9);
+    assert!((page_radius(2.0, 3.0) - 3.0).abs() < 1e-9);
+    assert!((page_radius(2.0, 1.5) - page_radius(1.0, 1.5)).abs() < 1e-9);
+}
+
+#[test]
+fn test_clear() {
+    let build = |pdf: &mut Pdf| {
+        pdf.add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_circle(Point { x: 50, y: 50 }, 10)
+        .to_bytes()
+    };
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off);
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .font(Font::Courier, 10)
+    .draw_dots(&[1.0; 100], &[1.0; 100]);
+    let capacity = pdf.page_buffer.capacity();
+    pdf.clear();
+    assert_eq!(pdf.page_buffer.capacity(), capacity);
+    assert_eq!(pdf.objects.len(), 2);
+    assert_eq!(build(&mut pdf), build(&mut Pdf::new()));
+}
+
+#[test]
+fn test_text_rtl() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text_rtl(Point { x: 90, y: 50 }, "abc");
+    let positions = text_positions(&pdf.page_buffer);
+    assert_eq!(positions.len(), 3);
+    assert!(positions[0].0 > positions[1].0 && positions[1].0 > positions[2].0);
+    assert!((positions[0].0 - (90.0 - pdf.width_of("a"))).abs() < 1e-9);
+    assert!((positions[2].0 - (90.0 - pdf.width_of("abc"))).abs() < 1e-9);
+}
+
+#[test]
+fn test_default_page_size() {
+    let bytes = Pdf::new()
+        .set_default_page_size(Size {
+            width: 612,
+            height: 792,
+        })
+        .add_default_page()
+        .add_default_page()
+        .to_bytes();
+    assert_eq!(
+        bytes
+            .windows(22)
+            .filter(|w| w == b"/MediaBox [0 0 612 792")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_image_streaming() {
+    let pixels: Vec<u8> = (0..4 * 3 * 3).collect();
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .add_image_streaming(
+        io::Cursor::new(&pixels),
+        4,
+        3,
+        ColorSpace::Rgb,
+        Point { x: 10, y: 20 },
+    )
+    .unwrap();
+    assert!(pdf.page_buffer.ends_with(b"q 4 0 0 3 10 20 cm /X0 Do Q\n"));
+
+    let object = &pdf.objects.last().unwrap().contents;
+    assert!(contains(
+        object,
+        b"/Width 4 /Height 3 /ColorSpace /DeviceRGB"
+    ));
+    let start = object.windows(7).position(|w| w == b"stream\n").unwrap() + 7;
+    let end = object.len() - b"\nendstream\n".len();
+    assert_eq!(
+        inflate::inflate_bytes_zlib(&object[start..end]).unwrap(),
+        pixels
+    );
+
+    let short = pdf.add_image_streaming(
+        io::Cursor::new(&pixels),
+        5,
+        3,
+        ColorSpace::Rgb,
+        Point { x: 0, y: 0 },
+    );
+    assert_eq!(short.err().unwrap().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_current_style_accessors() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .set_color(Color::rgb(10, 20, 30))
+    .set_line_width(2.5)
+    .font(Font::Courier, 14);
+    assert_eq!(pdf.current_fill_color(), Color::rgb(10, 20, 30));
+    assert_eq!(pdf.current_stroke_color(), Color::rgb(10, 20, 30));
+    assert_eq!(pdf.current_line_width(), 2.5);
+    assert_eq!(pdf.current_font(), (&Font::Courier, 14.0));
+
+    pdf.set_stroke_color(Color::gray(128)).draw_text_colored(
+        Point { x: 10, y: 10 },
+        Alignment::TopLeft,
+        Color::gray(200),
+        "hi",
+    );
+    assert_eq!(pdf.current_fill_color(), Color::rgb(10, 20, 30));
+    assert_eq!(pdf.current_stroke_color(), Color::gray(128));
+}
+
+#[test]
+fn test_sparkline() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    pdf.draw_sparkline(
+        (
+            Point { x: 10, y: 10 },
+            Size {
+                width: 50,
+                height: 10,
+            },
+        ),
+        &[3.0, 1.0, 4.0, 1.0, 5.0],
+        Color::gray(0),
+    );
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    assert_eq!(stream.lines().filter(|l| l.ends_with(" m")).count(), 1);
+    assert_eq!(stream.lines().filter(|l| l.ends_with(" l")).count(), 4);
+    assert_eq!(stream.matches(" Do ").count(), 2);
+    assert!(contains(&pdf.page_buffer, b"22.5 10 cm"));
+    assert!(contains(&pdf.page_buffer, b"60 20 cm"));
+
+    let before = pdf.page_buffer.len();
+    pdf.draw_sparkline(
+        (
+            Point { x: 10, y: 10 },
+            Size {
+                width: 50,
+                height: 10,
+            },
+        ),
+        &[],
+        Color::gray(0),
+    );
+    assert_eq!(pdf.page_buffer.len(), before);
+}
+
+#[test]
+fn test_ligatures() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .font(Font::TimesRoman, 10);
+    let separate = pdf.width_of("fi");
+    pdf.enable_ligatures(true);
+    let ligature = pdf.width_of("fi");
+    assert!((ligature - fonts::glyph_width(&Font::TimesRoman, '\u{FB01}') * 10.0).abs() < 1e-9);
+    assert!(ligature < separate);
+
+    pdf.draw_text(Point { x: 10, y: 10 }, Alignment::TopLeft, "fix ffi");
+    // "ffi" has no glyph of its own, so it falls back to "f" followed by "fi"
+    assert!(contains(&pdf.page_buffer, b"(\\34\\170\\40\\146\\34) Tj"));
+}
+
+#[test]
+fn test_current_page_stream() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .move_to(Point { x: 10, y: 20 });
+    assert!(contains(pdf.current_page_stream(), b"10 20 m\n"));
+}
+
+#[test]
+fn test_draw_text_clamped() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    let truncated = pdf.draw_text_clamped(
+        Point { x: 10, y: 190 },
+        Alignment::TopLeft,
+        "first line\nsecond line\nthird line",
+        100.0,
+        2,
+    );
+    assert!(truncated);
+    assert_eq!(text_positions(&pdf.page_buffer).len(), 2);
+    // "second line…" ends with the WinAnsi ellipsis
+    assert!(contains(&pdf.page_buffer, b"\\145\\205) Tj"));
+
+    let truncated = pdf.draw_text_clamped(
+        Point { x: 10, y: 100 },
+        Alignment::TopLeft,
+        "short",
+        100.0,
+        2,
+    );
+    assert!(!truncated);
+}
+
+#[test]
+fn test_rectangle_corners() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_rectangle_corners(Point { x: 10, y: 20 }, Point { x: 40, y: 60 })
+    .draw_rectangle_corners(Point { x: 40, y: 60 }, Point { x: 10, y: 20 })
+    .draw_rectangle_corners(Point { x: 10, y: 60 }, Point { x: 40, y: 20 })
+    .draw_rectangle_corners_filled(Point { x: 40, y: 20 }, Point { x: 10, y: 60 });
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    assert_eq!(stream.matches("10 20 30 40 re S\n").count(), 3);
+    assert!(stream.contains("10 20 30 40 re f\n"));
+}
+
+#[test]
+fn test_per_page_compression() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .compression(Compression::Fast)
+        .draw_line(vec![10.0, 90.0], vec![10.0, 90.0])
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_line(vec![10.0, 90.0], vec![10.0, 90.0]);
+    pdf.to_bytes();
+    let streams: Vec<&[u8]> = pdf
+        .objects
+        .iter()
+        .map(|object| &object.contents[..])
+        .filter(|contents| contents.starts_with(b"<< /Length"))
+        .collect();
+    // The first page was started uncompressed, even though compression was turned on before
+    // anything was drawn on it
+    assert!(!contains(streams[0], b"/FlateDecode"));
+    assert!(contains(streams[streams.len() - 1], b"/FlateDecode"));
+}
+
+#[test]
+fn test_lzw_page_stream() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Lzw)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 90 }, Alignment::TopLeft, "Hello, LZW")
+        .draw_line(vec![10.0, 90.0], vec![10.0, 90.0]);
+    let page = pdf.page_buffer.clone();
+    pdf.to_bytes();
+    let stream = &pdf.objects.iter().find(|o| o.id == 3).unwrap().contents;
+    assert!(stream.starts_with(b"<< /Length"));
+    assert!(contains(stream, b"/Filter [/LZWDecode]"));
+    let start = stream.windows(7).position(|w| w == b"stream\n").unwrap() + 7;
+    let end = stream.len() - b"endstream\n".len();
+    assert_eq!(lzw::decompress(&stream[start..end]).unwrap(), page);
+}
+
+#[test]
+fn test_ascii85_page_stream() {
+    let mut pdf = Pdf::new();
+    pdf.set_ascii_filter(AsciiFilter::Ascii85)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 90 }, Alignment::TopLeft, "Hello, ASCII85")
+        .draw_line(vec![10.0, 90.0], vec![10.0, 90.0]);
+    let page = pdf.page_buffer.clone();
+    pdf.to_bytes();
+    let stream = &pdf.objects.iter().find(|o| o.id == 3).unwrap().contents;
+    assert!(contains(
+        stream,
+        b"/Filter [/ASCII85Decode /FlateDecode] >>\nstream\n"
+    ));
+    let start = stream.windows(7).position(|w| w == b"stream\n").unwrap() + 7;
+    let end = stream.len() - b"endstream\n".len();
+    let encoded = &stream[start..end];
+    assert!(encoded.iter().all(|&c| c == b'\n' || c.is_ascii_graphic()));
+    let compressed = ascii::ascii85_decode(encoded);
+    assert_eq!(inflate::inflate_bytes_zlib(&compressed).unwrap(), page);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_draw_tabular() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    })
+    .font(Font::Helvetica, 10)
+    .draw_tabular(
+        Point { x: 10, y: 190 },
+        &[0.0, 50.0],
+        &["Name\tValue", "Extra\tfields\tgo\there"],
+    );
+    let positions = text_positions(&pdf.page_buffer);
+    assert_eq!(
+        positions,
+        vec![(10.0, 180.0), (60.0, 180.0), (10.0, 170.0), (60.0, 170.0)]
+    );
+    assert!(contains(&pdf.page_buffer, b"\\147\\157\\40\\150"));
+}
+
+#[test]
+fn test_module_grid() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let start = pdf.page_buffer.len();
+    let checkerboard: Vec<Vec<bool>> = (0..3)
+        .map(|r| (0..3).map(|c| (r + c) % 2 == 0).collect())
+        .collect();
+    pdf.draw_module_grid(Point { x: 10, y: 40 }, 10.0, &checkerboard, Color::gray(0));
+    let stream = String::from_utf8_lossy(&pdf.page_buffer[start..]);
+    let rectangles: Vec<&str> = stream.lines().filter(|l| l.ends_with(" re")).collect();
+    assert_eq!(
+        rectangles,
+        vec![
+            "10 30 10 10 re",
+            "30 30 10 10 re",
+            "20 20 10 10 re",
+            "10 10 10 10 re",
+            "30 10 10 10 re"
+        ]
+    );
+    assert_eq!(stream.lines().filter(|l| *l == "f").count(), 1);
+
+    let start = pdf.page_buffer.len();
+    pdf.draw_module_grid(
+        Point { x: 0, y: 10 },
+        1.0,
+        &[vec![true, true, false, true, true, true]],
+        Color::gray(0),
+    );
+    let stream = String::from_utf8_lossy(&pdf.page_buffer[start..]);
+    assert!(stream.contains("0 9 2 1 re\n3 9 3 1 re\nf\nQ\n"));
+
+    pdf.draw_module_grid(
+        Point { x: 0, y: 10 },
+        1.0,
+        &[vec![true]],
+        Color::rgb(255, 0, 0),
+    );
+    assert_eq!(pdf.current_fill_color(), Color::gray(0));
+    assert_eq!(pdf.validate(), Ok(()));
+}
+
+#[test]
+fn test_text_knockout() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off)
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .set_text_knockout(false);
+    assert!(pdf.page_buffer.ends_with(b"/GS0 gs\n"));
+    assert!(contains(
+        &pdf.to_bytes(),
+        b"/ExtGState << /GS0 << /TK false >> >>"
+    ));
+}
+
+#[test]
+fn test_image_color_key() {
+    let pixels = [255_u8, 255, 255, 200, 0, 0];
+    let image = Image::new(&pixels, 2, 1).with_color_key((250, 255), (250, 255), (250, 255));
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .add_image_at(image, Point { x: 10, y: 10 });
+    assert!(contains(&pdf.page_buffer, b"q 2 0 0 1 10 10 cm /X0 Do Q\n"));
+    let object = &pdf.objects.last().unwrap().contents;
+    assert!(contains(object, b"/Mask [250 255 250 255 250 255] "));
+}
+
+#[test]
+fn test_baseline_grid() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 400,
+        height: 400,
+    })
+    .set_baseline_grid(12.0, 0.0);
+    pdf.draw_text_run(Point { x: 10, y: 305 }, "snapped");
+    pdf.set_baseline_grid(12.0, 5.0).draw_text(
+        Point { x: 10, y: 320 },
+        Alignment::TopLeft,
+        "offset",
+    );
+    pdf.set_baseline_grid(0.0, 0.0)
+        .draw_raw_text(Point { x: 10, y: 305 }, b"free");
+    assert_eq!(
+        text_positions(&pdf.page_buffer),
+        vec![(10.0, 300.0), (10.0, 305.0), (10.0, 305.0)]
+    );
+}
+
+#[test]
+fn test_baseline_grid_wider_than_lines() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 400,
+        height: 400,
+    })
+    .set_baseline_grid(30.0, 0.0)
+    .draw_text(
+        Point { x: 10, y: 312 },
+        Alignment::TopLeft,
+        "one\ntwo\nthree",
+    );
+    assert_eq!(
+        text_positions(&pdf.page_buffer),
+        vec![(10.0, 300.0), (10.0, 270.0), (10.0, 240.0)]
+    );
+}
+
+#[test]
+fn test_text_outline_width() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .set_line_width(2)
+    .set_text_render_mode(TextRenderMode::Stroke)
+    .set_text_outline_width(Some(0.5))
+    .draw_text(Point { x: 10, y: 90 }, Alignment::TopLeft, "a");
+    assert!(contains(
+        &pdf.page_buffer,
+        b"1 Tr\nBT\n0.5 w\n/F0 12 Tf\n1 0 0 1 10 78\nTm (\\141) Tj\nET\n2 w\n"
+    ));
+}
+
+#[test]
+fn test_draw_dots_weighted() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_dots_weighted(&[10.0, 20.0, 30.0], &[40.0, 50.0, 60.0], &[1.0, 2.0, 3.0]);
+    assert!(contains(
+        &pdf.page_buffer,
+        b"q 1 0 0 1 10 40 cm /X0 Do Q\n\
+          q 2 0 0 2 20 50 cm /X0 Do Q\n\
+          q 3 0 0 3 30 60 cm /X0 Do Q\n"
+    ));
+}
+
+#[test]
+fn test_draw_dots_colored() {
+    let red = Color::rgb(255, 0, 0);
+    let blue = Color::rgb(0, 0, 255);
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_dots_colored(
+        &[10.0, 20.0, 30.0, 40.0],
+        &[10.0, 20.0, 30.0, 40.0],
+        &[red, blue, red, blue],
+    );
+    let stream = String::from_utf8_lossy(&pdf.page_buffer);
+    assert_eq!(stream.matches(" rg\n").count(), 2);
+    assert_eq!(stream.matches(" /X0 Do Q\n").count(), 4);
+    assert_eq!(pdf.current_fill_color(), Color::gray(0));
+}
+
+#[test]
+fn test_heatmap() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let start = pdf.page_buffer.len();
+    pdf.draw_heatmap(
+        Point { x: 0, y: 20 },
+        Size {
+            width: 10,
+            height: 10,
+        },
+        &[vec![0.1, 0.2], vec![0.3, 0.9]],
+        |value| {
+            if value < 0.5 {
+                Color::gray(0)
+            } else {
+                Color::gray(255)
+            }
+        },
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&pdf.page_buffer[start..]),
+        "q\n0 0 0 rg\n0 10 20 10 re\n0 0 10 10 re\nf\n1 1 1 rg\n10 0 10 10 re\nf\nQ\n"
+    );
+}
+
+#[test]
+fn test_rendering_intent() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .set_rendering_intent(RenderingIntent::Perceptual);
+    assert!(pdf.page_buffer.ends_with(b"\n/Perceptual ri\n"));
+}
+
+#[test]
+fn test_draw_text_with_positions() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .font(Font::Courier, 10);
+    let offsets = pdf.draw_text_with_positions(Point { x: 10, y: 50 }, "abcd");
+    let advance = pdf.width_of("a");
+    assert_eq!(offsets.len(), 4);
+    for (i, offset) in offsets.iter().enumerate() {
+        assert!((offset - i as f64 * advance).abs() < 1e-9);
+    }
+    assert_eq!(text_positions(&pdf.page_buffer), vec![(10.0, 50.0)]);
+}
+
+#[test]
+fn test_draw_text_with_positions_ligatures() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .enable_ligatures(true);
+    let offsets = pdf.draw_text_with_positions(Point { x: 10, y: 50 }, "fix");
+    let ligature = pdf.width_of("\u{FB01}");
+    let expected = [0.0, ligature / 2.0, ligature];
+    assert_eq!(offsets.len(), expected.len());
+    for (offset, expected) in offsets.iter().zip(&expected) {
+        assert!((offset - expected).abs() < 1e-9, "{:?}", offsets);
+    }
+    assert!((pdf.width_of("fix") - ligature - pdf.width_of("x")).abs() < 1e-9);
+}
+
+#[test]
+fn test_set_version() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    assert!(pdf.to_bytes().starts_with(b"%PDF-1.7\n"));
+
+    let bytes = pdf.set_version(2, 0).to_bytes();
+    assert!(bytes.starts_with(b"%PDF-2.0\n"));
+    assert!(contains(&bytes, b"/Pages 2 0 R\n/Version /2.0 >>"));
+
+    let bytes = pdf.set_version(1, 4).to_bytes();
+    assert!(bytes.starts_with(b"%PDF-1.4\n"));
+    assert!(!contains(&bytes, b"/Version"));
+}
+
+#[test]
+fn test_progress_callback() {
+    use std::sync::{Arc, Mutex};
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let mut pdf = Pdf::new();
+    let sink = Arc::clone(&reports);
+    pdf.set_progress_callback(move |progress| sink.lock().unwrap().push(progress));
+    for _ in 0..3 {
+        pdf.add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_dots(&[10.0], &[10.0]);
+    }
+    assert_eq!(reports.lock().unwrap().len(), 2);
+    pdf.to_bytes();
+    let reports = reports.lock().unwrap().clone();
+    let pages: Vec<usize> = reports.iter().map(|report| report.pages).collect();
+    assert_eq!(pages, vec![1, 2, 3]);
+    assert!(reports.iter().all(|report| report.content_bytes > 0));
+    // A document with a callback can still be shared between threads
+    let _: &dyn Sync = &pdf;
+}
+
+#[test]
+fn test_rectangle_rounded_corners() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.draw_rectangle_rounded_corners(
+        Point { x: 10, y: 10 },
+        Size {
+            width: 40,
+            height: 20,
+        },
+        [5.0, 0.0, 0.0, 50.0],
+    );
+    let stream = String::from_utf8_lossy(pdf.current_page_stream()).into_owned();
+    let curves = stream.lines().filter(|l| l.ends_with(" c")).count();
+    assert_eq!(curves, 2);
+    // The top-right and bottom-right corners are sharp
+    assert!(stream.contains("50 30 l\n"));
+    assert!(stream.contains("50 10 l\n"));
+    // The bottom-left radius is clamped to half the height
+    assert!(stream.contains("10 20 c\n"));
+    assert!(stream.ends_with("s\n"));
+}
+
+#[test]
+fn test_set_font_str() {
+    let mut pdf = Pdf::new();
+    pdf.set_font_str("Helvetica-Bold", 14.0).unwrap();
+    assert_eq!(pdf.current_font(), (&Font::HelveticaBold, 14.0));
+    pdf.set_font_str("ZapfDingbats", 10.0).unwrap();
+    assert_eq!(pdf.current_font(), (&Font::ZapfDingbats, 10.0));
+    assert_eq!(
+        pdf.set_font_str("Comic Sans", 12.0).map(|_| ()),
+        Err(UnknownFontError("Comic Sans".to_string()))
+    );
+    assert_eq!(pdf.current_font(), (&Font::ZapfDingbats, 10.0));
+}
+
+#[test]
+fn test_draw_text_smallcaps() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 100,
+    });
+    pdf.font(Font::Helvetica, 10.0);
+    pdf.draw_text_smallcaps(Point { x: 10, y: 50 }, Alignment::BottomLeft, "Hello");
+    let stream = String::from_utf8_lossy(pdf.current_page_stream()).into_owned();
+    let sizes: Vec<&str> = stream.lines().filter(|l| l.ends_with(" Tf")).collect();
+    assert_eq!(sizes, ["/F0 10 Tf", "/F0 8 Tf"]);
+    // The lowercase letters are drawn as capitals
+    assert!(stream.contains("(\\110) Tj\n/F0 8 Tf\n(\\105\\114\\114\\117) Tj\n"));
+    assert_eq!(pdf.current_font(), (&Font::Helvetica, 10.0));
+}
+
+#[test]
+fn test_repeated_image_embedded_once() {
+    let pixels = [255_u8, 0, 0, 0, 0, 255];
+    let mut pdf = Pdf::new();
+    for _ in 0..2 {
+        pdf.add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .add_image_at(Image::new(&pixels, 2, 1), Point { x: 10, y: 10 })
+        .add_image_at(Image::new(&pixels, 2, 1), Point { x: 50, y: 10 });
+    }
+    let bytes = pdf.to_bytes();
+    let images = bytes
+        .windows(b"/Subtype /Image".len())
+        .filter(|w| w == b"/Subtype /Image")
+        .count();
+    assert_eq!(images, 1);
+    // Each page names the image once in its resources
+    let resources = bytes
+        .windows(b"/XObject << /X0 ".len())
+        .filter(|w| w == b"/XObject << /X0 ")
+        .count();
+    assert_eq!(resources, 2);
+    assert!(!contains(&bytes, b"/X1"));
+}
+
+#[test]
+fn test_strike_through_scales_with_font_size() {
+    let strike_y = |size: f64| {
+        let mut pdf = Pdf::new();
+        pdf.add_page(Size {
+            width: 200,
+            height: 100,
+        });
+        pdf.font(Font::TimesRoman, size).draw_text_decorated(
+            Point { x: 10, y: 0 },
+            Alignment::BottomLeft,
+            "Struck",
+            TextDecoration::StrikeThrough,
+        );
+        let stream = String::from_utf8_lossy(pdf.current_page_stream()).into_owned();
+        let rect: Vec<f64> = stream
+            .lines()
+            .find(|l| l.ends_with(" re f"))
+            .unwrap()
+            .split(' ')
+            .take(4)
+            .map(|v| v.parse().unwrap())
+            .collect();
+        // The middle of the line
+        rect[1] + rect[3] / 2.0
+    };
+    let small = strike_y(10.0);
+    let large = strike_y(20.0);
+    assert!((small - 0.45 / 2.0 * 10.0).abs() < 1e-9, "{}", small);
+    assert!((large - 2.0 * small).abs() < 1e-9, "{} {}", small, large);
+}
+
+#[test]
+fn test_image_reused_after_discarded_page() {
+    let pixels = [255_u8, 0, 0];
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .add_image_at(Image::new(&pixels, 1, 1), Point { x: 10, y: 10 })
+    .discard_current_page();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .add_image_at(Image::new(&pixels, 1, 1), Point { x: 10, y: 10 });
+    let bytes = pdf.to_bytes();
+    assert!(contains(&bytes, b"/Subtype /Image"));
+}
+
+#[test]
+fn test_image_hash_collision_embeds_both() {
+    use std::hash::{Hash, Hasher};
+
+    let red = [255_u8, 0, 0];
+    let blue = [0_u8, 0, 255];
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .add_image_at(Image::new(&red, 1, 1), Point { x: 10, y: 10 });
+    // File the red image under the blue image's hash, as if the two hashes collided
+    let blue_image = Image::new(&blue, 1, 1);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (
+        &blue_image.buf,
+        blue_image.width,
+        blue_image.height,
+        blue_image.color_key,
+    )
+        .hash(&mut hasher);
+    let embedded: Vec<_> = pdf.image_ids.drain().flat_map(|(_, ids)| ids).collect();
+    pdf.image_ids.insert(hasher.finish(), embedded);
+    pdf.add_image_at(blue_image, Point { x: 50, y: 10 });
+    assert!(contains(&pdf.page_buffer, b"/X0 Do"));
+    assert!(contains(&pdf.page_buffer, b"/X1 Do"));
+}
+
+#[test]
+fn test_push_and_pop_clip() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.push_clip(
+        Point { x: 10, y: 10 },
+        Size {
+            width: 50,
+            height: 50,
+        },
+    )
+    .set_color(Color::rgb(255, 0, 0))
+    .draw_circle_filled(Point { x: 10, y: 10 }, 20)
+    .pop_clip();
+    let stream = String::from_utf8_lossy(pdf.current_page_stream()).into_owned();
+    assert!(stream.contains("q\n10 10 50 50 re W n\n"));
+    assert!(stream.ends_with("Q\n"));
+    assert_eq!(pdf.validate(), Ok(()));
+    assert_eq!(pdf.current_fill_color(), Color::rgb(0, 0, 0));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_pop_clip_restores_graphics_state() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let width = pdf.width_of("hello");
+    pdf.push_clip(
+        Point { x: 10, y: 10 },
+        Size {
+            width: 50,
+            height: 50,
+        },
+    )
+    .set_horizontal_scale(50.0)
+    .set_alpha(0.5)
+    .set_dash(&[2.0, 1.0], 0.0)
+    .transform(Matrix::uniform_scale(2.0))
+    .pop_clip();
+    assert_eq!(pdf.width_of("hello"), width);
+    assert_eq!(pdf.alpha, 1.0);
+    assert!(pdf.dash.0.is_empty());
+    assert_eq!(pdf.ctm.v, Matrix::uniform_scale(1.0).v);
+}
+
+#[test]
+fn test_draw_path() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let start = pdf.current_page_stream().len();
+    pdf.draw_path(&[(10.0, 10.0), (50.0, 90.0), (90.0, 10.0)]);
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    assert_eq!(stream, "10 10 m\n50 90 l\n90 10 l\nS\n");
+
+    pdf.fill_path(&[(10.0, 10.0), (50.0, 90.0), (90.0, 10.0)]);
+    assert!(pdf.current_page_stream().ends_with(b"90 10 l\nf\n"));
+}
+
+#[test]
+fn test_page_resources_include_proc_set() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let bytes = pdf.to_bytes();
+    assert!(contains(
+        &bytes,
+        b"/Resources <<\n  /ProcSet [/PDF /Text /ImageB /ImageC /ImageI]\n"
+    ));
+}
+
+#[test]
+fn test_draw_markup() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 100,
+    });
+    pdf.font(Font::Helvetica, 10.0);
+    let start = pdf.current_page_stream().len();
+    pdf.draw_markup(Point { x: 10, y: 50 }, "ab {color:#f00}c{/}d {oops}");
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    let colors: Vec<&str> = stream.lines().filter(|l| l.ends_with(" rg")).collect();
+    assert_eq!(colors, ["1 0 0 rg", "0 0 0 rg"]);
+    // The red text starts where the text before it ends
+    let x = 10.0 + pdf.width_of("ab ");
+    assert!(stream.contains(&format!("1 0 0 1 {x} 50\nTm (\\143) Tj")));
+    // Unknown tags are drawn as text
+    assert_eq!(stream.matches(" Tj").count(), 3);
+    assert!(stream.contains("(\\144\\40\\173\\157\\157\\160\\163\\175) Tj"));
+    assert_eq!(pdf.current_fill_color(), Color::rgb(0, 0, 0));
+    assert_eq!(pdf.current_font(), (&Font::Helvetica, 10.0));
+}
+
+#[test]
+fn test_internal_links() {
+    let mut pdf = Pdf::new();
+    let size = Size {
+        width: 100,
+        height: 100,
+    };
+    let first = pdf.add_page_handle(size);
+    pdf.draw_text(Point { x: 10, y: 90 }, Alignment::TopLeft, "Top");
+    let second = pdf.add_page_handle(size);
+    let link_area = (
+        Point { x: 10, y: 10 },
+        Size {
+            width: 30,
+            height: 12,
+        },
+    );
+    // The second page is still in progress when it is linked to
+    pdf.add_internal_link(link_area, first)
+        .add_internal_link(link_area, second);
+    let bytes = pdf.to_bytes();
+    let (first_id, second_id) = (pdf.page_order[0], pdf.page_order[1]);
+    let link = |id: usize| {
+        format!(
+            "<< /Type /Annot /Subtype /Link /Rect [10 10 40 22] /Border [0 0 0] \
+             /Dest [{id} 0 R /Fit] >>"
+        )
+    };
+    assert!(contains(&bytes, link(first_id).as_bytes()));
+    assert!(contains(&bytes, link(second_id).as_bytes()));
+    assert!(contains(&bytes, b"/Annots ["));
+}
+
+#[test]
+fn test_font_ascent_and_descent() {
+    let mut pdf = Pdf::new();
+    pdf.font(Font::Helvetica, 12.0);
+    assert!((pdf.font_ascent() - 718.0 * 12.0 / 1000.0).abs() < 1e-9);
+    assert!((pdf.font_descent() - -207.0 * 12.0 / 1000.0).abs() < 1e-9);
+    // Symbol has no ascender, so its bounding box is used instead
+    pdf.font(Font::Symbol, 10.0);
+    assert!((pdf.font_ascent() - 10.1).abs() < 1e-9);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_font_metrics() {
+    let times = font_metrics(&Font::TimesRoman);
+    // CapHeight 662 in Times-Roman.afm
+    assert_eq!(times.cap_height, Some(0.662));
+    assert_eq!(times.x_height, Some(0.45));
+    assert_eq!(times.ascender, 0.683);
+    assert_eq!(times.descender, -0.217);
+    assert_eq!(times.bbox, [-0.168, -0.218, 1.0, 0.898]);
+    assert_eq!(font_metrics(&Font::ZapfDingbats).cap_height, None);
+}
+
+#[test]
+fn test_draw_text_justified() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 300,
+        height: 100,
+    });
+    pdf.font(Font::Helvetica, 10.0);
+    let text = "Justified words";
+    let width = pdf.width_of(text) + 4.0;
+    let start = pdf.current_page_stream().len();
+    pdf.draw_text_justified(Point { x: 10, y: 50 }, text, width);
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    assert!(stream.starts_with("4 Tw\n"), "{}", stream);
+    assert!(stream.ends_with("0 Tw\n"));
+
+    // A long word has no spaces to stretch
+    let text = "Antidisestablishmentarianism";
+    let width = pdf.width_of(text) + 54.0;
+    let start = pdf.current_page_stream().len();
+    pdf.draw_text_justified(Point { x: 10, y: 30 }, text, width);
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    assert!(stream.starts_with("2 Tc\n"), "{}", stream);
+    assert!(!stream.contains("Tw"));
+}
+
+#[cfg(test)]
+fn embedded_font_program(pdf: &Pdf) -> Vec<u8> {
+    let object = pdf
+        .objects
+        .iter()
+        .find(|object| object.contents.starts_with(b"<< /Length1 "))
+        .unwrap();
+    // Streams are written uncompressed in these tests, so the length is the same
+    let contents = String::from_utf8_lossy(&object.contents);
+    let length: usize = contents[12..].split(' ').next().unwrap().parse().unwrap();
+    let start = object
+        .contents
+        .windows(7)
+        .position(|w| w == b"stream\n")
+        .unwrap()
+        + 7;
+    object.contents[start..start + length].to_vec()
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_embed_font() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off);
+    let font = pdf.embed_font(truetype::test_font()).unwrap();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text_embedded(Point { x: 10, y: 20 }, font, 10.0, "CAB");
+    assert!(contains(
+        pdf.current_page_stream(),
+        b"/E0 10 Tf\n1 0 0 1 10 20\nTm <000300010002> Tj\n"
+    ));
+    assert_eq!(pdf.width_of_embedded(font, 10.0, "CAB"), 18.0);
+    assert_eq!(pdf.validate(), Ok(()));
+
+    let bytes = pdf.to_bytes();
+    assert!(contains(&bytes, b"/E0 "));
+    assert!(contains(&bytes, b"/BaseFont /TestA\n"));
+    assert!(contains(&bytes, b"/W [ 1 [600 600 600] ]\n"));
+    assert!(contains(&bytes, b"/CIDToGIDMap /Identity"));
+    assert!(contains(&bytes, b"<0003> <0043>\n"));
+    assert!(embedded_font_program(&pdf) == truetype::test_font());
+
+    assert!(pdf.embed_font(b"not a font".to_vec()).is_err());
+}
+
+#[test]
+fn test_embed_font_subset() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off);
+    let font = pdf.embed_font_subset(truetype::test_font()).unwrap();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text_embedded(Point { x: 10, y: 20 }, font, 10.0, "CZ");
+    // Glyphs from a discarded page are left out
+    pdf.add_page_same_size()
+        .draw_text_embedded(Point { x: 10, y: 20 }, font, 10.0, "M")
+        .discard_current_page();
+    pdf.add_page_same_size()
+        .draw_text_embedded(Point { x: 10, y: 20 }, font, 10.0, "AC");
+
+    let bytes = pdf.to_bytes();
+    assert!(contains(&bytes, b"/W [ 1 [600] 3 [600] 26 [600] ]\n"));
+    assert!(!contains(&bytes, b"<000D>"));
+    let program = embedded_font_program(&pdf);
+    assert!(program.len() < truetype::test_font().len());
+    let subset = truetype::TrueTypeFont::parse(program).unwrap();
+    assert_eq!(subset.glyph_id('A'), 1);
+    assert_eq!(subset.glyph_id('C'), 2);
+    assert_eq!(subset.glyph_id('Z'), 3);
+    assert_eq!(subset.glyph_id('M'), 0);
+    assert!(contains(&bytes, b"+TestA\n"));
+    // The map from the glyph ids in the content to the subset's is 27 entries long
+    assert!(contains(&bytes, b"<< /Length 54 >>\nstream\n"));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_embedded_font_in_parallel_pages() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off);
+    let font = pdf.embed_font_subset(truetype::test_font()).unwrap();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text_embedded(Point { x: 10, y: 20 }, font, 10.0, "A");
+    pdf.render_pages_parallel(&["B", "C"], |page, text| {
+        page.draw_text_embedded(Point { x: 10, y: 20 }, font, 10.0, text);
+    });
+    let bytes = pdf.to_bytes();
+    assert!(contains(&bytes, b"/W [ 1 [600 600 600] ]\n"));
+    assert_eq!(
+        String::from_utf8_lossy(&bytes).matches("/E0 ").count(),
+        6,
+        "each page should use the font and name it in its resources"
+    );
+}
+
+#[test]
+fn test_append_embedded_font() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let mut other = Pdf::new();
+    let font = other.embed_font_subset(truetype::test_font()).unwrap();
+    other
+        .add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text_embedded(Point { x: 10, y: 20 }, font, 10.0, "A");
+    pdf.append(other);
+
+    let font = pdf
+        .objects
+        .iter()
+        .find(|object| contains(&object.contents, b"/Subtype /Type0"))
+        .unwrap();
+    let contents = String::from_utf8_lossy(&font.contents);
+    let descendant: usize = contents
+        .split("/DescendantFonts [")
+        .nth(1)
+        .and_then(|rest| rest.split(' ').next())
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(pdf
+        .objects
+        .iter()
+        .any(|object| object.id == descendant
+            && contains(&object.contents, b"/Subtype /CIDFontType2")));
+}
+
+#[test]
+fn test_draw_rectangle_shadowed() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 200,
+    });
+    let start = pdf.current_page_stream().len();
+    pdf.draw_rectangle_shadowed(
+        (
+            Point { x: 50, y: 50 },
+            Size {
+                width: 80,
+                height: 40,
+            },
+        ),
+        Color::gray(255),
+        Color::gray(0),
+        Point { x: 2.0, y: -2.0 },
+        3,
+    );
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    let rects: Vec<&str> = stream.lines().filter(|l| l.ends_with(" re f")).collect();
+    assert_eq!(
+        rects,
+        [
+            "49 45 86 46 re f",
+            "50 46 84 44 re f",
+            "51 47 82 42 re f",
+            "52 48 80 40 re f",
+            "50 50 80 40 re f",
+        ]
+    );
+    let states: Vec<&str> = stream.lines().filter(|l| l.ends_with(" gs")).collect();
+    assert_eq!(
+        states,
+        ["/GS0 gs", "/GS1 gs", "/GS2 gs", "/GS3 gs", "/GS3 gs"]
+    );
+    assert_eq!(
+        pdf.ext_g_states,
+        [
+            b"/CA 0.25 /ca 0.25".to_vec(),
+            b"/CA 0.5 /ca 0.5".to_vec(),
+            b"/CA 0.75 /ca 0.75".to_vec(),
+            b"/CA 1 /ca 1".to_vec(),
+        ]
+    );
+    assert!(stream.ends_with("Q\n"));
+}
+
+#[test]
+fn test_draw_footer() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 400,
+        height: 300,
+    });
+    pdf.font(Font::Courier, 10.0);
+    let start = pdf.current_page_stream().len();
+    pdf.draw_footer(20.0, "Report", "Quarterly", "Page 3");
+    let positions = text_positions(&pdf.current_page_stream()[start..]);
+    // Courier characters are all 6 units wide at size 10
+    assert_eq!(
+        positions,
+        [(36.0, 20.0), (200.0 - 27.0, 20.0), (364.0 - 36.0, 20.0)]
+    );
+}
+
+#[cfg(feature = "recompress")]
+#[test]
+fn test_recompress_all() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off);
+    for i in 0..3 {
+        pdf.add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 50 }, Alignment::TopLeft, &i.to_string());
+    }
+    pdf.to_bytes();
+    let streams = |pdf: &Pdf| -> Vec<Vec<u8>> {
+        pdf.objects
+            .iter()
+            .filter(|object| object.contents.starts_with(b"<< /Length"))
+            .map(|object| object.contents.clone())
+            .collect()
+    };
+    let uncompressed = streams(&pdf);
+    assert!(uncompressed.iter().all(|s| !contains(s, b"/Filter")));
+
+    pdf.recompress_all(Compression::Best);
+    let compressed = streams(&pdf);
+    assert_eq!(compressed.len(), uncompressed.len());
+    let mut recompressed = 0;
+    for (before, after) in uncompressed.iter().zip(&compressed) {
+        let (decoded, _) = Pdf::decode_stream_object(after).unwrap();
+        assert_eq!(Pdf::decode_stream_object(before).unwrap().0, decoded);
+        if contains(after, b"/Filter [/FlateDecode]") {
+            recompressed += 1;
+        }
+    }
+    assert_eq!(recompressed, 3);
+}
+
+#[cfg(feature = "recompress")]
+#[test]
+fn test_recompress_all_leaves_page_in_progress() {
+    let mut pdf = Pdf::new();
+    pdf.compression(Compression::Off);
+    for i in 0..2 {
+        pdf.add_page(Size {
+            width: 100,
+            height: 100,
+        })
+        .draw_text(Point { x: 10, y: 50 }, Alignment::TopLeft, &i.to_string());
+    }
+    pdf.recompress_all(Compression::Best);
+    let bytes = pdf.to_bytes();
+    let compressed = bytes
+        .windows(b"/Filter [/FlateDecode]".len())
+        .filter(|w| w == b"/Filter [/FlateDecode]")
+        .count();
+    assert_eq!(compressed, 1);
+}
+
+#[test]
+fn test_draw_hyperlink() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 100,
+    });
+    pdf.font(Font::Helvetica, 10.0);
+    let start = pdf.current_page_stream().len();
+    pdf.draw_hyperlink(
+        Point { x: 20, y: 50 },
+        "Docs",
+        "https://docs.rs/pdfpdf?q=(a)",
+    );
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    assert!(stream.starts_with("q\n0 0 0.93"), "{}", stream);
+    // The underline is drawn in the link color too, before the color is restored
+    assert!(stream.ends_with(" re f\nQ\n"));
+    assert_eq!(pdf.current_fill_color(), Color::rgb(0, 0, 0));
+
+    let width = pdf.width_of("Docs");
+    let bytes = pdf.to_bytes();
+    let annotation = format!(
+        "<< /Type /Annot /Subtype /Link /Rect [20 47.93 {} 57.18] /Border [0 0 0] /A << /Type \
+         /Action /S /URI /URI (https://docs.rs/pdfpdf?q=\\(a\\)) >> >>",
+        20.0 + width
+    );
+    assert!(contains(&bytes, annotation.as_bytes()));
+    assert!(contains(&bytes, b"/Annots ["));
+}
+
+#[test]
+fn test_draw_hyperlink_on_baseline_grid() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 200,
+        height: 100,
+    })
+    .set_baseline_grid(12.0, 0.0)
+    .font(Font::Helvetica, 10.0)
+    .draw_hyperlink(Point { x: 20, y: 50 }, "Café", "https://example.com/café");
+    assert_eq!(text_positions(&pdf.page_buffer), vec![(20.0, 48.0)]);
+    let width = pdf.width_of("Café");
+    let bytes = pdf.to_bytes();
+    let annotation = format!(
+        "/Rect [20 45.93 {} 55.18] /Border [0 0 0] /A << /Type /Action /S /URI /URI \
+         (https://example.com/caf%C3%A9) >>",
+        20.0 + width
+    );
+    assert!(contains(&bytes, annotation.as_bytes()));
+}
+
+#[test]
+fn test_draw_styled_polyline() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    let points = [
+        Point { x: 10.0, y: 10.0 },
+        Point { x: 50.0, y: 50.0 },
+        Point { x: 90.0, y: 10.0 },
+    ];
+    let red = StrokeStyle {
+        color: Color::rgb(255, 0, 0),
+        width: 2.0,
+    };
+    let blue = StrokeStyle {
+        color: Color::rgb(0, 0, 255),
+        width: 1.0,
+    };
+    let start = pdf.current_page_stream().len();
+    pdf.draw_styled_polyline(&points, &[red, blue]);
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    assert_eq!(
+        stream,
+        "q\n1 0 0 SC\n2 w\n10 10 m\n50 50 l\nS\n0 0 1 SC\n1 w\n50 50 m\n90 10 l\nS\nQ\n"
+    );
+    assert_eq!(pdf.current_stroke_color(), Color::rgb(0, 0, 0));
+
+    // A single style strokes the whole line at once
+    let start = pdf.current_page_stream().len();
+    pdf.draw_styled_polyline(&points, &[red]);
+    let stream = String::from_utf8_lossy(&pdf.current_page_stream()[start..]).into_owned();
+    assert_eq!(stream.matches("S\n").count(), 1);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_style_carries_over_to_new_page() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.set_color(Color::rgb(0, 128, 0)).set_line_width(3);
+    pdf.add_page_same_size();
+    let start = pdf.current_page_stream().len();
+    pdf.draw_rectangle_filled(
+        Point { x: 10, y: 10 },
+        Size {
+            width: 20,
+            height: 20,
+        },
+    );
+    assert!(pdf.current_page_stream()[..start]
+        .ends_with(b"3 w\n0 0.5019607843 0 SC\n0 0.5019607843 0 rg\n"));
+    assert_eq!(pdf.current_fill_color(), Color::rgb(0, 128, 0));
+    assert_eq!(pdf.current_line_width(), 3.0);
+
+    // Fill and stroke colors set separately are kept separately
+    pdf.set_fill_color(Color::rgb(255, 0, 0));
+    pdf.add_page_same_size();
+    assert!(pdf
+        .current_page_stream()
+        .ends_with(b"3 w\n0 0.5019607843 0 SC\n1 0 0 rg\n"));
+}
+
+#[test]
+fn test_discarded_page_style_not_carried_over() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .set_color(Color::rgb(0, 128, 0));
+    pdf.add_page_same_size()
+        .set_color(Color::rgb(255, 0, 0))
+        .discard_current_page();
+    pdf.add_page_same_size();
+    assert_eq!(pdf.current_fill_color(), Color::rgb(0, 128, 0));
+    assert!(pdf
+        .current_page_stream()
+        .ends_with(b"0 0.5019607843 0 SC\n0 0.5019607843 0 rg\n"));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_style_carries_over_to_parallel_pages() {
+    let mut pdf = Pdf::new();
+    pdf.add_page(Size {
+        width: 100,
+        height: 100,
+    })
+    .set_color(Color::rgb(0, 128, 0));
+    pdf.render_pages_parallel(&[(), ()], |page, ()| {
+        assert_eq!(page.current_fill_color(), Color::rgb(0, 128, 0));
+        page.set_color(Color::rgb(255, 0, 0));
+    });
+    pdf.add_page_same_size();
+    assert_eq!(pdf.current_fill_color(), Color::rgb(255, 0, 0));
 }
@@ -0,0 +1,150 @@
+//! An LZW encoder for streams written with `/LZWDecode`.
+//!
+//! Codes are packed most significant bit first and grow from 9 to 12 bits, switching one code
+//! early as required by the default `/EarlyChange 1`.
+
+use std::collections::HashMap;
+
+const CLEAR_TABLE: u16 = 256;
+const END_OF_DATA: u16 = 257;
+const FIRST_CODE: u16 = 258;
+/// The table is cleared before it needs a code wider than 12 bits
+const MAX_CODE: u16 = 4095;
+
+/// Packs codes of varying width into bytes
+struct BitWriter {
+    out: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+#[allow(clippy::cast_possible_truncation)] // Only the low byte of the buffer is written each time
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u32) {
+        self.buffer = (self.buffer << width) | u32::from(code);
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.out.push((self.buffer >> self.bits) as u8);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.out.push((self.buffer << (8 - self.bits)) as u8);
+        }
+        self.out
+    }
+}
+
+/// Compress `data`, starting with a clear-table code and ending with an end-of-data code
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter {
+        out: Vec::with_capacity(data.len() / 2),
+        buffer: 0,
+        bits: 0,
+    };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = FIRST_CODE;
+    let mut width = 9;
+    writer.write(CLEAR_TABLE, width);
+
+    let mut current: Option<u16> = None;
+    for &byte in data {
+        let Some(prefix) = current else {
+            current = Some(u16::from(byte));
+            continue;
+        };
+        if let Some(&code) = table.get(&(prefix, byte)) {
+            current = Some(code);
+            continue;
+        }
+        writer.write(prefix, width);
+        table.insert((prefix, byte), next_code);
+        next_code += 1;
+        // The decoder adds each entry one code later than we do, so it widens its codes once the
+        // table it has built is one short of the next power of two
+        if next_code == MAX_CODE {
+            writer.write(CLEAR_TABLE, width);
+            table.clear();
+            next_code = FIRST_CODE;
+            width = 9;
+        } else if next_code >= 1 << width {
+            width += 1;
+        }
+        current = Some(u16::from(byte));
+    }
+
+    if let Some(code) = current {
+        writer.write(code, width);
+        if next_code + 1 >= 1 << width {
+            width += 1;
+        }
+    }
+    writer.write(END_OF_DATA, width);
+    writer.finish()
+}
+
+/// Decode `data` the way a PDF reader does, or return `None` if it is not valid LZW data
+#[cfg(any(test, feature = "recompress"))]
+#[allow(clippy::cast_possible_truncation)]
+pub fn decompress(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut table: Vec<Vec<u8>> = Vec::new();
+    let mut width = 9;
+    let mut previous: Option<Vec<u8>> = None;
+    let mut buffer = 0_u32;
+    let mut bits = 0;
+    let mut bytes = data.iter();
+    loop {
+        while bits < width {
+            buffer = (buffer << 8) | u32::from(*bytes.next()?);
+            bits += 8;
+        }
+        bits -= width;
+        let code = ((buffer >> bits) & ((1 << width) - 1)) as u16;
+        match code {
+            CLEAR_TABLE => {
+                table = (0..=255).map(|b| vec![b]).collect();
+                table.extend([Vec::new(), Vec::new()]);
+                width = 9;
+                previous = None;
+                continue;
+            }
+            END_OF_DATA => break,
+            _ => {}
+        }
+        let entry = match (table.get(usize::from(code)), &previous) {
+            (Some(entry), _) => entry.clone(),
+            (None, Some(previous)) => {
+                let mut entry = previous.clone();
+                entry.push(previous[0]);
+                entry
+            }
+            (None, None) => return None,
+        };
+        if let Some(mut previous) = previous {
+            previous.push(entry[0]);
+            table.push(previous);
+            if table.len() + 1 >= 1 << width {
+                width = (width + 1).min(12);
+            }
+        }
+        out.extend_from_slice(&entry);
+        previous = Some(entry);
+    }
+    Some(out)
+}
+
+#[test]
+fn test_round_trip() {
+    let short = b"TOBEORNOTTOBEORTOBEORNOT".to_vec();
+    assert_eq!(decompress(&compress(&short)).unwrap(), short);
+    assert_eq!(decompress(&compress(b"")).unwrap(), b"");
+
+    // Long enough to widen the codes all the way and clear the table several times
+    let long: Vec<u8> = (0..100_000_u32)
+        .map(|i| i.wrapping_mul(2_654_435_761).to_be_bytes()[1])
+        .collect();
+    assert_eq!(decompress(&compress(&long)).unwrap(), long);
+}
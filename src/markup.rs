@@ -0,0 +1,122 @@
+//! A small markup language for changing the style of text partway through a line.
+//!
+//! `{color:#f00}`, `{size:14}`, and `{font:Times-Bold}` change the style until the matching `{/}`.
+//! Colors are written as three or six hexadecimal digits, and fonts by their `PostScript` names.
+//! Anything in braces that isn't one of these tags, including a `{/}` with no tag to close, is
+//! drawn as it is written.
+
+use crate::fonts::{self, Font};
+use crate::Color;
+
+/// A change to the style of the text that follows it
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Color(Color),
+    Size(f64),
+    Font(Font),
+}
+
+/// A piece of markup
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token<'a> {
+    Text(&'a str),
+    Open(Change),
+    Close,
+}
+
+/// Split `markup` into text and tags, treating anything that isn't a known tag as text
+pub fn parse(markup: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut depth = 0_usize;
+    let mut rest = markup;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('{') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let Some(len) = rest[start..].find('}') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let (tag, end) = (&rest[start + 1..start + len], start + len + 1);
+        let token = match tag {
+            "/" if depth > 0 => Some(Token::Close),
+            _ => parse_change(tag).map(Token::Open),
+        };
+        match token {
+            Some(token) => {
+                if start > 0 {
+                    tokens.push(Token::Text(&rest[..start]));
+                }
+                match token {
+                    Token::Open(_) => depth += 1,
+                    Token::Close => depth -= 1,
+                    Token::Text(_) => {}
+                }
+                tokens.push(token);
+            }
+            None => tokens.push(Token::Text(&rest[..end])),
+        }
+        rest = &rest[end..];
+    }
+    tokens
+}
+
+fn parse_change(tag: &str) -> Option<Change> {
+    let (name, value) = tag.split_once(':')?;
+    match name {
+        "color" => parse_color(value).map(Change::Color),
+        "size" => value
+            .parse()
+            .ok()
+            .filter(|size: &f64| *size > 0.0)
+            .map(Change::Size),
+        "font" => fonts::font_from_name(value).map(Change::Font),
+        _ => None,
+    }
+}
+
+/// Parse `#rgb` or `#rrggbb`
+fn parse_color(value: &str) -> Option<Color> {
+    let digits = value.strip_prefix('#')?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let component = |range: std::ops::Range<usize>| u8::from_str_radix(&digits[range], 16).ok();
+    match digits.len() {
+        3 => {
+            let short = |i: usize| component(i..i + 1).map(|c| c * 17);
+            Some(Color::rgb(short(0)?, short(1)?, short(2)?))
+        }
+        6 => Some(Color::rgb(
+            component(0..2)?,
+            component(2..4)?,
+            component(4..6)?,
+        )),
+        _ => None,
+    }
+}
+
+#[test]
+#[allow(clippy::literal_string_with_formatting_args)] // The tags look like format arguments
+fn test_parse() {
+    assert_eq!(
+        parse("a {color:#f00}red{/} {size:x}{/}"),
+        [
+            Token::Text("a "),
+            Token::Open(Change::Color(Color::rgb(255, 0, 0))),
+            Token::Text("red"),
+            Token::Close,
+            Token::Text(" {size:x}"),
+            Token::Text("{/}"),
+        ]
+    );
+    assert_eq!(
+        parse("{font:Courier}{color:#00ff80}"),
+        [
+            Token::Open(Change::Font(Font::Courier)),
+            Token::Open(Change::Color(Color::rgb(0, 255, 128))),
+        ]
+    );
+    assert_eq!(parse("{unclosed"), [Token::Text("{unclosed")]);
+}
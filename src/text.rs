@@ -1,6 +1,9 @@
 #![allow(missing_docs)]
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
 /// Text alignment options
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Alignment {
     TopLeft,
     TopRight,
@@ -12,3 +15,120 @@ pub enum Alignment {
     BottomRight,
     BottomCenter,
 }
+
+/// Horizontal part of an `Alignment`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// Vertical part of an `Alignment`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VAlign {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl Alignment {
+    /// Combine separate horizontal and vertical alignments
+    #[inline]
+    #[must_use]
+    pub fn new(horizontal: HAlign, vertical: VAlign) -> Self {
+        match (vertical, horizontal) {
+            (VAlign::Top, HAlign::Left) => Self::TopLeft,
+            (VAlign::Top, HAlign::Center) => Self::TopCenter,
+            (VAlign::Top, HAlign::Right) => Self::TopRight,
+            (VAlign::Center, HAlign::Left) => Self::CenterLeft,
+            (VAlign::Center, HAlign::Center) => Self::CenterCenter,
+            (VAlign::Center, HAlign::Right) => Self::CenterRight,
+            (VAlign::Bottom, HAlign::Left) => Self::BottomLeft,
+            (VAlign::Bottom, HAlign::Center) => Self::BottomCenter,
+            (VAlign::Bottom, HAlign::Right) => Self::BottomRight,
+        }
+    }
+}
+
+/// How the glyphs of text are painted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextRenderMode {
+    Fill = 0,
+    /// Only the outlines of the glyphs are drawn, using the line width or text outline width
+    Stroke = 1,
+    FillStroke = 2,
+    /// Text is not painted, but can still be selected and searched
+    Invisible = 3,
+}
+
+/// A line drawn along with text by `Pdf::draw_text_decorated`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextDecoration {
+    /// A line below the baseline, at the font's underline position
+    Underline,
+    /// A line through the middle of the lowercase letters
+    StrikeThrough,
+}
+
+/// The error returned when a string is not the name of an `Alignment`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseAlignmentError(String);
+
+impl Display for ParseAlignmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown alignment {:?}, expected a vertical and a horizontal alignment such as \"top-left\"",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseAlignmentError {}
+
+impl FromStr for Alignment {
+    type Err = ParseAlignmentError;
+
+    /// Parse the vertical and horizontal alignment separated by a hyphen, such as `"top-left"`
+    /// or `"center-center"`, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseAlignmentError(s.to_string());
+        let lower = s.to_ascii_lowercase();
+        let (vertical, horizontal) = lower.split_once('-').ok_or_else(error)?;
+        let vertical = match vertical {
+            "top" => VAlign::Top,
+            "center" => VAlign::Center,
+            "bottom" => VAlign::Bottom,
+            _ => return Err(error()),
+        };
+        let horizontal = match horizontal {
+            "left" => HAlign::Left,
+            "center" => HAlign::Center,
+            "right" => HAlign::Right,
+            _ => return Err(error()),
+        };
+        Ok(Self::new(horizontal, vertical))
+    }
+}
+
+#[test]
+fn test_alignment_new() {
+    assert_eq!(
+        Alignment::new(HAlign::Center, VAlign::Top),
+        Alignment::TopCenter
+    );
+    assert_eq!(
+        Alignment::new(HAlign::Right, VAlign::Bottom),
+        Alignment::BottomRight
+    );
+}
+
+#[test]
+fn test_alignment_from_str() {
+    assert_eq!("top-left".parse(), Ok(Alignment::TopLeft));
+    assert_eq!("center-center".parse(), Ok(Alignment::CenterCenter));
+    assert_eq!("Bottom-Right".parse(), Ok(Alignment::BottomRight));
+    assert!("left-top".parse::<Alignment>().is_err());
+    assert!("center".parse::<Alignment>().is_err());
+}
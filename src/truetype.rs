@@ -0,0 +1,804 @@
+//! Reading TrueType fonts, and writing subsets of them which only contain the glyphs a document
+//! uses.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::convert::TryFrom;
+use std::fmt::{self, Write};
+
+/// The error returned when font data is not a TrueType font that can be embedded
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidFontError(&'static str);
+
+impl fmt::Display for InvalidFontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid TrueType font: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidFontError {}
+
+const TRUNCATED: InvalidFontError = InvalidFontError("the data ends in the middle of a table");
+
+fn u16_at(data: &[u8], offset: usize) -> Result<u16, InvalidFontError> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or(TRUNCATED)
+}
+
+fn i16_at(data: &[u8], offset: usize) -> Result<i16, InvalidFontError> {
+    data.get(offset..offset + 2)
+        .map(|b| i16::from_be_bytes([b[0], b[1]]))
+        .ok_or(TRUNCATED)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32, InvalidFontError> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or(TRUNCATED)
+}
+
+/// The tables copied into a subset unchanged, apart from the ones that are rebuilt
+const KEPT_TABLES: [[u8; 4]; 4] = [*b"cvt ", *b"fpgm", *b"prep", *b"OS/2"];
+
+/// A parsed TrueType font
+pub struct TrueTypeFont {
+    data: Vec<u8>,
+    /// The offset and length of each table, by tag
+    tables: BTreeMap<[u8; 4], (usize, usize)>,
+    units_per_em: f64,
+    /// The smallest box enclosing every glyph, in font units
+    pub bbox: [i16; 4],
+    pub ascent: i16,
+    pub descent: i16,
+    pub cap_height: i16,
+    pub italic_angle: f64,
+    pub fixed_pitch: bool,
+    pub postscript_name: String,
+    /// The advance width and left side bearing of each glyph, in font units
+    metrics: Vec<(u16, i16)>,
+    /// Where each glyph starts in the `glyf` table, with one more entry for the end of the last
+    glyph_offsets: Vec<usize>,
+    cmap: HashMap<char, u16>,
+}
+
+impl TrueTypeFont {
+    /// Parse the tables needed to draw text with and embed the font in `data`
+    pub fn parse(data: Vec<u8>) -> Result<Self, InvalidFontError> {
+        match u32_at(&data, 0)? {
+            0x0001_0000 | 0x7472_7565 => {}
+            0x4F54_544F => {
+                return Err(InvalidFontError(
+                    "OpenType fonts with CFF outlines are not supported",
+                ))
+            }
+            _ => return Err(InvalidFontError("not a TrueType font")),
+        }
+        let mut tables = BTreeMap::new();
+        for i in 0..usize::from(u16_at(&data, 4)?) {
+            let record = 12 + 16 * i;
+            let tag = data.get(record..record + 4).ok_or(TRUNCATED)?;
+            let offset = u32_at(&data, record + 8)? as usize;
+            let length = u32_at(&data, record + 12)? as usize;
+            if data.len() < offset + length {
+                return Err(TRUNCATED);
+            }
+            tables.insert([tag[0], tag[1], tag[2], tag[3]], (offset, length));
+        }
+        let table = |tag: &[u8; 4]| -> Result<&[u8], InvalidFontError> {
+            let &(offset, length) = tables
+                .get(tag)
+                .ok_or(InvalidFontError("a required table is missing"))?;
+            Ok(&data[offset..offset + length])
+        };
+
+        let head = table(b"head")?;
+        let units_per_em = f64::from(u16_at(head, 18)?);
+        let bbox = [
+            i16_at(head, 36)?,
+            i16_at(head, 38)?,
+            i16_at(head, 40)?,
+            i16_at(head, 42)?,
+        ];
+        let long_offsets = i16_at(head, 50)? == 1;
+
+        let hhea = table(b"hhea")?;
+        let (ascent, descent) = (i16_at(hhea, 4)?, i16_at(hhea, 6)?);
+        let num_metrics = usize::from(u16_at(hhea, 34)?);
+        let num_glyphs = usize::from(u16_at(table(b"maxp")?, 4)?);
+        if num_metrics == 0 || num_metrics > num_glyphs {
+            return Err(InvalidFontError("the hhea table has a bad metrics count"));
+        }
+
+        let metrics = parse_hmtx(table(b"hmtx")?, num_metrics, num_glyphs)?;
+        let glyph_offsets = parse_loca(
+            table(b"loca")?,
+            long_offsets,
+            num_glyphs,
+            table(b"glyf")?.len(),
+        )?;
+
+        let cap_height = match table(b"OS/2") {
+            Ok(os2) if u16_at(os2, 0)? >= 2 => i16_at(os2, 88)?,
+            _ => ascent,
+        };
+        let (italic_angle, fixed_pitch) = match table(b"post") {
+            Ok(post) => (
+                f64::from(i16_at(post, 4)?) + f64::from(u16_at(post, 6)?) / 65536.0,
+                u32_at(post, 12)? != 0,
+            ),
+            Err(_) => (0.0, false),
+        };
+        let postscript_name = match table(b"name") {
+            Ok(name) => postscript_name(name)?,
+            Err(_) => None,
+        }
+        .unwrap_or_else(|| "EmbeddedFont".to_string());
+        let cmap = parse_cmap(table(b"cmap")?, num_glyphs)?;
+
+        Ok(Self {
+            data,
+            tables,
+            units_per_em,
+            bbox,
+            ascent,
+            descent,
+            cap_height,
+            italic_angle,
+            fixed_pitch,
+            postscript_name,
+            metrics,
+            glyph_offsets,
+            cmap,
+        })
+    }
+
+    /// The complete font program
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn table(&self, tag: [u8; 4]) -> Option<&[u8]> {
+        self.tables
+            .get(&tag)
+            .map(|&(offset, length)| &self.data[offset..offset + length])
+    }
+
+    /// Convert a length in font units to thousandths of the font size, the unit PDF uses
+    pub fn scale(&self, units: i16) -> f64 {
+        f64::from(units) * 1000.0 / self.units_per_em
+    }
+
+    /// The glyph which draws `c`, which is glyph 0 (`.notdef`) if the font has none
+    pub fn glyph_id(&self, c: char) -> u16 {
+        self.cmap.get(&c).copied().unwrap_or(0)
+    }
+
+    /// The advance width of `glyph` in thousandths of the font size
+    pub fn advance(&self, glyph: u16) -> f64 {
+        let advance = self.metrics.get(usize::from(glyph)).map_or(0, |m| m.0);
+        f64::from(advance) * 1000.0 / self.units_per_em
+    }
+
+    /// The outline data of `glyph`
+    fn glyph(&self, glyph: u16) -> &[u8] {
+        let glyf = self.table(*b"glyf").unwrap_or_default();
+        let g = usize::from(glyph);
+        &glyf[self.glyph_offsets[g]..self.glyph_offsets[g + 1].max(self.glyph_offsets[g])]
+    }
+
+    /// Build a font which only contains the glyphs in `used` and the glyphs they are built from,
+    /// numbered in order after `.notdef`, with a cmap for the characters they were drawn with
+    /// Returns the font program and the new number of each glyph in `used`.
+    pub fn subset(&self, used: &BTreeMap<u16, char>) -> (Vec<u8>, BTreeMap<u16, u16>) {
+        let mut kept: BTreeSet<u16> = used.keys().copied().collect();
+        kept.insert(0);
+        let mut pending: Vec<u16> = kept.iter().copied().collect();
+        while let Some(glyph) = pending.pop() {
+            for (_, component) in components(self.glyph(glyph)) {
+                if usize::from(component) < self.metrics.len() && kept.insert(component) {
+                    pending.push(component);
+                }
+            }
+        }
+        let new_ids: BTreeMap<u16, u16> = kept
+            .iter()
+            .enumerate()
+            .filter_map(|(new, &old)| Some((old, u16::try_from(new).ok()?)))
+            .collect();
+
+        let mut glyf = Vec::new();
+        let mut loca = Vec::new();
+        let mut hmtx = Vec::new();
+        for &old in &kept {
+            loca.extend(u32::try_from(glyf.len()).unwrap_or(u32::MAX).to_be_bytes());
+            let mut glyph = self.glyph(old).to_vec();
+            for (offset, component) in components(&glyph) {
+                let new = new_ids.get(&component).copied().unwrap_or(0);
+                glyph[offset..offset + 2].copy_from_slice(&new.to_be_bytes());
+            }
+            glyf.extend(glyph);
+            glyf.resize(padded(glyf.len()), 0);
+            let (advance, lsb) = self.metrics[usize::from(old)];
+            hmtx.extend(advance.to_be_bytes());
+            hmtx.extend(lsb.to_be_bytes());
+        }
+        loca.extend(u32::try_from(glyf.len()).unwrap_or(u32::MAX).to_be_bytes());
+        let num_glyphs = u16::try_from(kept.len()).unwrap_or(u16::MAX).to_be_bytes();
+
+        let mut head = self.table(*b"head").unwrap_or_default().to_vec();
+        // The checksum adjustment is filled in once the whole font is assembled
+        head[8..12].copy_from_slice(&[0; 4]);
+        head[50..52].copy_from_slice(&1_i16.to_be_bytes());
+        let mut hhea = self.table(*b"hhea").unwrap_or_default().to_vec();
+        hhea[34..36].copy_from_slice(&num_glyphs);
+        let mut maxp = self.table(*b"maxp").unwrap_or_default().to_vec();
+        maxp[4..6].copy_from_slice(&num_glyphs);
+
+        let mut tables = vec![
+            (*b"cmap", cmap_table(used, &new_ids)),
+            (*b"glyf", glyf),
+            (*b"head", head),
+            (*b"hhea", hhea),
+            (*b"hmtx", hmtx),
+            (*b"loca", loca),
+            (*b"maxp", maxp),
+        ];
+        if let Some(post) = self.table(*b"post").and_then(|post| post.get(..32)) {
+            // Version 3 has no glyph names, which would refer to the old glyph numbers
+            let mut post = post.to_vec();
+            post[..4].copy_from_slice(&0x0003_0000_u32.to_be_bytes());
+            tables.push((*b"post", post));
+        }
+        for tag in KEPT_TABLES {
+            if let Some(table) = self.table(tag) {
+                tables.push((tag, table.to_vec()));
+            }
+        }
+        (assemble(tables), new_ids)
+    }
+}
+
+/// The offset and glyph number of each component of a composite glyph
+fn components(glyph: &[u8]) -> Vec<(usize, u16)> {
+    const ARGS_ARE_WORDS: u16 = 0x0001;
+    const HAVE_SCALE: u16 = 0x0008;
+    const MORE_COMPONENTS: u16 = 0x0020;
+    const HAVE_X_AND_Y_SCALE: u16 = 0x0040;
+    const HAVE_TWO_BY_TWO: u16 = 0x0080;
+
+    let mut components = Vec::new();
+    if !matches!(i16_at(glyph, 0), Ok(contours) if contours < 0) {
+        return components;
+    }
+    let mut pos = 10;
+    while let (Ok(flags), Ok(component)) = (u16_at(glyph, pos), u16_at(glyph, pos + 2)) {
+        components.push((pos + 2, component));
+        pos += if flags & ARGS_ARE_WORDS == 0 { 6 } else { 8 };
+        if flags & HAVE_SCALE != 0 {
+            pos += 2;
+        } else if flags & HAVE_X_AND_Y_SCALE != 0 {
+            pos += 4;
+        } else if flags & HAVE_TWO_BY_TWO != 0 {
+            pos += 8;
+        }
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+    components
+}
+
+/// Read the advance width and left side bearing of every glyph from `hmtx`
+fn parse_hmtx(
+    hmtx: &[u8],
+    num_metrics: usize,
+    num_glyphs: usize,
+) -> Result<Vec<(u16, i16)>, InvalidFontError> {
+    let mut metrics: Vec<(u16, i16)> = Vec::with_capacity(num_glyphs);
+    for g in 0..num_glyphs {
+        metrics.push(if g < num_metrics {
+            (u16_at(hmtx, 4 * g)?, i16_at(hmtx, 4 * g + 2)?)
+        } else {
+            // Later glyphs share the last advance width
+            (
+                metrics[num_metrics - 1].0,
+                i16_at(hmtx, 4 * num_metrics + 2 * (g - num_metrics))?,
+            )
+        });
+    }
+    Ok(metrics)
+}
+
+/// Read where each glyph starts in a `glyf` table `glyf_length` long from `loca`
+fn parse_loca(
+    loca: &[u8],
+    long_offsets: bool,
+    num_glyphs: usize,
+    glyf_length: usize,
+) -> Result<Vec<usize>, InvalidFontError> {
+    (0..=num_glyphs)
+        .map(|g| {
+            let offset = if long_offsets {
+                u32_at(loca, 4 * g)? as usize
+            } else {
+                2 * usize::from(u16_at(loca, 2 * g)?)
+            };
+            if offset > glyf_length {
+                return Err(InvalidFontError("a glyph lies outside of the glyf table"));
+            }
+            Ok(offset)
+        })
+        .collect()
+}
+
+/// Read the character to glyph mapping from a Unicode subtable of `cmap`, leaving out glyphs
+/// past the `num_glyphs` in the font
+fn parse_cmap(cmap: &[u8], num_glyphs: usize) -> Result<HashMap<char, u16>, InvalidFontError> {
+    let mut best = None;
+    for i in 0..usize::from(u16_at(cmap, 2)?) {
+        let record = 4 + 8 * i;
+        let platform = u16_at(cmap, record)?;
+        let encoding = u16_at(cmap, record + 2)?;
+        let offset = u32_at(cmap, record + 4)? as usize;
+        let format = u16_at(cmap, offset)?;
+        // Full Unicode subtables are preferred over ones for only the Basic Multilingual Plane
+        let rank = match (platform, encoding, format) {
+            (3, 10, 12) | (0, _, 12) => 2,
+            (3, 1, 4) | (0, _, 4) => 1,
+            _ => continue,
+        };
+        if best.map_or(true, |(best_rank, _)| rank > best_rank) {
+            best = Some((rank, offset));
+        }
+    }
+    let (_, offset) = best.ok_or(InvalidFontError("there is no Unicode cmap subtable"))?;
+    let subtable = &cmap[offset..];
+
+    let mut map = HashMap::new();
+    if u16_at(subtable, 0)? == 12 {
+        for group in 0..u32_at(subtable, 12)? as usize {
+            let start = u32_at(subtable, 16 + 12 * group)?;
+            // Nothing past the last Unicode code point can be drawn
+            let end = u32_at(subtable, 20 + 12 * group)?.min(0x10_FFFF);
+            let glyph = u32_at(subtable, 24 + 12 * group)?;
+            if glyph.checked_add(end.saturating_sub(start)).is_none() {
+                continue;
+            }
+            for code in start..=end {
+                if let (Some(c), Ok(glyph)) =
+                    (char::from_u32(code), u16::try_from(glyph + code - start))
+                {
+                    if usize::from(glyph) < num_glyphs {
+                        map.insert(c, glyph);
+                    }
+                }
+            }
+        }
+        return Ok(map);
+    }
+
+    let segments = usize::from(u16_at(subtable, 6)? / 2);
+    let ends = 14;
+    let starts = ends + 2 * segments + 2;
+    let deltas = starts + 2 * segments;
+    let range_offsets = deltas + 2 * segments;
+    for s in 0..segments {
+        let end = u16_at(subtable, ends + 2 * s)?;
+        let start = u16_at(subtable, starts + 2 * s)?;
+        let delta = u16_at(subtable, deltas + 2 * s)?;
+        let range_offset = usize::from(u16_at(subtable, range_offsets + 2 * s)?);
+        for code in start..=end {
+            if code == 0xFFFF {
+                break;
+            }
+            let glyph = if range_offset == 0 {
+                code.wrapping_add(delta)
+            } else {
+                // The offset is relative to where it is stored
+                let index = range_offsets + 2 * s + range_offset + 2 * usize::from(code - start);
+                match u16_at(subtable, index)? {
+                    0 => 0,
+                    glyph => glyph.wrapping_add(delta),
+                }
+            };
+            if let Some(c) = char::from_u32(u32::from(code)) {
+                if glyph != 0 && usize::from(glyph) < num_glyphs {
+                    map.insert(c, glyph);
+                }
+            }
+        }
+    }
+    Ok(map)
+}
+
+/// Find the `PostScript` name of the font in the `name` table, leaving out any characters that
+/// are not allowed in a PDF name
+fn postscript_name(name: &[u8]) -> Result<Option<String>, InvalidFontError> {
+    let strings = usize::from(u16_at(name, 4)?);
+    for i in 0..usize::from(u16_at(name, 2)?) {
+        let record = 6 + 12 * i;
+        if u16_at(name, record + 6)? != 6 {
+            continue;
+        }
+        let platform = u16_at(name, record)?;
+        let length = usize::from(u16_at(name, record + 8)?);
+        let offset = strings + usize::from(u16_at(name, record + 10)?);
+        let bytes = name.get(offset..offset + length).ok_or(TRUNCATED)?;
+        let text: String = match platform {
+            // Windows and Unicode names are UTF-16
+            0 | 3 => char::decode_utf16(
+                bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]])),
+            )
+            .filter_map(Result::ok)
+            .collect(),
+            _ => bytes.iter().copied().map(char::from).collect(),
+        };
+        let text: String = text
+            .chars()
+            .filter(|c| c.is_ascii_graphic() && !"()<>[]{}/%#".contains(*c))
+            .collect();
+        if !text.is_empty() {
+            return Ok(Some(text));
+        }
+    }
+    Ok(None)
+}
+
+/// Build a cmap table which maps the characters in `used` to the new glyph numbers, with a
+/// format 4 subtable for the Basic Multilingual Plane and a format 12 subtable for all of Unicode
+fn cmap_table(used: &BTreeMap<u16, char>, new_ids: &BTreeMap<u16, u16>) -> Vec<u8> {
+    let mut mappings: Vec<(u32, u16)> = used
+        .iter()
+        .map(|(glyph, &c)| (u32::from(c), new_ids[glyph]))
+        .collect();
+    mappings.sort_unstable();
+    mappings.dedup_by_key(|&mut (code, _)| code);
+
+    let mut subtables = Vec::new();
+    if let Some(subtable) = cmap_format_4(&mappings) {
+        subtables.push((1_u16, subtable));
+    }
+    subtables.push((10, cmap_format_12(&mappings)));
+    let mut cmap = vec![0, 0];
+    cmap.extend(
+        u16::try_from(subtables.len())
+            .unwrap_or(u16::MAX)
+            .to_be_bytes(),
+    );
+    let mut offset = 4 + 8 * subtables.len();
+    for (encoding, subtable) in &subtables {
+        cmap.extend(3_u16.to_be_bytes());
+        cmap.extend(encoding.to_be_bytes());
+        cmap.extend(u32::try_from(offset).unwrap_or(u32::MAX).to_be_bytes());
+        offset += subtable.len();
+    }
+    for (_, subtable) in subtables {
+        cmap.extend(subtable);
+    }
+    cmap
+}
+
+/// A format 4 subtable for the sorted `mappings` of characters in the Basic Multilingual Plane,
+/// unless there are too many segments for its 16-bit length
+fn cmap_format_4(mappings: &[(u32, u16)]) -> Option<Vec<u8>> {
+    // Runs of characters drawn with consecutive glyphs share a segment, and the last segment must
+    // end at 0xFFFF
+    let mut segments: Vec<(u16, u16, u16)> = Vec::new();
+    for &(code, glyph) in mappings {
+        let code = match u16::try_from(code) {
+            Ok(code) if code != 0xFFFF => code,
+            _ => continue,
+        };
+        let delta = glyph.wrapping_sub(code);
+        match segments.last_mut() {
+            Some((_, end, last_delta)) if *end + 1 == code && *last_delta == delta => *end = code,
+            _ => segments.push((code, code, delta)),
+        }
+    }
+    segments.push((0xFFFF, 0xFFFF, 1));
+    let count = u16::try_from(segments.len())
+        .ok()
+        .filter(|&count| count <= 8000)?;
+
+    let entry_selector = 15 - u16::try_from(count.leading_zeros()).unwrap_or(15);
+    let search_range = 2 << entry_selector;
+    let mut subtable = Vec::new();
+    for value in [
+        4,
+        16 + 8 * count,
+        0,
+        2 * count,
+        search_range,
+        entry_selector,
+        2 * count - search_range,
+    ] {
+        subtable.extend(value.to_be_bytes());
+    }
+    for &(_, end, _) in &segments {
+        subtable.extend(end.to_be_bytes());
+    }
+    subtable.extend([0, 0]);
+    for &(start, _, _) in &segments {
+        subtable.extend(start.to_be_bytes());
+    }
+    for &(_, _, delta) in &segments {
+        subtable.extend(delta.to_be_bytes());
+    }
+    for _ in &segments {
+        subtable.extend([0, 0]);
+    }
+    Some(subtable)
+}
+
+/// A format 12 subtable for the sorted `mappings`
+fn cmap_format_12(mappings: &[(u32, u16)]) -> Vec<u8> {
+    // Runs of characters drawn with consecutive glyphs share a group
+    let mut groups: Vec<[u32; 3]> = Vec::new();
+    for &(code, glyph) in mappings {
+        let glyph = u32::from(glyph);
+        match groups.last_mut() {
+            Some([start, end, first]) if *end + 1 == code && *first + code - *start == glyph => {
+                *end = code;
+            }
+            _ => groups.push([code, code, glyph]),
+        }
+    }
+    let count = u32::try_from(groups.len()).unwrap_or(u32::MAX);
+    let mut subtable = vec![0, 12, 0, 0];
+    for value in [16 + 12 * count, 0, count] {
+        subtable.extend(value.to_be_bytes());
+    }
+    for group in groups {
+        for value in group {
+            subtable.extend(value.to_be_bytes());
+        }
+    }
+    subtable
+}
+
+/// `len` rounded up to a whole number of the 32-bit words that tables are padded to
+const fn padded(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// The sum of `data` as big-endian 32-bit words, padded with zeros
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0, |sum, word| {
+        let mut bytes = [0; 4];
+        bytes[..word.len()].copy_from_slice(word);
+        sum.wrapping_add(u32::from_be_bytes(bytes))
+    })
+}
+
+/// Write a font file with the tables in `tables`, which must include `head`
+fn assemble(mut tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    tables.sort_by_key(|(tag, _)| *tag);
+    let count = u16::try_from(tables.len()).unwrap_or(u16::MAX);
+    let entry_selector = 15 - u16::try_from(count.leading_zeros()).unwrap_or(15);
+    let search_range = 16 << entry_selector;
+
+    let mut font = 0x0001_0000_u32.to_be_bytes().to_vec();
+    for value in [
+        count,
+        search_range,
+        entry_selector,
+        16 * count - search_range,
+    ] {
+        font.extend(value.to_be_bytes());
+    }
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = 0;
+    for (tag, table) in &tables {
+        if tag == b"head" {
+            head_offset = offset;
+        }
+        font.extend(tag);
+        font.extend(checksum(table).to_be_bytes());
+        font.extend(u32::try_from(offset).unwrap_or(u32::MAX).to_be_bytes());
+        font.extend(u32::try_from(table.len()).unwrap_or(u32::MAX).to_be_bytes());
+        offset += padded(table.len());
+    }
+    for (_, table) in tables {
+        font.extend(&table);
+        font.resize(padded(font.len()), 0);
+    }
+    let adjustment = 0xB1B0_AFBA_u32.wrapping_sub(checksum(&font));
+    font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    font
+}
+
+/// The `/W` array giving the width of each glyph in `used`, numbered by their original glyph ids
+pub fn width_array(font: &TrueTypeFont, used: &BTreeMap<u16, char>) -> String {
+    let mut widths = String::from("[");
+    let mut previous = None;
+    for &glyph in used.keys() {
+        if previous.map_or(true, |previous: u16| previous + 1 != glyph) {
+            if previous.is_some() {
+                widths.push(']');
+            }
+            let _ = write!(widths, " {glyph} [");
+        } else {
+            widths.push(' ');
+        }
+        let _ = write!(widths, "{}", font.advance(glyph).round());
+        previous = Some(glyph);
+    }
+    if previous.is_some() {
+        widths.push(']');
+    }
+    widths.push_str(" ]");
+    widths
+}
+
+/// Build a `/ToUnicode` character map from 2-byte glyph ids to the characters they were drawn for
+pub fn to_unicode_cmap(used: &BTreeMap<u16, char>) -> Vec<u8> {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n\
+         12 dict begin\n\
+         begincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n\
+         /CMapType 2 def\n\
+         1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let mappings: Vec<(&u16, &char)> = used.iter().collect();
+    // A bfchar block may contain at most 100 entries
+    for chunk in mappings.chunks(100) {
+        let _ = writeln!(cmap, "{} beginbfchar", chunk.len());
+        for (glyph, c) in chunk {
+            let _ = write!(cmap, "<{glyph:04X}> <");
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                let _ = write!(cmap, "{unit:04X}");
+            }
+            cmap.push_str(">\n");
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str(
+        "endcmap\n\
+         CMapName currentdict /CMap defineresource pop\n\
+         end\n\
+         end\n",
+    );
+    cmap.into_bytes()
+}
+
+/// Build a small font with a glyph for each letter from 'A' to 'Z', drawn as a square with a
+/// different number of points, plus a composite glyph for 'Å' made of 'A' and 'O'
+#[cfg(test)]
+pub fn test_font() -> Vec<u8> {
+    let letters = 26_u16;
+    let num_glyphs = letters + 2;
+    let mut glyf = Vec::new();
+    let mut loca = Vec::new();
+    let mut hmtx = Vec::new();
+    // Glyph 0 is an empty .notdef
+    loca.extend(0_u32.to_be_bytes());
+    hmtx.extend([500_u16.to_be_bytes(), 0_u16.to_be_bytes()].concat());
+    for g in 0..letters {
+        loca.extend(u32::try_from(glyf.len()).unwrap().to_be_bytes());
+        let points = 4 + g;
+        let mut glyph = Vec::new();
+        for value in [1_i16, 0, 0, 600, 700] {
+            glyph.extend(value.to_be_bytes());
+        }
+        glyph.extend((points - 1).to_be_bytes());
+        glyph.extend(0_u16.to_be_bytes());
+        // Every point is on the curve with 2-byte coordinates
+        glyph.extend(std::iter::repeat(1).take(usize::from(points)));
+        for _ in 0..2 * points {
+            glyph.extend(10_i16.to_be_bytes());
+        }
+        glyf.extend(glyph);
+        glyf.resize(glyf.len() + glyf.len() % 2, 0);
+        hmtx.extend([600_u16.to_be_bytes(), 0_u16.to_be_bytes()].concat());
+    }
+    // 'Å' is glyph 27, made of 'A' (glyph 1) and 'O' (glyph 15)
+    loca.extend(u32::try_from(glyf.len()).unwrap().to_be_bytes());
+    for value in [-1_i16, 0, 0, 600, 900] {
+        glyf.extend(value.to_be_bytes());
+    }
+    for (flags, component) in [(0x0020_u16, 1_u16), (0, 15)] {
+        glyf.extend(flags.to_be_bytes());
+        glyf.extend(component.to_be_bytes());
+        glyf.extend([0, 0]);
+    }
+    hmtx.extend([600_u16.to_be_bytes(), 0_u16.to_be_bytes()].concat());
+    loca.extend(u32::try_from(glyf.len()).unwrap().to_be_bytes());
+
+    let mut head = vec![0; 54];
+    head[..4].copy_from_slice(&0x0001_0000_u32.to_be_bytes());
+    head[12..16].copy_from_slice(&0x5F0F_3CF5_u32.to_be_bytes());
+    head[18..20].copy_from_slice(&1000_u16.to_be_bytes());
+    head[40..42].copy_from_slice(&600_i16.to_be_bytes());
+    head[42..44].copy_from_slice(&900_i16.to_be_bytes());
+    head[50..52].copy_from_slice(&1_i16.to_be_bytes());
+    let mut hhea = vec![0; 36];
+    hhea[4..6].copy_from_slice(&800_i16.to_be_bytes());
+    hhea[6..8].copy_from_slice(&(-200_i16).to_be_bytes());
+    hhea[34..36].copy_from_slice(&num_glyphs.to_be_bytes());
+    let mut maxp = vec![0; 6];
+    maxp[..4].copy_from_slice(&0x0000_5000_u32.to_be_bytes());
+    maxp[4..6].copy_from_slice(&num_glyphs.to_be_bytes());
+
+    let mut used = BTreeMap::new();
+    for g in 0..letters {
+        used.insert(g + 1, char::from(b'A' + u8::try_from(g).unwrap()));
+    }
+    used.insert(27, 'Å');
+    let identity = (0..num_glyphs).map(|g| (g, g)).collect();
+    // Padding which a subset leaves out, like the hinting programs of a real font
+    let name = b"\x00\x00\x00\x01\x00\x12\x00\x03\x00\x01\x04\x09\x00\x06\x00\x0C\x00\x00\x00T\x00e\x00s\x00t\x00\x20\x00A".to_vec();
+    assemble(vec![
+        (*b"cmap", cmap_table(&used, &identity)),
+        (*b"glyf", glyf),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx),
+        (*b"loca", loca),
+        (*b"maxp", maxp),
+        (*b"name", name),
+    ])
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_parse() {
+    let font = TrueTypeFont::parse(test_font()).unwrap();
+    assert_eq!(font.glyph_id('A'), 1);
+    assert_eq!(font.glyph_id('Z'), 26);
+    assert_eq!(font.glyph_id('Å'), 27);
+    assert_eq!(font.glyph_id('a'), 0);
+    assert_eq!(font.advance(1), 600.0);
+    assert_eq!(font.postscript_name, "TestA");
+    assert_eq!((font.ascent, font.descent), (800, -200));
+    assert_eq!(components(font.glyph(27)), [(12, 1), (18, 15)]);
+    assert!(TrueTypeFont::parse(b"OTTO\x00\x00".to_vec()).is_err());
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_subset() {
+    let font = TrueTypeFont::parse(test_font()).unwrap();
+    let used: BTreeMap<u16, char> = [(3, 'C'), (27, 'Å')].iter().copied().collect();
+    let (data, new_ids) = font.subset(&used);
+    // 'A' and 'O' come along as parts of 'Å'
+    assert_eq!(
+        new_ids.into_iter().collect::<Vec<_>>(),
+        [(0, 0), (1, 1), (3, 2), (15, 3), (27, 4)]
+    );
+    assert!(data.len() < font.data().len());
+
+    let subset = TrueTypeFont::parse(data).unwrap();
+    assert_eq!(subset.glyph_id('C'), 2);
+    assert_eq!(subset.glyph_id('Å'), 4);
+    assert_eq!(subset.glyph_id('A'), 0);
+    assert_eq!(subset.glyph(2), font.glyph(3));
+    assert_eq!(components(subset.glyph(4)), [(12, 1), (18, 3)]);
+    assert_eq!(subset.advance(4), 600.0);
+    assert_eq!(checksum(subset.data()), 0xB1B0_AFBA);
+}
+
+#[test]
+fn test_parse_cmap_bounds() {
+    let mut cmap = vec![0, 0, 0, 1, 0, 3, 0, 10, 0, 0, 0, 12];
+    cmap.extend([0, 12, 0, 0, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 4]);
+    // One glyph too many, a group past the last code point, and one whose glyphs overflow
+    for group in [
+        [0x41, 0x43, 1],
+        [0x61, 0x62, 4],
+        [0x10_FFFF, u32::MAX, 2],
+        [0x30, 0x31, u32::MAX],
+    ] {
+        cmap.extend(group.iter().flat_map(|v| v.to_be_bytes()));
+    }
+    let map = parse_cmap(&cmap, 5).unwrap();
+    assert_eq!(map.get(&'C'), Some(&3));
+    assert_eq!(map.get(&'a'), Some(&4));
+    assert_eq!(map.get(&'b'), None);
+    assert_eq!(map.get(&'\u{10FFFF}'), Some(&2));
+    assert_eq!(map.get(&'0'), None);
+    assert_eq!(map.len(), 5);
+}
@@ -1,3 +1,7 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::{Add, Sub};
+
 // tt muncher
 macro_rules! ryu {
     ($buffer:expr, $precision:expr, $($tail:tt)*) => {
@@ -63,7 +67,7 @@ impl Formattable for f64 {
                 out.extend_from_slice(digits);
             }
         } else {
-            out.extend_from_slice(format!("{}", self).as_bytes());
+            out.extend_from_slice(format!("{self}").as_bytes());
         }
     }
 }
@@ -71,10 +75,52 @@ impl Formattable for f64 {
 impl Formattable for &str {
     #[inline]
     fn ryu_format(self, out: &mut Vec<u8>, _: u8, _: &mut ryu::Buffer) {
-        out.extend_from_slice(self.as_bytes())
+        out.extend_from_slice(self.as_bytes());
     }
 }
 
+/// Convert `\r\n` and lone `\r` line endings to `\n`
+pub fn normalize_newlines(text: &str) -> Cow<'_, str> {
+    if text.contains('\r') {
+        Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Hash some bytes into a 128-bit hex string, stable across runs and platforms
+/// This is two FNV-1a hashes with different offset bases, which is plenty for a file identifier
+pub fn content_id(bytes: &[u8]) -> String {
+    let fnv = |mut hash: u64| {
+        for b in bytes {
+            hash ^= u64::from(*b);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        hash
+    };
+    format!(
+        "{:016X}{:016X}",
+        fnv(0xcbf2_9ce4_8422_2325),
+        fnv(0x6c62_272e_07bb_0142)
+    )
+}
+
+/// Construct a `Point<f64, f64>` from any two numbers, e.g. `point!(0, 0.5)`
+#[macro_export]
+macro_rules! point {
+    ($x:expr, $y:expr) => {
+        $crate::Point::new($x, $y)
+    };
+}
+
+/// Construct a `Size<f64, f64>` from any two numbers, e.g. `size!(400, 300.5)`
+#[macro_export]
+macro_rules! size {
+    ($width:expr, $height:expr) => {
+        $crate::Size::new($width, $height)
+    };
+}
+
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug)]
 pub struct Point<X, Y> {
@@ -82,6 +128,66 @@ pub struct Point<X, Y> {
     pub y: Y,
 }
 
+impl Point<f64, f64> {
+    /// Create a point from any two numbers, which need not be the same type
+    #[inline]
+    pub fn new<X, Y>(x: X, y: Y) -> Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+}
+
+impl<X, Y> From<(X, Y)> for Point<X, Y>
+where
+    X: Into<f64>,
+    Y: Into<f64>,
+{
+    #[inline]
+    fn from((x, y): (X, Y)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<X1, Y1, X2, Y2> Add<Point<X2, Y2>> for Point<X1, Y1>
+where
+    X1: Into<f64>,
+    Y1: Into<f64>,
+    X2: Into<f64>,
+    Y2: Into<f64>,
+{
+    type Output = Point<f64, f64>;
+    #[inline]
+    fn add(self, other: Point<X2, Y2>) -> Point<f64, f64> {
+        Point {
+            x: self.x.into() + other.x.into(),
+            y: self.y.into() + other.y.into(),
+        }
+    }
+}
+
+impl<X1, Y1, X2, Y2> Sub<Point<X2, Y2>> for Point<X1, Y1>
+where
+    X1: Into<f64>,
+    Y1: Into<f64>,
+    X2: Into<f64>,
+    Y2: Into<f64>,
+{
+    type Output = Point<f64, f64>;
+    #[inline]
+    fn sub(self, other: Point<X2, Y2>) -> Point<f64, f64> {
+        Point {
+            x: self.x.into() - other.x.into(),
+            y: self.y.into() - other.y.into(),
+        }
+    }
+}
+
 impl<X, Y> Point<X, Y>
 where
     X: Into<f64>,
@@ -102,6 +208,32 @@ pub struct Size<X, Y> {
     pub height: Y,
 }
 
+impl Size<f64, f64> {
+    /// Create a size from any two numbers, which need not be the same type
+    #[inline]
+    pub fn new<W, H>(width: W, height: H) -> Self
+    where
+        W: Into<f64>,
+        H: Into<f64>,
+    {
+        Self {
+            width: width.into(),
+            height: height.into(),
+        }
+    }
+}
+
+impl<W, H> From<(W, H)> for Size<W, H>
+where
+    W: Into<f64>,
+    H: Into<f64>,
+{
+    #[inline]
+    fn from((width, height): (W, H)) -> Self {
+        Self { width, height }
+    }
+}
+
 impl<X, Y> Size<X, Y>
 where
     X: Into<f64>,
@@ -114,3 +246,97 @@ where
         }
     }
 }
+
+/// Replace the object numbers in every `N 0 R` reference in `contents` according to `ids`
+pub fn remap_references(contents: &[u8], ids: &HashMap<usize, usize>) -> Vec<u8> {
+    let mut remapped = Vec::with_capacity(contents.len());
+    let mut i = 0;
+    while i < contents.len() {
+        let starts_number =
+            contents[i].is_ascii_digit() && (i == 0 || !contents[i - 1].is_ascii_digit());
+        if starts_number {
+            let len = contents[i..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            let end = i + len;
+            if contents[end..].starts_with(b" 0 R") {
+                let id: Option<usize> = std::str::from_utf8(&contents[i..end])
+                    .ok()
+                    .and_then(|n| n.parse().ok());
+                if let Some(new_id) = id.and_then(|id| ids.get(&id)) {
+                    remapped.extend(new_id.to_string().bytes());
+                    i = end;
+                    continue;
+                }
+            }
+        }
+        remapped.push(contents[i]);
+        i += 1;
+    }
+    remapped
+}
+
+/// The bottom-left corner and size of the rectangle with opposite corners `a` and `b`
+pub fn corners_to_rect(
+    a: Point<f64, f64>,
+    b: Point<f64, f64>,
+) -> (Point<f64, f64>, Size<f64, f64>) {
+    (
+        Point {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+        },
+        Size {
+            width: (a.x - b.x).abs(),
+            height: (a.y - b.y).abs(),
+        },
+    )
+}
+
+#[test]
+fn test_mixed_point() {
+    let p = Point::new(3_i32, 0.5_f64);
+    assert_eq!((p.x, p.y), (3.0, 0.5));
+
+    let q = point!(1_u8, 2.5_f32)
+        + Point {
+            x: 1_i32,
+            y: 0.5_f64,
+        };
+    assert_eq!((q.x, q.y), (2.0, 3.0));
+
+    let d = Point {
+        x: 10_u16,
+        y: 4.0_f64,
+    } - p;
+    assert_eq!((d.x, d.y), (7.0, 3.5));
+
+    let s = size!(400_u32, 300.5_f64);
+    assert_eq!((s.width, s.height), (400.0, 300.5));
+}
+
+#[test]
+fn test_tuple_conversions() {
+    let p: Point<i32, i32> = (10, 20).into();
+    assert_eq!((p.x, p.y), (10, 20));
+    let p: Point<f64, f64> = (1.5, 2.5).into();
+    assert_eq!((p.x, p.y), (1.5, 2.5));
+
+    let s: Size<u32, u32> = (400, 300).into();
+    assert_eq!((s.width, s.height), (400, 300));
+    let s: Size<f64, f32> = (1.5, 2.5_f32).into();
+    assert_eq!((s.width, s.height), (1.5, 2.5));
+}
+
+#[test]
+fn test_remap_references() {
+    let ids: HashMap<usize, usize> = [(3, 10), (4, 11)].iter().copied().collect();
+    assert_eq!(
+        remap_references(
+            b"/Parent 2 0 R /Contents 3 0 R /X0 4 0 R /W 3 /Y 13 0 R",
+            &ids
+        ),
+        b"/Parent 2 0 R /Contents 10 0 R /X0 11 0 R /W 3 /Y 13 0 R".to_vec()
+    );
+}
@@ -0,0 +1,199 @@
+//! A sanity checker for the content streams this crate writes.
+
+use std::fmt::{self, Display};
+
+/// A problem found in a page's content stream by `Pdf::validate`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A `Q` was emitted without a matching `q`
+    UnmatchedRestore,
+    /// The page ends with this many `q` operators that were never restored
+    UnclosedSave(usize),
+    /// An `ET` was emitted outside of a text object
+    UnmatchedEndText,
+    /// A `BT` was emitted inside another text object
+    NestedText,
+    /// The page ends inside a text object
+    UnclosedText,
+    /// A `Tf` references a font that is not in the page's resource dictionary
+    UnknownFont(String),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnmatchedRestore => write!(f, "Q without a matching q"),
+            Self::UnclosedSave(n) => write!(f, "{n} unmatched q at end of page"),
+            Self::UnmatchedEndText => write!(f, "ET outside of a text object"),
+            Self::NestedText => write!(f, "BT inside of a text object"),
+            Self::UnclosedText => write!(f, "text object not closed at end of page"),
+            Self::UnknownFont(name) => write!(f, "reference to unknown font /{name}"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Splits a content stream into operands and operators
+/// Strings, comments, and inline image data are skipped entirely because their contents may look
+/// like operators.
+struct Tokens<'a> {
+    stream: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let s = self.stream;
+        loop {
+            while self.pos < s.len() && s[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            match s.get(self.pos)? {
+                b'%' => {
+                    while self.pos < s.len() && s[self.pos] != b'\n' {
+                        self.pos += 1;
+                    }
+                }
+                b'(' => {
+                    let mut depth = 0;
+                    while self.pos < s.len() {
+                        match s[self.pos] {
+                            b'\\' => self.pos += 1,
+                            b'(' => depth += 1,
+                            b')' => depth -= 1,
+                            _ => {}
+                        }
+                        self.pos += 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                _ => {
+                    let start = self.pos;
+                    while self.pos < s.len()
+                        && !s[self.pos].is_ascii_whitespace()
+                        && s[self.pos] != b'('
+                    {
+                        self.pos += 1;
+                    }
+                    let token = &s[start..self.pos];
+                    if token == b"ID" {
+                        // Binary image data runs until an EI surrounded by whitespace
+                        let end = s[self.pos..]
+                            .windows(4)
+                            .position(|w| {
+                                w[0].is_ascii_whitespace()
+                                    && &w[1..3] == b"EI"
+                                    && w[3].is_ascii_whitespace()
+                            })
+                            .map_or(s.len(), |p| self.pos + p + 3);
+                        self.pos = end;
+                    }
+                    return Some(token);
+                }
+            }
+        }
+    }
+}
+
+/// Check a single uncompressed content stream which has `num_fonts` standard fonts and the
+/// embedded fonts with the indices in `embedded_fonts` in its resources
+pub fn check_stream(
+    stream: &[u8],
+    num_fonts: usize,
+    embedded_fonts: &[usize],
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut save_depth = 0_usize;
+    let mut in_text = false;
+    let mut previous = [&b""[..]; 2];
+
+    for token in (Tokens { stream, pos: 0 }) {
+        match token {
+            b"q" => save_depth += 1,
+            b"Q" => {
+                if save_depth == 0 {
+                    errors.push(ValidationError::UnmatchedRestore);
+                } else {
+                    save_depth -= 1;
+                }
+            }
+            b"BT" => {
+                if in_text {
+                    errors.push(ValidationError::NestedText);
+                }
+                in_text = true;
+            }
+            b"ET" => {
+                if !in_text {
+                    errors.push(ValidationError::UnmatchedEndText);
+                }
+                in_text = false;
+            }
+            b"Tf" => {
+                let name = String::from_utf8_lossy(previous[0].get(1..).unwrap_or_default());
+                let index = |prefix| {
+                    name.strip_prefix(prefix)
+                        .and_then(|index: &str| index.parse::<usize>().ok())
+                };
+                let known = index('F').is_some_and(|index| index < num_fonts)
+                    || index('E').is_some_and(|index| embedded_fonts.contains(&index));
+                if !known {
+                    errors.push(ValidationError::UnknownFont(name.into_owned()));
+                }
+            }
+            _ => {}
+        }
+        previous = [previous[1], token];
+    }
+
+    if save_depth > 0 {
+        errors.push(ValidationError::UnclosedSave(save_depth));
+    }
+    if in_text {
+        errors.push(ValidationError::UnclosedText);
+    }
+    errors
+}
+
+#[test]
+fn test_unbalanced_save() {
+    let mut pdf = crate::Pdf::new();
+    pdf.add_page(crate::Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.page_buffer.extend(b"q\n");
+    assert_eq!(pdf.validate(), Err(vec![ValidationError::UnclosedSave(1)]));
+}
+
+#[test]
+fn test_unmatched_begin_text() {
+    let mut pdf = crate::Pdf::new();
+    pdf.add_page(crate::Size {
+        width: 100,
+        height: 100,
+    });
+    pdf.page_buffer.extend(b"BT\n/F0 12 Tf\n");
+    assert_eq!(pdf.validate(), Err(vec![ValidationError::UnclosedText]));
+}
+
+#[test]
+fn test_valid_page() {
+    let mut pdf = crate::Pdf::new();
+    pdf.add_page(crate::Size {
+        width: 100,
+        height: 100,
+    })
+    .draw_text(
+        crate::Point { x: 10, y: 10 },
+        crate::Alignment::TopLeft,
+        "(q) BT \\",
+    )
+    .draw_dots(&[1.0, 2.0], &[3.0, 4.0]);
+    assert_eq!(pdf.validate(), Ok(()));
+}